@@ -0,0 +1,78 @@
+//! Compares rendering the example front page (from `tests/integration/page.rs`)
+//! into an empty buffer, a buffer pre-sized using `Node::size_hint`,
+//! and a buffer reused from a `RenderPool`.
+//!
+//! Run with `cargo bench --bench render_capacity`.
+#![feature(test)]
+
+extern crate test;
+
+use ::renderx::dom::Node;
+use ::renderx::render::Render;
+use ::renderx::render::RenderPool;
+use ::renderx::rsx;
+use ::test::black_box;
+use ::test::Bencher;
+
+const COPY_TITLE: &str = "Example Page";
+const COPYRIGHT: &str = "Copyright Big Inc 2021";
+
+fn front_page() -> Node {
+    rsx! {
+      <!doctype html>
+      <html lang="en">
+        <head>
+          <title>Example Page</title>
+        </head>
+
+        <body>
+          <header>
+            <h1>{COPY_TITLE}</h1>
+          </header>
+
+          <article>
+          </article>
+
+          <footer>
+            <p class="p p--small">
+              {COPYRIGHT}
+            </p>
+          </footer>
+        </body>
+      </html>
+    }
+}
+
+#[bench]
+fn bench_render_into_empty_buffer(bencher: &mut Bencher) {
+    let page = front_page();
+
+    bencher.iter(|| {
+        let mut render = Render::new();
+        render.render(black_box(&page)).unwrap();
+        black_box(render.as_str().len())
+    });
+}
+
+#[bench]
+fn bench_render_into_sized_buffer(bencher: &mut Bencher) {
+    let page = front_page();
+
+    bencher.iter(|| {
+        let mut render = Render::with_capacity(page.size_hint());
+        render.render(black_box(&page)).unwrap();
+        black_box(render.as_str().len())
+    });
+}
+
+#[bench]
+fn bench_render_into_pooled_buffer(bencher: &mut Bencher) {
+    let page = front_page();
+    let pool = RenderPool::new();
+
+    bencher.iter(|| {
+        let mut render = pool.take();
+        render.render(black_box(&page)).unwrap();
+        black_box(render.as_str().len())
+    });
+}
@@ -0,0 +1,146 @@
+//! Compares the pre-rendered output of `rsx!` for constant markup,
+//! against building the same tree at runtime (as `rsx!` used to do).
+//!
+//! Run with `cargo bench --bench static_render`.
+#![feature(test)]
+
+extern crate test;
+
+use ::renderx::dom::Attribute;
+use ::renderx::dom::AttributeValue;
+use ::renderx::dom::Attributes;
+use ::renderx::dom::Node;
+use ::renderx::render::render;
+use ::renderx::rsx;
+use ::std::alloc::GlobalAlloc;
+use ::std::alloc::Layout;
+use ::std::alloc::System;
+use ::std::cell::Cell;
+use ::test::Bencher;
+
+/// Counts allocations made on the current thread,
+/// so benchmarks running in parallel don't interfere with each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(|count| count.get());
+    f();
+    ALLOCATIONS.with(|count| count.get()) - before
+}
+
+fn static_page() -> Node {
+    rsx! {
+      <article class="post">
+        <header>
+          <h1>Example Page</h1>
+        </header>
+        <p class="p p--small">Hello world!</p>
+        <hr />
+      </article>
+    }
+}
+
+/// This is the same page as `static_page`,
+/// built the way `rsx!` builds it when nothing can be pre-rendered.
+fn dynamic_page() -> Node {
+    Node::new_open(
+        "article",
+        Some(Attributes::Owned(vec![Attribute::new(
+            "class",
+            AttributeValue::Text("post".into()),
+        )])),
+        Some(vec![
+            Node::new_open(
+                "header",
+                None,
+                Some(vec![Node::new_open(
+                    "h1",
+                    None,
+                    Some(vec![Node::new_text("Example Page")]),
+                )]),
+            ),
+            Node::new_open(
+                "p",
+                Some(Attributes::Owned(vec![Attribute::new(
+                    "class",
+                    AttributeValue::Text("p p--small".into()),
+                )])),
+                Some(vec![Node::new_text("Hello world!")]),
+            ),
+            Node::new_self_closing("hr", None),
+        ]),
+    )
+}
+
+#[test]
+fn static_and_dynamic_pages_render_the_same() -> Result<(), std::fmt::Error> {
+    assert_eq!(render(static_page())?, render(dynamic_page())?);
+
+    Ok(())
+}
+
+#[test]
+fn static_page_allocates_less_than_dynamic_page() {
+    let static_allocations = count_allocations(|| {
+        render(static_page()).unwrap();
+    });
+    let dynamic_allocations = count_allocations(|| {
+        render(dynamic_page()).unwrap();
+    });
+
+    println!(
+        "allocations; static {}, dynamic {}",
+        static_allocations, dynamic_allocations
+    );
+    assert!(static_allocations < dynamic_allocations);
+}
+
+#[test]
+fn static_attributes_allocate_less_than_owned_attributes() {
+    let text = "Hello world!";
+    let static_allocations = count_allocations(|| {
+        rsx! {
+          <p class="p p--small" hidden>{text}</p>
+        };
+    });
+    let owned_allocations = count_allocations(|| {
+        Node::new_open(
+            "p",
+            Some(Attributes::Owned(vec![
+                Attribute::new("class", AttributeValue::Text("p p--small".into())),
+                Attribute::new("hidden", AttributeValue::ImplicitTrue),
+            ])),
+            Some(vec![Node::new_text(text)]),
+        );
+    });
+
+    assert!(static_allocations < owned_allocations);
+}
+
+#[bench]
+fn bench_static_page(bencher: &mut Bencher) {
+    bencher.iter(|| render(static_page()).unwrap());
+}
+
+#[bench]
+fn bench_dynamic_page(bencher: &mut Bencher) {
+    bencher.iter(|| render(dynamic_page()).unwrap());
+}
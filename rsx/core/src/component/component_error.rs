@@ -0,0 +1,74 @@
+use crate::dom::Node;
+use crate::dom::ToNode;
+use ::std::any::Any;
+use ::std::error::Error;
+use ::std::fmt;
+use ::std::sync::Arc;
+
+/// An error from a component, which is held in the tree until it is rendered.
+///
+/// Rendering it fails the render, unless it's within an `ErrorBoundary`,
+/// which renders its fallback instead.
+#[derive(Clone, Debug)]
+pub enum ComponentError {
+    /// A component returned an `Err`.
+    Error(Arc<dyn Error + Send + Sync>),
+
+    /// A component panicked within an `ErrorBoundary` which catches panics.
+    Panic(String),
+}
+
+impl ComponentError {
+    pub fn new<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        Self::Error(Arc::from(error.into()))
+    }
+
+    /// Takes the message from what `catch_unwind` returns.
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "unknown panic".to_string(),
+            },
+        };
+
+        Self::Panic(message)
+    }
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error(error) => write!(f, "{}", error),
+            Self::Panic(message) => write!(f, "component panicked, {}", message),
+        }
+    }
+}
+
+impl Error for ComponentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Error(error) => Some(error.as_ref()),
+            Self::Panic(_) => None,
+        }
+    }
+}
+
+/// Components can return `Result<Node, E>`.
+/// The error is kept in the tree, and caught by the nearest `ErrorBoundary` when rendered.
+impl<N, E> ToNode for Result<N, E>
+where
+    N: ToNode,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn to_node(self) -> Node {
+        match self {
+            Ok(node) => node.to_node(),
+            Err(error) => Node::new_error(ComponentError::new(error)),
+        }
+    }
+}
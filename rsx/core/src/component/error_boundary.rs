@@ -0,0 +1,216 @@
+use crate::component::ComponentError;
+use crate::dom::normalize_nodes;
+use crate::dom::Node;
+use ::std::fmt;
+use ::std::panic;
+use ::std::panic::AssertUnwindSafe;
+use ::std::sync::Arc;
+
+type Fallback = Arc<dyn Fn(&ComponentError) -> Node + Send + Sync>;
+
+/// Renders its children, or the fallback if an error is found within them.
+///
+/// ```text
+/// <ErrorBoundary fallback={|err| rsx! { <p>Sorry, this couldn't be loaded.</p> }}>
+///   <UserCard id={id} />
+/// </ErrorBoundary>
+/// ```
+///
+/// Errors are components returning `Err`. With `catch_panics`, panics whilst the children are built,
+/// or whilst they are rendered, are caught too. Errors which are caught are logged.
+#[derive(Clone)]
+pub struct ErrorBoundary {
+    fallback: Fallback,
+    catch_panics: bool,
+    children: Vec<Node>,
+}
+
+impl ErrorBoundary {
+    pub fn new<F>(fallback: F, children: Node) -> Self
+    where
+        F: Fn(&ComponentError) -> Node + Send + Sync + 'static,
+    {
+        Self {
+            fallback: Arc::new(fallback),
+            catch_panics: false,
+            children: normalize_nodes(children.into_nodes()),
+        }
+    }
+
+    /// Builds the children, catching a panic whilst doing so, and whilst they are rendered.
+    pub fn catching_panics<F, C>(fallback: F, children: C) -> Self
+    where
+        F: Fn(&ComponentError) -> Node + Send + Sync + 'static,
+        C: FnOnce() -> Node,
+    {
+        let children = panic::catch_unwind(AssertUnwindSafe(children))
+            .unwrap_or_else(|payload| Node::new_error(ComponentError::from_panic(payload)));
+
+        Self {
+            catch_panics: true,
+            ..Self::new(fallback, children)
+        }
+    }
+
+    pub fn catch_panics(&self) -> bool {
+        self.catch_panics
+    }
+
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.children
+    }
+
+    /// The node to render in place of the children.
+    pub fn fallback(&self, error: &ComponentError) -> Node {
+        (self.fallback)(error)
+    }
+}
+
+impl fmt::Debug for ErrorBoundary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorBoundary")
+            .field("catch_panics", &self.catch_panics)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod error_boundary {
+    use super::*;
+    use crate::dom::ToNode;
+    use crate::i18n::Translation;
+    use crate::i18n::Translator;
+    use crate::render::render;
+    use crate::render::render_translated;
+    use crate::render::RenderError;
+
+    fn apology(error: &ComponentError) -> Node {
+        Node::new_open("p", None, Some(format!("Sorry, {}", error)))
+    }
+
+    fn user_card(name: Result<&'static str, &'static str>) -> Node {
+        name.map(|name| Node::new_open("b", None, Some(name)))
+            .to_node()
+    }
+
+    #[test]
+    fn it_should_render_the_children_without_an_error() {
+        let node = Node::new_error_boundary(apology, user_card(Ok("Anna")));
+
+        assert_eq!("<b>Anna</b>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_render_the_fallback_in_place_of_the_children() {
+        let node = Node::new_open(
+            "div",
+            None,
+            Some(Node::new_error_boundary(
+                apology,
+                Node::new_fragment(vec![
+                    user_card(Ok("Anna")),
+                    user_card(Err("user not found")),
+                ]),
+            )),
+        );
+
+        assert_eq!(
+            "<div><p>Sorry, user not found</p></div>",
+            render(&node).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_fail_rendering_without_a_boundary() {
+        let node = Node::new_open("div", None, Some(user_card(Err("user not found"))));
+
+        match render(&node) {
+            Err(RenderError::Component(error)) => {
+                assert_eq!("user not found", error.to_string())
+            }
+            result => panic!("expected a component error, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn it_should_pass_errors_in_the_fallback_to_the_boundary_above() {
+        let inner = Node::new_error_boundary(
+            |_: &ComponentError| user_card(Err("no fallback")),
+            user_card(Err("user not found")),
+        );
+        let node = Node::new_error_boundary(apology, inner);
+
+        assert_eq!("<p>Sorry, no fallback</p>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_take_back_head_elements_from_the_children() {
+        let node = Node::new_open(
+            "html",
+            None,
+            Some(vec![
+                Node::new_open(
+                    "head",
+                    None,
+                    Some(Node::new_open("title", None, Some("Home"))),
+                ),
+                Node::new_error_boundary(
+                    apology,
+                    Node::new_fragment(vec![
+                        Node::new_slot_content(
+                            "head",
+                            vec![Node::new_open("title", None, Some("Anna"))],
+                        ),
+                        user_card(Err("user not found")),
+                    ]),
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            "<html><head><title>Home</title></head><p>Sorry, user not found</p></html>",
+            render(&node).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_catch_panics_whilst_building_the_children() {
+        let node =
+            Node::new_error_boundary_catching_panics(apology, || -> Node { panic!("no database") });
+
+        assert_eq!(
+            "<p>Sorry, component panicked, no database</p>",
+            render(&node).unwrap()
+        );
+    }
+
+    #[derive(Debug)]
+    struct PanickingTranslator;
+
+    impl Translator for PanickingTranslator {
+        fn lang(&self) -> &str {
+            "en"
+        }
+
+        fn translate(&self, translation: &Translation) -> Option<String> {
+            panic!("no translation for {}", translation.key())
+        }
+    }
+
+    #[test]
+    fn it_should_catch_panics_whilst_rendering_the_children() {
+        let node = Node::new_error_boundary_catching_panics(apology, || {
+            Translation::new("greeting").to_node()
+        });
+
+        assert_eq!(
+            "<p>Sorry, component panicked, no translation for greeting</p>",
+            render_translated(&node, Arc::new(PanickingTranslator)).unwrap()
+        );
+    }
+}
@@ -1,2 +1,8 @@
 mod component;
 pub use self::component::*;
+
+mod component_error;
+pub use self::component_error::*;
+
+mod error_boundary;
+pub use self::error_boundary::*;
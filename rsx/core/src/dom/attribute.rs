@@ -1,16 +1,55 @@
 use crate::dom::AttributeValue;
+use ::std::borrow::Cow;
 
 #[derive(Clone, Debug)]
 pub struct Attribute {
     #[doc(hidden)]
-    pub key: &'static str,
+    pub key: Cow<'static, str>,
 
     #[doc(hidden)]
     pub value: AttributeValue,
 }
 
 impl Attribute {
-    pub fn new(key: &'static str, value: AttributeValue) -> Self {
-        Self { key, value }
+    pub const fn new(key: &'static str, value: AttributeValue) -> Self {
+        Self {
+            key: Cow::Borrowed(key),
+            value,
+        }
+    }
+
+    /// Creates an attribute with a key which is only known at runtime.
+    pub fn new_owned<K>(key: K, value: AttributeValue) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        Self {
+            key: key.into(),
+            value,
+        }
+    }
+
+    /// An estimate of how many bytes this will be, once rendered.
+    pub fn size_hint(&self) -> usize {
+        match &self.value {
+            AttributeValue::ImplicitFalse => 0,
+            AttributeValue::ImplicitTrue => " ".len() + self.key.len(),
+            AttributeValue::Text(text) => " =\"\"".len() + self.key.len() + text.len(),
+            AttributeValue::Translation(translation) => {
+                " =\"\"".len() + self.key.len() + translation.key().len()
+            }
+            AttributeValue::Char(c) => " =\"\"".len() + self.key.len() + c.len_utf8(),
+            AttributeValue::UnsignedInteger(_)
+            | AttributeValue::SignedInteger(_)
+            | AttributeValue::UnsignedInteger128(_)
+            | AttributeValue::SignedInteger128(_)
+            | AttributeValue::Float(_) => " =\"\"".len() + self.key.len() + NUMBER_SIZE_HINT,
+            AttributeValue::FloatWithPrecision(_, precision) => {
+                " =\"\"".len() + self.key.len() + NUMBER_SIZE_HINT + precision
+            }
+        }
     }
 }
+
+/// A guess at how long a number will be once printed.
+const NUMBER_SIZE_HINT: usize = 4;
@@ -1,3 +1,6 @@
+use crate::i18n::Translation;
+use ::std::borrow::Cow;
+
 #[derive(Clone, Debug)]
 pub enum AttributeValue {
     /// This is for when the attribute is set,
@@ -7,8 +10,15 @@ pub enum AttributeValue {
     /// This is for keys which don't have a value.
     /// i.e. The `disabled` in `<button disabled>`.
     ImplicitTrue,
-    Text(&'static str),
+    Text(Cow<'static, str>),
     UnsignedInteger(u64),
     SignedInteger(i64),
+    UnsignedInteger128(u128),
+    SignedInteger128(i128),
     Float(f64),
+    /// A float with a fixed number of decimal places. See `Precision`.
+    FloatWithPrecision(f64, usize),
+    Char(char),
+    /// Text which is looked up by the `Translator` when rendering.
+    Translation(Box<Translation>),
 }
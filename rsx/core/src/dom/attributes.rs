@@ -0,0 +1,127 @@
+use crate::dom::Attribute;
+use crate::dom::AttributeValue;
+use ::std::borrow::Cow;
+use ::std::convert::From;
+use ::std::ops::Deref;
+
+/// The list of attributes on a node.
+///
+/// When every key and value is known at compile time,
+/// `rsx!` will use a `Static` list, which requires no allocation.
+#[derive(Clone, Debug)]
+pub enum Attributes {
+    Static(&'static [Attribute]),
+    Owned(Vec<Attribute>),
+}
+
+impl Attributes {
+    /// Returns the attributes as a `Vec` which can be changed,
+    /// copying them first if they are `Static`.
+    pub fn to_mut(&mut self) -> &mut Vec<Attribute> {
+        if let Self::Static(attributes) = self {
+            *self = Self::Owned(attributes.to_vec());
+        }
+
+        match self {
+            Self::Owned(attributes) => attributes,
+            Self::Static(_) => unreachable!(),
+        }
+    }
+}
+
+impl Deref for Attributes {
+    type Target = [Attribute];
+
+    fn deref(&self) -> &[Attribute] {
+        match self {
+            Self::Static(attributes) => attributes,
+            Self::Owned(attributes) => attributes,
+        }
+    }
+}
+
+impl From<&'static [Attribute]> for Attributes {
+    fn from(attributes: &'static [Attribute]) -> Self {
+        Self::Static(attributes)
+    }
+}
+
+impl From<Vec<Attribute>> for Attributes {
+    fn from(attributes: Vec<Attribute>) -> Self {
+        Self::Owned(attributes)
+    }
+}
+
+/// Returns where an attribute is, ignoring the case of the key.
+pub(crate) fn attribute_index(attributes: &[Attribute], key: &str) -> Option<usize> {
+    attributes
+        .iter()
+        .position(|attribute| attribute.key.eq_ignore_ascii_case(key))
+}
+
+/// Returns the value of an attribute, ignoring the case of the key.
+/// Attributes which will not be rendered, i.e. set to `false`, are treated as missing.
+pub(crate) fn find_attribute<'a>(
+    attributes: &'a [Attribute],
+    key: &str,
+) -> Option<&'a AttributeValue> {
+    let index = attribute_index(attributes, key)?;
+
+    Some(&attributes[index].value).filter(|value| !matches!(value, AttributeValue::ImplicitFalse))
+}
+
+/// Sets an attribute, replacing it if it is already present.
+pub(crate) fn insert_attribute(
+    attributes: &mut Vec<Attribute>,
+    key: Cow<'static, str>,
+    value: AttributeValue,
+) {
+    match attribute_index(attributes, &key) {
+        Some(index) => attributes[index].value = value,
+        None => attributes.push(Attribute::new_owned(key, value)),
+    }
+}
+
+#[cfg(test)]
+mod attributes {
+    use super::*;
+
+    #[test]
+    fn it_should_deref_static_attributes() {
+        const ATTRIBUTES: &[Attribute] = &[Attribute::new(
+            "class",
+            AttributeValue::Text(Cow::Borrowed("button")),
+        )];
+        let attributes = Attributes::from(ATTRIBUTES);
+
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes[0].key, "class");
+    }
+
+    #[test]
+    fn it_should_deref_owned_attributes() {
+        let attributes = Attributes::from(vec![
+            Attribute::new("class", AttributeValue::Text(Cow::Borrowed("button"))),
+            Attribute::new("disabled", AttributeValue::ImplicitTrue),
+        ]);
+
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(attributes[1].key, "disabled");
+    }
+
+    #[test]
+    fn it_should_copy_static_attributes_when_changed() {
+        const ATTRIBUTES: &[Attribute] = &[Attribute::new(
+            "class",
+            AttributeValue::Text(Cow::Borrowed("button")),
+        )];
+        let mut attributes = Attributes::from(ATTRIBUTES);
+        attributes
+            .to_mut()
+            .push(Attribute::new("disabled", AttributeValue::ImplicitTrue));
+
+        assert!(matches!(attributes, Attributes::Owned(_)));
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(ATTRIBUTES.len(), 1);
+    }
+}
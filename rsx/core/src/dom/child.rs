@@ -1,8 +1,26 @@
+#![allow(deprecated)]
+
 use crate::dom::Node;
+use crate::dom::ToNode;
 
+/// What `ToChild` returns.
+///
+/// Children are now a `Vec<Node>`, so this is turned into a node when it is used,
+/// with `Nodes` becoming a fragment.
+#[deprecated(note = "children are now a `Vec<Node>`; implement `ToNode` instead")]
 #[derive(Clone, Debug)]
 pub enum Child {
     None,
     Nodes { nodes: Vec<Node> },
     Text { contents: &'static str },
 }
+
+impl ToNode for Child {
+    fn to_node(self) -> Node {
+        match self {
+            Self::None => Node::Empty,
+            Self::Nodes { nodes } => Node::new_fragment(nodes),
+            Self::Text { contents } => Node::new_text(contents),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::dom::Node;
+use ::std::slice;
+
+/// Iterates over the children of a node.
+/// Fragments and error boundaries are flattened, so their children are returned in their place.
+#[derive(Clone, Debug)]
+pub struct Children<'a> {
+    stack: Vec<slice::Iter<'a, Node>>,
+}
+
+impl<'a> Children<'a> {
+    pub(crate) fn new(node: &'a Node) -> Self {
+        let children: &'a [Node] = match node.resolve() {
+            Node::Fragment { children } | Node::OpenWithChildren { children, .. } => children,
+            Node::ErrorBoundary { boundary } => boundary.children(),
+            _ => &[],
+        };
+
+        Self {
+            stack: vec![children.iter()],
+        }
+    }
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        loop {
+            let node = match self.stack.last_mut()?.next() {
+                Some(node) => node.resolve(),
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            match node {
+                Node::Empty | Node::Error { .. } => {}
+                Node::Fragment { children } => self.stack.push(children.iter()),
+                Node::ErrorBoundary { boundary } => self.stack.push(boundary.children().iter()),
+                node => return Some(node),
+            }
+        }
+    }
+}
+
+/// Iterates over every node within a node, depth first.
+#[derive(Clone, Debug)]
+pub struct Descendants<'a> {
+    stack: Vec<Children<'a>>,
+}
+
+impl<'a> Descendants<'a> {
+    pub(crate) fn new(node: &'a Node) -> Self {
+        Self {
+            stack: vec![node.children()],
+        }
+    }
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some(node) => {
+                    self.stack.push(node.children());
+                    return Some(node);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod iter {
+    use super::*;
+
+    fn list() -> Node {
+        Node::new_open(
+            "ul",
+            None,
+            Some(vec![
+                Node::new_open("li", None, Some("a")),
+                Node::Fragment {
+                    children: vec![
+                        Node::Empty,
+                        Node::new_open("li", None, Some("b")),
+                        new_static!("<li>c</li>"),
+                    ],
+                },
+            ]),
+        )
+    }
+
+    #[test]
+    fn it_should_iterate_over_children_through_fragments() {
+        let node = list();
+        let names: Vec<_> = node.children().filter_map(Node::name).collect();
+
+        assert_eq!(vec!["li", "li", "li"], names);
+    }
+
+    #[test]
+    fn it_should_iterate_over_descendants_depth_first() {
+        let node = list();
+        let found: Vec<_> = node
+            .descendants()
+            .map(|node| node.name().or(node.text()).unwrap())
+            .collect();
+
+        assert_eq!(vec!["li", "a", "li", "b", "li", "c"], found);
+    }
+
+    #[test]
+    fn it_should_find_no_children_for_text() {
+        assert_eq!(0, Node::new_text("a").children().count());
+        assert_eq!(0, Node::new_self_closing("br", None).descendants().count());
+    }
+}
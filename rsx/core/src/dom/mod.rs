@@ -4,6 +4,9 @@ pub use self::node::*;
 mod attribute;
 pub use self::attribute::*;
 
+mod attributes;
+pub use self::attributes::*;
+
 mod attribute_value;
 pub use self::attribute_value::*;
 
@@ -18,3 +21,19 @@ pub use self::to_child::*;
 
 mod to_node;
 pub use self::to_node::*;
+
+mod iter;
+pub use self::iter::*;
+
+mod slot;
+pub(crate) use self::slot::*;
+
+mod normalize;
+pub(crate) use self::normalize::*;
+
+mod url;
+pub use self::url::*;
+
+mod traverse;
+
+pub mod visit;
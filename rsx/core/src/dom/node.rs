@@ -1,13 +1,20 @@
+use crate::component::ComponentError;
+use crate::component::ErrorBoundary;
+use crate::dom::fragment_or_node;
+use crate::dom::normalize_nodes;
 use crate::dom::Attribute;
-use crate::dom::Child;
-use crate::dom::ToChild;
+use crate::dom::Attributes;
+use crate::dom::ToNode;
+use crate::i18n::Translation;
+use ::std::borrow::Cow;
 use ::std::convert::AsRef;
+use ::std::sync::OnceLock;
 
 /// The contents of the Node are all doc-hidden.
 /// This is because the Node structure may change in future releases.
 ///
-/// You can look in the source code if you want to use it directly.
-/// Just be aware a future release might break your code.
+/// To inspect or change a tree, use the methods such as `name`, `attribute`, `children`,
+/// and `set_attribute`, or the visitors in `dom::visit`. These will stay stable.
 #[derive(Clone, Debug)]
 pub enum Node {
     #[doc(hidden)]
@@ -15,60 +22,277 @@ pub enum Node {
 
     #[doc(hidden)]
     Doctype {
-        name: &'static str,
-        attributes: Option<Vec<Attribute>>,
+        name: Cow<'static, str>,
+        attributes: Option<Attributes>,
     },
 
     #[doc(hidden)]
     Comment { children: Option<Vec<Self>> },
 
+    /// i.e. `<![CDATA[ ... ]]>`.
+    #[doc(hidden)]
+    CData { children: Option<Vec<Self>> },
+
+    /// i.e. `<?xml version="1.0" encoding="UTF-8"?>`.
+    #[doc(hidden)]
+    ProcessingInstruction {
+        target: Cow<'static, str>,
+        attributes: Option<Attributes>,
+    },
+
     #[doc(hidden)]
     Fragment { children: Vec<Self> },
 
     #[doc(hidden)]
     SelfClosing {
-        name: &'static str,
-        attributes: Option<Vec<Attribute>>,
+        name: Cow<'static, str>,
+        attributes: Option<Attributes>,
     },
 
     #[doc(hidden)]
     OpenEmpty {
-        name: &'static str,
-        attributes: Option<Vec<Attribute>>,
+        name: Cow<'static, str>,
+        attributes: Option<Attributes>,
     },
 
     #[doc(hidden)]
     OpenWithChildren {
-        name: &'static str,
-        attributes: Option<Vec<Attribute>>,
-        child: Child,
+        name: Cow<'static, str>,
+        attributes: Option<Attributes>,
+        children: Vec<Self>,
     },
 
+    /// Where a layout writes the content given for a slot, i.e. `<slot:head/>`.
+    #[doc(hidden)]
+    Slot { name: Cow<'static, str> },
+
+    /// Content for a slot, i.e. `<slot:head><title>Home</title></slot:head>`.
+    /// This is moved into the slot by `fill_slots`, and isn't rendered where it's written.
+    /// Content for the head which is left over is collected by `render::Head`.
+    #[doc(hidden)]
+    SlotContent {
+        name: Cow<'static, str>,
+        children: Vec<Self>,
+    },
+
+    #[doc(hidden)]
+    Text { contents: Cow<'static, str> },
+
+    /// Text which is looked up by the `Translator` when rendering, i.e. `t!("greeting")`.
+    #[doc(hidden)]
+    Translation { translation: Box<Translation> },
+
+    /// An error from a component, i.e. one returning `Err`.
+    /// Rendering this fails, unless it's within an `ErrorBoundary`.
+    #[doc(hidden)]
+    Error { error: ComponentError },
+
+    /// Renders its children, or a fallback if they hold an error. See `ErrorBoundary`.
     #[doc(hidden)]
-    Text { contents: &'static str },
+    ErrorBoundary { boundary: Box<ErrorBoundary> },
+
+    /// HTML which has been rendered at compile time,
+    /// and is written out verbatim.
+    ///
+    /// `build` creates the same nodes as a tree, as `rsx!` was given them.
+    /// This is only called if the node is traversed, or rendered with hooks or as XML,
+    /// and is then kept in `tree`.
+    #[doc(hidden)]
+    Static {
+        html: &'static str,
+        build: fn() -> Node,
+        tree: OnceLock<Box<Node>>,
+    },
 }
 
 impl Node {
-    pub fn new_open<N>(
-        name: &'static str,
-        attributes: Option<Vec<Attribute>>,
-        maybe_child: Option<N>,
+    /// Creates an element, i.e. `<div></div>`.
+    ///
+    /// The children can be anything which implements `ToNode`.
+    /// When a `Fragment` is given, its children become the children of the element.
+    pub fn new_open<S, N>(
+        name: S,
+        attributes: Option<Attributes>,
+        maybe_children: Option<N>,
     ) -> Self
     where
-        N: ToChild,
+        S: Into<Cow<'static, str>>,
+        N: ToNode,
+    {
+        match maybe_children {
+            Some(children) => {
+                Self::new_open_with_children(name, attributes, children.to_node().into_nodes())
+            }
+            None => Self::new_open_empty(name, attributes),
+        }
+    }
+
+    /// Creates an element which was written without children, i.e. `<div/>`.
+    pub fn new_open_empty<S>(name: S, attributes: Option<Attributes>) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::OpenEmpty {
+            name: name.into(),
+            attributes,
+        }
+    }
+
+    /// Creates an element with the children given.
+    ///
+    /// The children are normalised, so fragments within them are flattened,
+    /// empty nodes are removed, and adjacent text is merged. See `Node::normalize`.
+    pub fn new_open_with_children<S>(
+        name: S,
+        attributes: Option<Attributes>,
+        children: Vec<Node>,
+    ) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        let children = normalize_nodes(children);
+        if children.is_empty() {
+            return Self::new_open_empty(name, attributes);
+        }
+
+        Self::OpenWithChildren {
+            name: name.into(),
+            attributes,
+            children,
+        }
+    }
+
+    /// Creates a fragment, which is normalised in the same way as the children of an element.
+    /// This gives `Empty` when there are no children, and the child itself when there is one.
+    pub fn new_fragment(children: Vec<Node>) -> Self {
+        fragment_or_node(normalize_nodes(children))
+    }
+
+    /// Turns the node into a list of nodes, for use as children.
+    /// A `Fragment` gives its children, and `Empty` gives none.
+    pub fn into_nodes(self) -> Vec<Node> {
+        match self {
+            Self::Empty => Vec::new(),
+            Self::Fragment { children } => children,
+            node => vec![node],
+        }
+    }
+
+    pub fn new_error(error: ComponentError) -> Self {
+        Self::Error { error }
+    }
+
+    /// Creates an `ErrorBoundary`, i.e. `<ErrorBoundary fallback={...}>...</ErrorBoundary>`.
+    pub fn new_error_boundary<F>(fallback: F, children: Node) -> Self
+    where
+        F: Fn(&ComponentError) -> Node + Send + Sync + 'static,
+    {
+        Self::ErrorBoundary {
+            boundary: Box::new(ErrorBoundary::new(fallback, children)),
+        }
+    }
+
+    /// Creates an `ErrorBoundary` which catches panics,
+    /// i.e. `<ErrorBoundary fallback={...} catch_panics>...</ErrorBoundary>`.
+    pub fn new_error_boundary_catching_panics<F, C>(fallback: F, children: C) -> Self
+    where
+        F: Fn(&ComponentError) -> Node + Send + Sync + 'static,
+        C: FnOnce() -> Node,
     {
-        match maybe_child {
-            Some(child) => Self::OpenWithChildren {
+        Self::ErrorBoundary {
+            boundary: Box::new(ErrorBoundary::catching_panics(fallback, children)),
+        }
+    }
+
+    pub fn new_self_closing<S>(name: S, attributes: Option<Attributes>) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::SelfClosing {
+            name: name.into(),
+            attributes,
+        }
+    }
+
+    /// Pre-rendered HTML, and a function which builds the nodes it was rendered from.
+    pub const fn new_static(html: &'static str, build: fn() -> Node) -> Self {
+        Self::Static {
+            html,
+            build,
+            tree: OnceLock::new(),
+        }
+    }
+
+    /// Creates a text node.
+    pub fn new_text<S>(contents: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::Text {
+            contents: contents.into(),
+        }
+    }
+
+    /// An estimate of how many bytes this will be, once rendered.
+    ///
+    /// This is exact for text, names, and text attributes,
+    /// and a guess for numbers. It's used to pre-size the render buffer.
+    pub fn size_hint(&self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::Doctype { name, attributes } => {
+                let attributes_size = attributes
+                    .as_ref()
+                    .map(|attributes| attributes.iter().map(|a| a.key.len() + 1).sum())
+                    .unwrap_or(0);
+
+                "<!>".len() + name.len() + attributes_size
+            }
+            Self::Comment { children } => match children {
+                Some(children) => "<!--  -->".len() + nodes_size_hint(children),
+                None => "<!-- -->".len(),
+            },
+            Self::CData { children } => {
+                "<![CDATA[]]>".len() + children.as_deref().map(nodes_size_hint).unwrap_or(0)
+            }
+            Self::ProcessingInstruction { target, attributes } => {
+                "<??>".len() + target.len() + attributes_size_hint(attributes)
+            }
+            Self::Fragment { children } => nodes_size_hint(children),
+            Self::SelfClosing { name, attributes } => {
+                "</>".len() + name.len() + attributes_size_hint(attributes)
+            }
+            Self::OpenEmpty { name, attributes } => {
+                "<></>".len() + name.len() * 2 + attributes_size_hint(attributes)
+            }
+            Self::OpenWithChildren {
                 name,
                 attributes,
-                child: child.to_child(),
-            },
-            None => Self::OpenEmpty { name, attributes },
+                children,
+            } => {
+                "<></>".len()
+                    + name.len() * 2
+                    + attributes_size_hint(attributes)
+                    + nodes_size_hint(children)
+            }
+            Self::Slot { .. } | Self::SlotContent { .. } => 0,
+            Self::Text { contents } => contents.len(),
+            Self::Translation { translation } => translation.key().len(),
+            Self::Static { html, .. } => html.len(),
+            Self::Error { .. } => 0,
+            Self::ErrorBoundary { boundary } => nodes_size_hint(boundary.children()),
         }
     }
+}
+
+pub(crate) fn nodes_size_hint(nodes: &[Node]) -> usize {
+    nodes.iter().map(Node::size_hint).sum()
+}
 
-    pub fn new_self_closing(name: &'static str, attributes: Option<Vec<Attribute>>) -> Self {
-        Self::SelfClosing { name, attributes }
+fn attributes_size_hint(maybe_attributes: &Option<Attributes>) -> usize {
+    match maybe_attributes {
+        Some(attributes) => attributes.iter().map(Attribute::size_hint).sum(),
+        None => 0,
     }
 }
 
@@ -97,22 +321,113 @@ mod node {
             None,
             Some(vec![
                 crate::dom::ToNode::to_node("Hello world!"),
-                crate::dom::ToNode::to_node(crate::dom::Node::Text {
-                    contents: "hello world!",
-                }),
+                crate::dom::ToNode::to_node(crate::dom::Node::new_text("hello world!")),
                 crate::dom::ToNode::to_node(text),
             ]),
         );
 
+        assert_eq!(node.text_content(), "Hello world!hello world!yo");
+
         if let Node::OpenWithChildren {
-            name,
-            attributes,
-            child,
+            name, attributes, ..
         } = node
         {
             assert_eq!(name, "h1");
+            assert!(attributes.is_none());
         } else {
             unreachable!();
         }
     }
 }
+
+#[cfg(test)]
+mod new_open {
+    use super::*;
+    use crate::render::render;
+
+    #[test]
+    fn it_should_take_the_children_of_a_fragment() {
+        let node = Node::new_open(
+            "ul",
+            None,
+            Some(Node::new_fragment(vec![
+                Node::new_open("li", None, Some("a")),
+                Node::new_open("li", None, Some(Node::Empty)),
+            ])),
+        );
+
+        assert_eq!(2, node.children().count());
+        assert_eq!("<ul><li>a</li><li></li></ul>", render(node).unwrap());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn it_should_build_children_from_to_child() {
+        use crate::dom::Child;
+        use crate::dom::ToChild;
+
+        struct Greeting(&'static str);
+
+        impl ToChild for Greeting {
+            fn to_child(self) -> Child {
+                Child::Nodes {
+                    nodes: vec![Node::new_text("Hello "), Node::new_text(self.0)],
+                }
+            }
+        }
+
+        let node = Node::new_open("p", None, Some(Greeting("world")));
+
+        assert_eq!("<p>Hello world</p>", render(node).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod size_hint {
+    use super::*;
+    use crate::dom::AttributeValue;
+    use crate::render::render;
+
+    #[test]
+    fn it_should_match_the_rendered_size_for_text_nodes() {
+        let node = Node::Fragment {
+            children: vec![
+                Node::Doctype {
+                    name: "doctype".into(),
+                    attributes: Some(Attributes::Owned(vec![Attribute::new(
+                        "html",
+                        AttributeValue::ImplicitTrue,
+                    )])),
+                },
+                Node::Comment {
+                    children: Some(vec![Node::new_text("hi")]),
+                },
+                Node::CData {
+                    children: Some(vec![Node::new_text("a < b")]),
+                },
+                Node::ProcessingInstruction {
+                    target: "xml-stylesheet".into(),
+                    attributes: Some(Attributes::Owned(vec![Attribute::new(
+                        "href",
+                        AttributeValue::Text("a.xsl".into()),
+                    )])),
+                },
+                Node::new_open(
+                    "div",
+                    Some(Attributes::Owned(vec![
+                        Attribute::new("class", AttributeValue::Text("a b".into())),
+                        Attribute::new("hidden", AttributeValue::ImplicitTrue),
+                        Attribute::new("disabled", AttributeValue::ImplicitFalse),
+                    ])),
+                    Some(vec![
+                        new_static!("<p>Hi</p>"),
+                        Node::new_self_closing("hr", None),
+                        Node::new_open("span", None, Option::<Vec<Node>>::None),
+                    ]),
+                ),
+            ],
+        };
+
+        assert_eq!(render(&node).unwrap().len(), node.size_hint());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::dom::Node;
+use ::std::mem;
+
+/// Trees built from components are full of fragments and empty nodes,
+/// i.e. from `<></>` and `Option<Node>` values which are `None`.
+/// Normalising removes them, so the tree has one way of being written.
+impl Node {
+    /// Rewrites the tree into its canonical form.
+    ///
+    /// Fragments are flattened into their parent, empty nodes and empty text are removed,
+    /// and adjacent text is merged. An element whose children all vanish becomes empty,
+    /// i.e. `<div>{None}</div>` becomes `<div></div>`.
+    ///
+    /// Pre-rendered HTML is left as it is.
+    pub fn normalize(&mut self) {
+        match self {
+            Self::Comment { children } | Self::CData { children } => {
+                if let Some(children) = children {
+                    normalize_all(children);
+                }
+            }
+            Self::SlotContent { children, .. } => normalize_all(children),
+            Self::ErrorBoundary { boundary } => normalize_all(boundary.children_mut()),
+            Self::Fragment { children } => {
+                normalize_all(children);
+                *self = fragment_or_node(mem::take(children));
+            }
+            Self::OpenWithChildren {
+                name,
+                attributes,
+                children,
+            } => {
+                normalize_all(children);
+                if children.is_empty() {
+                    *self = Self::OpenEmpty {
+                        name: mem::take(name),
+                        attributes: attributes.take(),
+                    };
+                }
+            }
+            Self::Text { contents } if contents.is_empty() => *self = Self::Empty,
+            _ => {}
+        }
+    }
+
+    /// Returns the tree in its canonical form. See `normalize`.
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+}
+
+fn normalize_all(nodes: &mut Vec<Node>) {
+    for node in nodes.iter_mut() {
+        node.normalize();
+    }
+
+    *nodes = normalize_nodes(mem::take(nodes));
+}
+
+/// A fragment of one node is just that node, and an empty fragment is nothing.
+pub(crate) fn fragment_or_node(mut children: Vec<Node>) -> Node {
+    match children.len() {
+        0 => Node::Empty,
+        1 => children.remove(0),
+        _ => Node::Fragment { children },
+    }
+}
+
+/// Normalises a list of nodes, without going into the elements within it.
+///
+/// This is used when building a node from its children,
+/// which have already been built, and so normalised.
+pub(crate) fn normalize_nodes(nodes: Vec<Node>) -> Vec<Node> {
+    if is_normalized(&nodes) {
+        return nodes;
+    }
+
+    let mut normalized = Vec::with_capacity(nodes.len());
+    push_nodes(&mut normalized, nodes);
+    normalized
+}
+
+fn push_nodes(normalized: &mut Vec<Node>, nodes: Vec<Node>) {
+    for node in nodes {
+        match node {
+            Node::Empty => {}
+            Node::Fragment { children } => push_nodes(normalized, children),
+            Node::Text { contents } if contents.is_empty() => {}
+            Node::Text { contents } => match normalized.last_mut() {
+                Some(Node::Text { contents: previous }) => previous.to_mut().push_str(&contents),
+                _ => normalized.push(Node::Text { contents }),
+            },
+            node => normalized.push(node),
+        }
+    }
+}
+
+/// Returns true if the list has no fragments, empty nodes, or text next to text.
+pub(crate) fn is_normalized(nodes: &[Node]) -> bool {
+    let mut is_after_text = false;
+
+    for node in nodes {
+        match node {
+            Node::Empty | Node::Fragment { .. } => return false,
+            Node::Text { contents } => {
+                if is_after_text || contents.is_empty() {
+                    return false;
+                }
+                is_after_text = true;
+            }
+            _ => is_after_text = false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod normalize {
+    use super::*;
+    use crate::render::render;
+
+    #[test]
+    fn it_should_flatten_fragments_and_remove_empties() {
+        let node = Node::OpenWithChildren {
+            name: "ul".into(),
+            attributes: None,
+            children: vec![
+                Node::Empty,
+                Node::Fragment {
+                    children: vec![
+                        Node::new_open("li", None, Some("a")),
+                        Node::Fragment {
+                            children: vec![Node::Empty, Node::new_open("li", None, Some("b"))],
+                        },
+                    ],
+                },
+            ],
+        }
+        .normalized();
+
+        match &node {
+            Node::OpenWithChildren { children, .. } => {
+                assert_eq!(2, children.len());
+                assert!(children.iter().all(|child| child.name() == Some("li")));
+            }
+            node => panic!("expected an element, found {:?}", node),
+        }
+        assert_eq!("<ul><li>a</li><li>b</li></ul>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_merge_adjacent_text() {
+        let node = Node::Fragment {
+            children: vec![
+                Node::new_text("Hello "),
+                Node::Fragment {
+                    children: vec![Node::new_text(""), Node::new_text("world")],
+                },
+                Node::Empty,
+                Node::new_text("!"),
+            ],
+        }
+        .normalized();
+
+        assert_eq!(Some("Hello world!"), node.text());
+    }
+
+    #[test]
+    fn it_should_empty_elements_whose_children_vanish() {
+        let node = Node::OpenWithChildren {
+            name: "div".into(),
+            attributes: None,
+            children: vec![
+                Node::Empty,
+                Node::Fragment {
+                    children: vec![Node::new_text("")],
+                },
+            ],
+        }
+        .normalized();
+
+        assert!(matches!(node, Node::OpenEmpty { ref name, .. } if name == "div"));
+        assert!(matches!(
+            Node::Fragment { children: vec![] }.normalized(),
+            Node::Empty
+        ));
+    }
+
+    #[test]
+    fn it_should_find_lists_which_are_not_normalized() {
+        assert!(is_normalized(&[
+            Node::new_text("a"),
+            Node::new_self_closing("br", None),
+            Node::new_text("b"),
+        ]));
+        assert!(!is_normalized(&[Node::new_text("a"), Node::new_text("b")]));
+        assert!(!is_normalized(&[Node::Empty]));
+        assert!(!is_normalized(&[Node::Fragment { children: vec![] }]));
+    }
+}
@@ -0,0 +1,272 @@
+//! Slots, for layouts which are filled in by the pages using them.
+//!
+//! A layout declares where each slot goes with `<slot:head/>`,
+//! and anything within the layout, including components deep inside a page,
+//! gives content for it with `<slot:head>...</slot:head>`.
+
+use crate::dom::Node;
+use crate::render::render;
+use ::std::borrow::Cow;
+use ::std::collections::HashMap;
+use ::std::collections::HashSet;
+use ::std::mem;
+
+/// Elements which are only written once per slot, when the same one is given more than once.
+const DEDUPLICATED_ELEMENTS: &[&str] = &["link", "script"];
+
+impl Node {
+    /// Where a layout writes the content given for the slot `name`.
+    pub fn new_slot<S>(name: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::Slot { name: name.into() }
+    }
+
+    /// Content for the slot `name`, which is moved there by `fill_slots`.
+    pub fn new_slot_content<S>(name: S, children: Vec<Node>) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::SlotContent {
+            name: name.into(),
+            children,
+        }
+    }
+
+    /// Moves content for slots, from anywhere in the tree, into the slots the tree declares.
+    /// `rsx!` calls this on any tree written with a `<slot:name/>`.
+    ///
+    /// Repeated `<link>` and `<script>` elements are only written once per slot.
+    /// Content for a slot which isn't declared is left where it is, for an outer layout to take.
+    pub fn fill_slots(mut self) -> Self {
+        let mut slots = HashMap::new();
+        find_slots(&self, &mut slots);
+        if slots.is_empty() {
+            return self;
+        }
+
+        take_slot_contents(&mut self, &mut slots);
+        write_slot_contents(&mut self, &mut slots);
+        self
+    }
+}
+
+/// The content given for one slot.
+#[derive(Default)]
+struct SlotContents {
+    nodes: Vec<Node>,
+    written_elements: HashSet<String>,
+}
+
+impl SlotContents {
+    fn push(&mut self, node: Node) {
+        for_each_element(node, &mut |node| {
+            let is_repeated =
+                deduplication_key(&node).is_some_and(|key| !self.written_elements.insert(key));
+
+            if !is_repeated {
+                self.nodes.push(node);
+            }
+        });
+    }
+}
+
+/// Calls `f` with each node in turn, going into fragments,
+/// and pre-rendered HTML with more than one element, such as two `<link>`s.
+/// Empty nodes are skipped.
+///
+/// This is used for slot content, and the `Head`, which are both lists of elements.
+pub(crate) fn for_each_element<F>(node: Node, f: &mut F)
+where
+    F: FnMut(Node),
+{
+    match node {
+        Node::Empty => {}
+        Node::Fragment { children } => {
+            for child in children {
+                for_each_element(child, f);
+            }
+        }
+        node if matches!(node.resolve(), Node::Fragment { .. }) => {
+            for child in node.children() {
+                for_each_element(child.clone(), f);
+            }
+        }
+        node => f(node),
+    }
+}
+
+/// For `<link>` and `<script>` elements, which are only written once, returns what they are
+/// compared by. This is their HTML, so they must be the same to be repeated.
+pub(crate) fn deduplication_key(node: &Node) -> Option<String> {
+    let name = node.name()?;
+    let is_deduplicated = DEDUPLICATED_ELEMENTS
+        .iter()
+        .any(|element| name.eq_ignore_ascii_case(element));
+
+    if is_deduplicated {
+        render(node).ok()
+    } else {
+        None
+    }
+}
+
+fn find_slots(node: &Node, slots: &mut HashMap<String, SlotContents>) {
+    match node {
+        Node::Slot { name } => {
+            slots.entry(name.to_string()).or_default();
+        }
+        node => {
+            for child in slot_children(node) {
+                find_slots(child, slots);
+            }
+        }
+    }
+}
+
+fn take_slot_contents(node: &mut Node, slots: &mut HashMap<String, SlotContents>) {
+    if let Node::SlotContent { name, children } = node {
+        if slots.contains_key(name.as_ref()) {
+            let name = name.to_string();
+            let mut children = mem::take(children);
+            *node = Node::Empty;
+
+            // Content for other slots can be given within this one.
+            for child in &mut children {
+                take_slot_contents(child, slots);
+            }
+
+            if let Some(contents) = slots.get_mut(&name) {
+                for child in children {
+                    contents.push(child);
+                }
+            }
+            return;
+        }
+    }
+
+    for child in slot_children_mut(node) {
+        take_slot_contents(child, slots);
+    }
+}
+
+fn write_slot_contents(node: &mut Node, slots: &mut HashMap<String, SlotContents>) {
+    if let Node::Slot { name } = node {
+        let children = slots
+            .get_mut(name.as_ref())
+            .map(|contents| mem::take(&mut contents.nodes))
+            .unwrap_or_default();
+        *node = Node::Fragment { children };
+        return;
+    }
+
+    for child in slot_children_mut(node) {
+        write_slot_contents(child, slots);
+    }
+}
+
+/// The children where slots can be found.
+/// Pre-rendered HTML is skipped, as it never holds slots.
+fn slot_children(node: &Node) -> &[Node] {
+    match node {
+        Node::Fragment { children }
+        | Node::SlotContent { children, .. }
+        | Node::OpenWithChildren { children, .. } => children,
+        Node::ErrorBoundary { boundary } => boundary.children(),
+        _ => &[],
+    }
+}
+
+fn slot_children_mut(node: &mut Node) -> &mut [Node] {
+    match node {
+        Node::Fragment { children }
+        | Node::SlotContent { children, .. }
+        | Node::OpenWithChildren { children, .. } => children,
+        Node::ErrorBoundary { boundary } => boundary.children_mut(),
+        _ => &mut [],
+    }
+}
+
+#[cfg(test)]
+mod fill_slots {
+    use super::*;
+
+    fn layout(page: Node) -> Node {
+        Node::new_open(
+            "html",
+            None,
+            Some(vec![
+                Node::new_open("head", None, Some(vec![Node::new_slot("head")])),
+                Node::new_open("body", None, Some(vec![page])),
+            ]),
+        )
+        .fill_slots()
+    }
+
+    fn title(text: &'static str) -> Node {
+        Node::new_open("title", None, Some(vec![Node::new_text(text)]))
+    }
+
+    #[test]
+    fn it_should_move_content_into_slots() {
+        let page = Node::new_open(
+            "main",
+            None,
+            Some(vec![
+                Node::new_slot_content("head", vec![title("Home")]),
+                Node::new_text("Welcome"),
+            ]),
+        );
+
+        assert_eq!(
+            "<html><head><title>Home</title></head><body><main>Welcome</main></body></html>",
+            render(layout(page)).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_write_repeated_links_and_scripts_once() {
+        let stylesheet = || new_static!(r#"<link rel="stylesheet" href="/app.css"/>"#);
+        let page = Node::Fragment {
+            children: vec![
+                Node::new_slot_content("head", vec![stylesheet(), title("Home")]),
+                Node::new_slot_content(
+                    "head",
+                    vec![new_static!(concat!(
+                        r#"<link rel="stylesheet" href="/app.css"/>"#,
+                        r#"<link rel="stylesheet" href="/menu.css"/>"#,
+                    ))],
+                ),
+                Node::new_slot_content("head", vec![stylesheet()]),
+            ],
+        };
+
+        assert_eq!(
+            concat!(
+                r#"<html><head><link rel="stylesheet" href="/app.css"/><title>Home</title>"#,
+                r#"<link rel="stylesheet" href="/menu.css"/></head><body></body></html>"#,
+            ),
+            render(layout(page)).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_leave_content_for_slots_which_are_not_declared() {
+        let page = Node::new_slot_content("scripts", vec![Node::new_text("a")]);
+        let html = layout(page);
+
+        assert_eq!(
+            "<html><head></head><body></body></html>",
+            render(&html).unwrap()
+        );
+
+        let outer = Node::Fragment {
+            children: vec![Node::new_slot("scripts"), html],
+        };
+        assert_eq!(
+            "a<html><head></head><body></body></html>",
+            render(outer.fill_slots()).unwrap()
+        );
+    }
+}
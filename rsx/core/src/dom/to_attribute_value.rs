@@ -1,4 +1,17 @@
 use crate::dom::AttributeValue;
+use ::std::borrow::Cow;
+use ::std::num::NonZeroI128;
+use ::std::num::NonZeroI16;
+use ::std::num::NonZeroI32;
+use ::std::num::NonZeroI64;
+use ::std::num::NonZeroI8;
+use ::std::num::NonZeroIsize;
+use ::std::num::NonZeroU128;
+use ::std::num::NonZeroU16;
+use ::std::num::NonZeroU32;
+use ::std::num::NonZeroU64;
+use ::std::num::NonZeroU8;
+use ::std::num::NonZeroUsize;
 
 pub trait ToAttributeValue {
     fn to_attribute_value(self) -> AttributeValue;
@@ -28,7 +41,13 @@ impl<N: ToAttributeValue> ToAttributeValue for Option<N> {
 
 impl ToAttributeValue for &'static str {
     fn to_attribute_value(self) -> AttributeValue {
-        AttributeValue::Text(self)
+        AttributeValue::Text(Cow::Borrowed(self))
+    }
+}
+
+impl ToAttributeValue for String {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::Text(Cow::Owned(self))
     }
 }
 
@@ -92,6 +111,45 @@ impl ToAttributeValue for usize {
     }
 }
 
+impl ToAttributeValue for i128 {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::SignedInteger128(self)
+    }
+}
+
+impl ToAttributeValue for u128 {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::UnsignedInteger128(self)
+    }
+}
+
+macro_rules! impl_non_zero {
+    ($($non_zero:ty),*) => {
+        $(
+            impl ToAttributeValue for $non_zero {
+                fn to_attribute_value(self) -> AttributeValue {
+                    self.get().to_attribute_value()
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
 impl ToAttributeValue for f64 {
     fn to_attribute_value(self) -> AttributeValue {
         AttributeValue::Float(self)
@@ -104,6 +162,23 @@ impl ToAttributeValue for f32 {
     }
 }
 
+/// A float written with a fixed number of decimal places.
+/// i.e. `Precision(0.1 + 0.2, 2)` is written as `0.30`, rather than `0.30000000000000004`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Precision(pub f64, pub usize);
+
+impl ToAttributeValue for Precision {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::FloatWithPrecision(self.0, self.1)
+    }
+}
+
+impl ToAttributeValue for char {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::Char(self)
+    }
+}
+
 impl ToAttributeValue for bool {
     fn to_attribute_value(self) -> AttributeValue {
         if self {
@@ -113,3 +188,51 @@ impl ToAttributeValue for bool {
         }
     }
 }
+
+#[cfg(test)]
+mod to_attribute_value {
+    use super::*;
+    use crate::dom::Attribute;
+    use crate::dom::Attributes;
+    use crate::dom::Node;
+    use crate::dom::Url;
+    use crate::render::render;
+
+    fn render_value<V: ToAttributeValue>(value: V) -> String {
+        let attributes = Attributes::Owned(vec![Attribute::new("v", value.to_attribute_value())]);
+        render(Node::new_self_closing("a", Some(attributes))).unwrap()
+    }
+
+    #[test]
+    fn it_should_write_floats_with_a_precision() {
+        assert_eq!(r#"<a v="0.30"/>"#, render_value(Precision(0.1 + 0.2, 2)));
+        assert_eq!(r#"<a v="3"/>"#, render_value(Precision(2.5_f64.ceil(), 0)));
+    }
+
+    #[test]
+    fn it_should_write_chars_and_wide_integers() {
+        assert_eq!(r#"<a v="&quot;"/>"#, render_value('"'));
+        assert_eq!(
+            r#"<a v="-170141183460469231731687303715884105728"/>"#,
+            render_value(i128::MIN)
+        );
+        assert_eq!(
+            r#"<a v="340282366920938463463374607431768211455"/>"#,
+            render_value(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn it_should_write_non_zero_integers() {
+        assert_eq!(r#"<a v="5"/>"#, render_value(NonZeroU8::new(5).unwrap()));
+        assert_eq!(r#"<a v="-5"/>"#, render_value(NonZeroI64::new(-5).unwrap()));
+    }
+
+    #[test]
+    fn it_should_write_urls() {
+        assert_eq!(
+            r#"<a v="/search?q=a%20b&amp;page=2"/>"#,
+            render_value(Url::parse("/search?q=a b&page=2").unwrap())
+        );
+    }
+}
@@ -1,72 +1,25 @@
+#![allow(deprecated)]
+
 use crate::dom::Child;
 use crate::dom::Node;
-
+use crate::dom::ToNode;
+
+/// Everything which can be a child now implements `ToNode`, which `rsx!` calls.
+///
+/// Existing implementations of `ToChild` keep working, as every `ToChild` is also a `ToNode`.
+/// To move a type over, implement `ToNode` in place of `ToChild`,
+/// returning `Node::Empty` for `Child::None`, `Node::new_text` for `Child::Text`,
+/// and `Node::new_fragment` for `Child::Nodes`.
+#[deprecated(note = "implement `ToNode` instead; children are now a `Vec<Node>`")]
 pub trait ToChild {
     fn to_child(self) -> Child;
 }
 
-impl ToChild for Child {
-    #[inline(always)]
-    fn to_child(self) -> Self {
-        self
-    }
-}
-
-impl<N: ToChild> ToChild for Option<N> {
-    fn to_child(self) -> Child {
-        match self {
-            None => Child::None,
-            Some(n) => n.to_child(),
-        }
-    }
-}
-
-impl ToChild for &&'static str {
-    fn to_child(self) -> Child {
-        Child::Text { contents: *self }
-    }
-}
-
-impl ToChild for &'static str {
-    fn to_child(self) -> Child {
-        Child::Text { contents: self }
-    }
-}
-
-impl ToChild for Vec<&'static str> {
-    fn to_child(self) -> Child {
-        if self.len() == 0 {
-            Child::None
-        } else if self.len() == 1 {
-            Child::Text { contents: self[0] }
-        } else {
-            Child::Nodes {
-                nodes: self
-                    .into_iter()
-                    .map(|text| Node::Text { contents: text })
-                    .collect(),
-            }
-        }
-    }
-}
-
-impl ToChild for Vec<Node> {
-    fn to_child(self) -> Child {
-        Child::Nodes { nodes: self }
-    }
-}
-
-impl ToChild for Node {
-    fn to_child(self) -> Child {
-        match self {
-            Node::Empty => Child::None,
-            node => Child::Nodes { nodes: vec![node] },
-        }
-    }
-}
-
-impl ToChild for () {
-    fn to_child(self) -> Child {
-        Child::None
+impl<C> ToNode for C
+where
+    C: ToChild,
+{
+    fn to_node(self) -> Node {
+        self.to_child().to_node()
     }
 }
@@ -1,4 +1,17 @@
 use crate::dom::Node;
+use ::std::fmt;
+use ::std::num::NonZeroI128;
+use ::std::num::NonZeroI16;
+use ::std::num::NonZeroI32;
+use ::std::num::NonZeroI64;
+use ::std::num::NonZeroI8;
+use ::std::num::NonZeroIsize;
+use ::std::num::NonZeroU128;
+use ::std::num::NonZeroU16;
+use ::std::num::NonZeroU32;
+use ::std::num::NonZeroU64;
+use ::std::num::NonZeroU8;
+use ::std::num::NonZeroUsize;
 
 pub trait ToNode {
     fn to_node(self) -> Node;
@@ -22,28 +35,103 @@ impl<N: ToNode> ToNode for Option<N> {
 
 impl ToNode for &&'static str {
     fn to_node(self) -> Node {
-        Node::Text { contents: *self }
+        Node::new_text(*self)
     }
 }
 
 impl ToNode for &'static str {
     fn to_node(self) -> Node {
-        Node::Text { contents: self }
+        Node::new_text(self)
+    }
+}
+
+impl ToNode for String {
+    fn to_node(self) -> Node {
+        Node::new_text(self)
+    }
+}
+
+/// Numbers are written as text, i.e. `{count}`.
+macro_rules! impl_to_string {
+    ($($value:ty),*) => {
+        $(
+            impl ToNode for $value {
+                fn to_node(self) -> Node {
+                    Node::new_text(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_string!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char);
+
+impl_to_string!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
+/// `false` renders nothing, as in React. `true` is written as text.
+impl ToNode for bool {
+    fn to_node(self) -> Node {
+        if self {
+            Node::new_text("true")
+        } else {
+            Node::Empty
+        }
+    }
+}
+
+/// Renders any `fmt::Display` type as text, which is escaped like any other.
+///
+/// ```
+/// use ::core::dom::display;
+/// use ::core::dom::ToNode;
+/// use ::core::render::render;
+/// use ::std::net::Ipv4Addr;
+///
+/// let node = display(Ipv4Addr::LOCALHOST).to_node();
+/// assert_eq!(render(node).unwrap(), "127.0.0.1");
+/// ```
+pub fn display<T>(value: T) -> Displayed<T>
+where
+    T: fmt::Display,
+{
+    Displayed(value)
+}
+
+/// A `fmt::Display` type to be rendered as text. This is made with `display`.
+#[derive(Copy, Clone, Debug)]
+pub struct Displayed<T>(pub T);
+
+impl<T> ToNode for Displayed<T>
+where
+    T: fmt::Display,
+{
+    fn to_node(self) -> Node {
+        Node::new_text(self.0.to_string())
     }
 }
 
 impl ToNode for Vec<&'static str> {
     fn to_node(self) -> Node {
-        if self.len() == 0 {
+        if self.is_empty() {
             Node::Empty
         } else if self.len() == 1 {
-            Node::Text { contents: self[0] }
+            Node::new_text(self[0])
         } else {
             Node::Fragment {
-                children: self
-                    .into_iter()
-                    .map(|text| Node::Text { contents: text })
-                    .collect(),
+                children: self.into_iter().map(Node::new_text).collect(),
             }
         }
     }
@@ -60,3 +148,45 @@ impl ToNode for () {
         Node::Empty
     }
 }
+
+#[cfg(test)]
+mod to_node {
+    use super::*;
+    use crate::render::render;
+
+    fn render_node<N: ToNode>(value: N) -> String {
+        render(value.to_node()).unwrap()
+    }
+
+    #[test]
+    fn it_should_write_numbers_and_chars() {
+        assert_eq!("42", render_node(42_u8));
+        assert_eq!("-7", render_node(-7_i64));
+        assert_eq!("1.5", render_node(1.5_f32));
+        assert_eq!(
+            "340282366920938463463374607431768211455",
+            render_node(u128::MAX)
+        );
+        assert_eq!("3", render_node(NonZeroUsize::new(3).unwrap()));
+        assert_eq!("&lt;", render_node('<'));
+    }
+
+    #[test]
+    fn it_should_write_nothing_for_false() {
+        assert!(matches!(false.to_node(), Node::Empty));
+        assert_eq!("true", render_node(true));
+    }
+
+    #[test]
+    fn it_should_escape_displayed_values() {
+        struct Tag;
+        impl fmt::Display for Tag {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "<b>{}</b>", 1)
+            }
+        }
+
+        assert_eq!("&lt;b&gt;1&lt;/b&gt;", render_node(display(Tag)));
+        assert_eq!("owned", render_node("owned".to_string()));
+    }
+}
@@ -0,0 +1,374 @@
+use crate::dom::attribute_index;
+use crate::dom::find_attribute;
+use crate::dom::insert_attribute;
+use crate::dom::Attribute;
+use crate::dom::AttributeValue;
+use crate::dom::Attributes;
+use crate::dom::Children;
+use crate::dom::Descendants;
+use crate::dom::Node;
+use crate::dom::ToAttributeValue;
+use ::std::borrow::Cow;
+use ::std::mem;
+
+/// A stable way to inspect and change a `Node` tree.
+///
+/// These look through how the tree is stored.
+/// Fragments are flattened into their parent, and pre-rendered HTML is parsed when it is reached.
+///
+/// Only what is rendered as part of the document is traversed.
+/// The text within comments and CDATA sections is not,
+/// nor is content for slots, which is only rendered once `fill_slots` has moved it.
+impl Node {
+    /// Returns true for elements, such as `<div>` and `<br/>`.
+    pub fn is_element(&self) -> bool {
+        self.name().is_some()
+    }
+
+    /// Returns the tag name, if this is an element.
+    pub fn name(&self) -> Option<&str> {
+        match self.resolve() {
+            Self::SelfClosing { name, .. }
+            | Self::OpenEmpty { name, .. }
+            | Self::OpenWithChildren { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the text, if this is a text node.
+    pub fn text(&self) -> Option<&str> {
+        match self.resolve() {
+            Self::Text { contents } => Some(contents),
+            _ => None,
+        }
+    }
+
+    /// Returns all of the text within this node, joined together.
+    pub fn text_content(&self) -> String {
+        match self.text() {
+            Some(text) => text.to_string(),
+            None => self.descendants().filter_map(Node::text).collect(),
+        }
+    }
+
+    /// Returns the attributes of an element.
+    /// This is empty for nodes which are not elements.
+    pub fn attributes(&self) -> &[Attribute] {
+        match self.resolve() {
+            Self::SelfClosing { attributes, .. }
+            | Self::OpenEmpty { attributes, .. }
+            | Self::OpenWithChildren { attributes, .. } => match attributes {
+                Some(attributes) => attributes,
+                None => &[],
+            },
+            _ => &[],
+        }
+    }
+
+    /// Returns the value of an attribute, ignoring the case of the key.
+    /// Attributes which will not be rendered, i.e. set to `false`, are treated as missing.
+    pub fn attribute(&self, key: &str) -> Option<&AttributeValue> {
+        find_attribute(self.attributes(), key)
+    }
+
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.attribute(key).is_some()
+    }
+
+    /// Sets an attribute, replacing it if it is already present.
+    /// This does nothing if this is not an element.
+    pub fn set_attribute<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<Cow<'static, str>>,
+        V: ToAttributeValue,
+    {
+        self.expand_static();
+
+        let maybe_attributes = match self {
+            Self::SelfClosing { attributes, .. }
+            | Self::OpenEmpty { attributes, .. }
+            | Self::OpenWithChildren { attributes, .. } => attributes,
+            _ => return,
+        };
+
+        let attributes = maybe_attributes
+            .get_or_insert_with(|| Attributes::Owned(Vec::new()))
+            .to_mut();
+        insert_attribute(attributes, key.into(), value.to_attribute_value());
+    }
+
+    /// Removes an attribute, and returns its value.
+    pub fn remove_attribute(&mut self, key: &str) -> Option<AttributeValue> {
+        if !self.has_attribute(key) {
+            return None;
+        }
+        self.expand_static();
+
+        let maybe_attributes = match self {
+            Self::SelfClosing { attributes, .. }
+            | Self::OpenEmpty { attributes, .. }
+            | Self::OpenWithChildren { attributes, .. } => attributes,
+            _ => return None,
+        };
+
+        let attributes = maybe_attributes.as_mut()?.to_mut();
+        let index = attribute_index(attributes, key)?;
+        let attribute = attributes.remove(index);
+        if attributes.is_empty() {
+            *maybe_attributes = None;
+        }
+
+        Some(attribute.value)
+    }
+
+    /// Iterates over the direct children of this node.
+    pub fn children(&self) -> Children<'_> {
+        Children::new(self)
+    }
+
+    /// Iterates over every node within this one, depth first.
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants::new(self)
+    }
+
+    /// Returns the list of children, so they can be added to or removed.
+    /// This returns `None` for nodes which cannot have children, such as text and `<br/>`.
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        self.expand_static();
+
+        if let Self::OpenEmpty { name, attributes } = self {
+            *self = Self::OpenWithChildren {
+                name: mem::take(name),
+                attributes: attributes.take(),
+                children: Vec::new(),
+            };
+        }
+
+        match self {
+            Self::Fragment { children } | Self::OpenWithChildren { children, .. } => Some(children),
+            Self::ErrorBoundary { boundary } => Some(boundary.children_mut()),
+            _ => None,
+        }
+    }
+
+    /// Replaces every node in the tree with the result of `f`.
+    ///
+    /// Children are mapped before their parent, so `f` sees the parent with its new children.
+    pub fn map<F>(self, mut f: F) -> Node
+    where
+        F: FnMut(Node) -> Node,
+    {
+        map_node(self, &mut f)
+    }
+
+    /// Removes every node in the tree, and everything within it, where `f` returns false.
+    pub fn filter<F>(self, mut f: F) -> Node
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        filter_node(self, &mut f).unwrap_or(Node::Empty)
+    }
+
+    /// Pre-rendered HTML is stored as a string.
+    /// This returns it as a tree, building it the first time it is needed.
+    pub(crate) fn resolve(&self) -> &Node {
+        match self {
+            Self::Static { build, tree, .. } => tree.get_or_init(|| Box::new(build())),
+            node => node,
+        }
+    }
+
+    /// Replaces pre-rendered HTML with its tree, so it can be changed.
+    pub(crate) fn expand_static(&mut self) {
+        if let Self::Static { build, tree, .. } = self {
+            *self = tree.take().map_or_else(*build, |node| *node);
+        }
+    }
+
+    /// Fragments, error boundaries, and empty nodes only exist to hold other nodes,
+    /// and are hidden when traversing the tree. As are errors, which have nothing to show.
+    fn is_transparent(&self) -> bool {
+        matches!(
+            self,
+            Self::Empty | Self::Fragment { .. } | Self::ErrorBoundary { .. } | Self::Error { .. }
+        )
+    }
+}
+
+fn map_node<F>(mut node: Node, f: &mut F) -> Node
+where
+    F: FnMut(Node) -> Node,
+{
+    node.expand_static();
+
+    if let Some(children) = traversed_children_mut(&mut node) {
+        *children = mem::take(children)
+            .into_iter()
+            .map(|child| map_node(child, f))
+            .collect();
+    }
+
+    if node.is_transparent() {
+        node
+    } else {
+        f(node)
+    }
+}
+
+fn filter_node<F>(mut node: Node, f: &mut F) -> Option<Node>
+where
+    F: FnMut(&Node) -> bool,
+{
+    node.expand_static();
+
+    if !node.is_transparent() && !f(&node) {
+        return None;
+    }
+
+    if let Some(children) = traversed_children_mut(&mut node) {
+        *children = mem::take(children)
+            .into_iter()
+            .filter_map(|child| filter_node(child, f))
+            .collect();
+    }
+
+    Some(node)
+}
+
+/// Unlike `children_mut`, this leaves elements without children as they are.
+fn traversed_children_mut(node: &mut Node) -> Option<&mut Vec<Node>> {
+    match node {
+        Node::Fragment { children } | Node::OpenWithChildren { children, .. } => Some(children),
+        Node::ErrorBoundary { boundary } => Some(boundary.children_mut()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod traverse {
+    use super::*;
+    use crate::render::render;
+
+    fn link() -> Node {
+        Node::new_open(
+            "a",
+            Some(Attributes::Owned(vec![
+                Attribute::new("href", AttributeValue::Text("/".into())),
+                Attribute::new("hidden", AttributeValue::ImplicitFalse),
+            ])),
+            Some("Home"),
+        )
+    }
+
+    #[test]
+    fn it_should_read_elements() {
+        let node = link();
+
+        assert!(node.is_element());
+        assert_eq!(Some("a"), node.name());
+        assert_eq!(None, node.text());
+        assert_eq!("Home", node.text_content());
+    }
+
+    #[test]
+    fn it_should_read_attributes() {
+        let node = link();
+
+        assert!(matches!(node.attribute("HREF"), Some(AttributeValue::Text(href)) if href == "/"));
+        assert!(!node.has_attribute("hidden"));
+        assert!(!node.has_attribute("title"));
+        assert_eq!(0, Node::new_text("a").attributes().len());
+    }
+
+    #[test]
+    fn it_should_set_and_remove_attributes() {
+        let mut node = link();
+        node.set_attribute("href", "/about");
+        node.set_attribute("rel", String::from("noopener"));
+        node.remove_attribute("hidden");
+
+        assert_eq!(
+            r#"<a href="/about" rel="noopener">Home</a>"#,
+            render(&node).unwrap()
+        );
+
+        node.remove_attribute("href");
+        node.remove_attribute("rel");
+        assert_eq!("<a>Home</a>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_add_children_to_empty_elements() {
+        let mut node = Node::new_open("ul", None, Option::<Vec<Node>>::None);
+        node.children_mut()
+            .unwrap()
+            .push(Node::new_open("li", None, Some("a")));
+
+        assert_eq!("<ul><li>a</li></ul>", render(&node).unwrap());
+        assert!(Node::new_self_closing("br", None).children_mut().is_none());
+    }
+
+    #[test]
+    fn it_should_look_inside_static_html() {
+        let mut node = new_static!(r#"<p class="intro">Hello <b>world</b></p>"#);
+
+        assert_eq!(Some("p"), node.name());
+        assert_eq!("Hello world", node.text_content());
+
+        node.set_attribute("id", "first");
+        assert_eq!(
+            r#"<p class="intro" id="first">Hello <b>world</b></p>"#,
+            render(&node).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_map_nodes() {
+        let node = Node::Fragment {
+            children: vec![link(), Node::new_text("!")],
+        };
+        let node = node.map(|mut node| {
+            if node.name() == Some("a") {
+                node.set_attribute("class", "link");
+            }
+            node
+        });
+
+        assert_eq!(
+            r#"<a href="/" class="link">Home</a>!"#,
+            render(&node).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_filter_nodes() {
+        let node = new_static!("<div><p>a</p><script>b</script><p>c</p></div>");
+        let node = node.filter(|node| node.name() != Some("script"));
+
+        assert_eq!("<div><p>a</p><p>c</p></div>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_only_map_what_is_rendered() {
+        let node = Node::Fragment {
+            children: vec![
+                Node::new_open_empty("script", None),
+                Node::Comment {
+                    children: Some(vec![Node::new_text("a")]),
+                },
+                Node::new_slot_content("head", vec![Node::new_text("b")]),
+            ],
+        };
+        let mut names = Vec::new();
+        let node = node.map(|node| {
+            names.push(node.name().map(|name| name.to_string()));
+            node
+        });
+
+        assert_eq!(vec![Some("script".to_string()), None, None], names);
+        assert!(matches!(
+            &node,
+            Node::Fragment { children } if matches!(children[0], Node::OpenEmpty { .. })
+        ));
+    }
+}
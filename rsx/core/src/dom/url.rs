@@ -0,0 +1,160 @@
+use crate::dom::AttributeValue;
+use crate::dom::ToAttributeValue;
+use crate::sanitize::url_scheme;
+use ::std::borrow::Cow;
+use ::std::error::Error;
+use ::std::fmt;
+use ::std::fmt::Write;
+use ::std::str::FromStr;
+
+/// The schemes a `Url` can have. Anything else, such as `javascript:`, is refused.
+pub const URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel", "sms", "ftp"];
+
+/// A URL for an attribute such as `href` or `src`, which has been checked and percent-encoded.
+///
+/// ```
+/// use ::core::dom::Url;
+///
+/// let url = Url::parse("/search?q=crème brûlée").unwrap();
+/// assert_eq!(url.as_str(), "/search?q=cr%C3%A8me%20br%C3%BBl%C3%A9e");
+///
+/// assert!(Url::parse("javascript:alert(1)").is_err());
+/// ```
+///
+/// Relative URLs are allowed. Characters which cannot be in a URL are percent-encoded,
+/// whilst those which give it structure, such as `/`, `?`, `&`, and `#`, are kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Url {
+    url: Cow<'static, str>,
+}
+
+impl Url {
+    pub fn parse(url: &str) -> Result<Self, UrlError> {
+        Self::parse_with_schemes(url, URL_SCHEMES)
+    }
+
+    /// Parses the URL, only allowing the schemes given.
+    pub fn parse_with_schemes(url: &str, schemes: &[&str]) -> Result<Self, UrlError> {
+        let url = url.trim();
+
+        if let Some(scheme) = url_scheme(url) {
+            if !schemes.contains(&scheme.as_str()) {
+                return Err(UrlError::UnsupportedScheme(scheme));
+            }
+        }
+
+        Ok(Self {
+            url: encode_url(url),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.url
+    }
+}
+
+impl FromStr for Url {
+    type Err = UrlError;
+
+    fn from_str(url: &str) -> Result<Self, UrlError> {
+        Self::parse(url)
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.url)
+    }
+}
+
+impl ToAttributeValue for Url {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::Text(self.url)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UrlError {
+    /// The scheme isn't allowed, i.e. `javascript`.
+    UnsupportedScheme(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedScheme(scheme) => write!(f, "unsupported URL scheme `{}:`", scheme),
+        }
+    }
+}
+
+impl Error for UrlError {}
+
+/// Percent-encodes the characters which cannot be in a URL.
+/// A `%` which already starts an encoded character is kept.
+fn encode_url(url: &str) -> Cow<'static, str> {
+    let mut encoded = String::with_capacity(url.len());
+    let bytes = url.as_bytes();
+
+    for (i, c) in url.char_indices() {
+        match c {
+            '%' if is_encoded_at(bytes, i) => encoded.push(c),
+            c if c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=".contains(c) => {
+                encoded.push(c)
+            }
+            c => {
+                let mut utf8 = [0; 4];
+                for byte in c.encode_utf8(&mut utf8).bytes() {
+                    let _ = write!(encoded, "%{:02X}", byte);
+                }
+            }
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+fn is_encoded_at(bytes: &[u8], i: usize) -> bool {
+    bytes.len() > i + 2 && bytes[i + 1].is_ascii_hexdigit() && bytes[i + 2].is_ascii_hexdigit()
+}
+
+#[cfg(test)]
+mod url {
+    use super::*;
+
+    fn encoded(url: &str) -> String {
+        Url::parse(url).unwrap().to_string()
+    }
+
+    #[test]
+    fn it_should_keep_urls_which_need_no_encoding() {
+        assert_eq!(
+            "https://example.com/a/b?c=1&d=2#top",
+            encoded("https://example.com/a/b?c=1&d=2#top")
+        );
+        assert_eq!("mailto:a@example.com", encoded("mailto:a@example.com"));
+        assert_eq!("../page", encoded("  ../page\n"));
+    }
+
+    #[test]
+    fn it_should_encode_characters_which_cannot_be_in_urls() {
+        assert_eq!(
+            "/a%20b?q=%22%3Cb%3E%22&x=%E2%9C%93",
+            encoded("/a b?q=\"<b>\"&x=✓")
+        );
+        assert_eq!("/100%25/%20", encoded("/100%/%20"));
+    }
+
+    #[test]
+    fn it_should_refuse_unsupported_schemes() {
+        assert_eq!(
+            Err(UrlError::UnsupportedScheme("javascript".to_string())),
+            Url::parse("JavaScript:alert(1)")
+        );
+        assert_eq!(
+            Err(UrlError::UnsupportedScheme("javascript".to_string())),
+            Url::parse("java\tscript:alert(1)")
+        );
+        assert!(Url::parse("data:text/html,hi").is_err());
+        assert!(Url::parse_with_schemes("myapp://open", &["myapp"]).is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+//! Visitors for walking over a `Node` tree.
+//!
+//! Override the methods for the nodes you are interested in.
+//! To carry on into the children of an element, call `walk_children` (or `walk_children_mut`).
+//!
+//! ```
+//! use ::core::dom::visit::walk_children;
+//! use ::core::dom::visit::Visit;
+//! use ::core::dom::Node;
+//! use ::core::parse::parse_html;
+//!
+//! #[derive(Default)]
+//! struct Headings<'a> {
+//!     headings: Vec<&'a Node>,
+//! }
+//!
+//! impl<'a> Visit<'a> for Headings<'a> {
+//!     fn visit_element(&mut self, element: &'a Node) {
+//!         if let Some("h1" | "h2") = element.name() {
+//!             self.headings.push(element);
+//!         }
+//!
+//!         walk_children(self, element);
+//!     }
+//! }
+//!
+//! let page = parse_html("<h1>Intro</h1><section><h2>Usage</h2></section>").unwrap();
+//! let mut headings = Headings::default();
+//! headings.visit_node(&page);
+//!
+//! let titles: Vec<_> = headings.headings.iter().map(|h| h.text_content()).collect();
+//! assert_eq!(titles, vec!["Intro", "Usage"]);
+//! ```
+
+use crate::dom::Node;
+use crate::i18n::Translation;
+use ::std::borrow::Cow;
+
+pub trait Visit<'a> {
+    fn visit_node(&mut self, node: &'a Node) {
+        walk_node(self, node);
+    }
+
+    /// By default this visits the children of the element.
+    fn visit_element(&mut self, element: &'a Node) {
+        walk_children(self, element);
+    }
+
+    fn visit_text(&mut self, _text: &'a str) {}
+
+    fn visit_translation(&mut self, _translation: &'a Translation) {}
+
+    fn visit_comment(&mut self, _comment: &'a Node) {}
+
+    fn visit_doctype(&mut self, _doctype: &'a Node) {}
+
+    fn visit_processing_instruction(&mut self, _instruction: &'a Node) {}
+
+    fn visit_cdata(&mut self, _cdata: &'a Node) {}
+}
+
+/// Calls the `Visit` method for the type of node given.
+pub fn walk_node<'a, V>(visitor: &mut V, node: &'a Node)
+where
+    V: Visit<'a> + ?Sized,
+{
+    match node.resolve() {
+        // Slots are only written once they have been filled.
+        Node::Empty
+        | Node::Static { .. }
+        | Node::Slot { .. }
+        | Node::SlotContent { .. }
+        | Node::Error { .. } => {}
+        Node::Fragment { .. } | Node::ErrorBoundary { .. } => walk_children(visitor, node),
+        Node::Doctype { .. } => visitor.visit_doctype(node),
+        Node::ProcessingInstruction { .. } => visitor.visit_processing_instruction(node),
+        Node::CData { .. } => visitor.visit_cdata(node),
+        Node::Comment { .. } => visitor.visit_comment(node),
+        Node::Text { contents } => visitor.visit_text(contents),
+        Node::Translation { translation } => visitor.visit_translation(translation),
+        element => visitor.visit_element(element),
+    }
+}
+
+pub fn walk_children<'a, V>(visitor: &mut V, node: &'a Node)
+where
+    V: Visit<'a> + ?Sized,
+{
+    for child in node.children() {
+        visitor.visit_node(child);
+    }
+}
+
+/// The same as `Visit`, but with the ability to change the nodes visited.
+pub trait VisitMut {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        walk_node_mut(self, node);
+    }
+
+    /// By default this visits the children of the element.
+    fn visit_element_mut(&mut self, element: &mut Node) {
+        walk_children_mut(self, element);
+    }
+
+    fn visit_text_mut(&mut self, _text: &mut Cow<'static, str>) {}
+
+    fn visit_translation_mut(&mut self, _translation: &mut Translation) {}
+
+    fn visit_comment_mut(&mut self, _comment: &mut Node) {}
+
+    fn visit_doctype_mut(&mut self, _doctype: &mut Node) {}
+
+    fn visit_processing_instruction_mut(&mut self, _instruction: &mut Node) {}
+
+    fn visit_cdata_mut(&mut self, _cdata: &mut Node) {}
+}
+
+/// Calls the `VisitMut` method for the type of node given.
+pub fn walk_node_mut<V>(visitor: &mut V, node: &mut Node)
+where
+    V: VisitMut + ?Sized,
+{
+    node.expand_static();
+
+    match node {
+        Node::Empty
+        | Node::Static { .. }
+        | Node::Slot { .. }
+        | Node::SlotContent { .. }
+        | Node::Error { .. } => {}
+        Node::Fragment { .. } | Node::ErrorBoundary { .. } => walk_children_mut(visitor, node),
+        Node::Doctype { .. } => visitor.visit_doctype_mut(node),
+        Node::ProcessingInstruction { .. } => visitor.visit_processing_instruction_mut(node),
+        Node::CData { .. } => visitor.visit_cdata_mut(node),
+        Node::Comment { .. } => visitor.visit_comment_mut(node),
+        Node::Text { contents } => visitor.visit_text_mut(contents),
+        Node::Translation { translation } => visitor.visit_translation_mut(translation),
+        _ => visitor.visit_element_mut(node),
+    }
+}
+
+pub fn walk_children_mut<V>(visitor: &mut V, node: &mut Node)
+where
+    V: VisitMut + ?Sized,
+{
+    let children = match node {
+        Node::Fragment { children } | Node::OpenWithChildren { children, .. } => children,
+        Node::ErrorBoundary { boundary } => boundary.children_mut(),
+        _ => return,
+    };
+
+    for child in children {
+        visitor.visit_node_mut(child);
+    }
+}
+
+#[cfg(test)]
+mod visit_mut {
+    use super::*;
+    use crate::parse::parse_html;
+    use crate::render::render;
+
+    struct ExternalLinks;
+
+    impl VisitMut for ExternalLinks {
+        fn visit_element_mut(&mut self, element: &mut Node) {
+            let is_external = match element.attribute("href") {
+                Some(crate::dom::AttributeValue::Text(href)) => href.starts_with("http"),
+                _ => false,
+            };
+
+            if element.name() == Some("a") && is_external {
+                element.set_attribute("rel", "noopener");
+            }
+
+            walk_children_mut(self, element);
+        }
+    }
+
+    struct Shout;
+
+    impl VisitMut for Shout {
+        fn visit_text_mut(&mut self, text: &mut Cow<'static, str>) {
+            *text = Cow::Owned(text.to_uppercase());
+        }
+    }
+
+    #[test]
+    fn it_should_change_elements() {
+        let mut node = Node::Fragment {
+            children: vec![
+                parse_html(r#"<p><a href="https://example.com">a</a></p>"#).unwrap(),
+                new_static!(r#"<a href="/about">b</a><a href="http://example.com">c</a>"#),
+            ],
+        };
+        ExternalLinks.visit_node_mut(&mut node);
+
+        assert_eq!(
+            r#"<p><a href="https://example.com" rel="noopener">a</a></p><a href="/about">b</a><a href="http://example.com" rel="noopener">c</a>"#,
+            render(&node).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_change_text() {
+        let mut node = parse_html("<p>Hello <b>world</b><!-- note --></p>").unwrap();
+        Shout.visit_node_mut(&mut node);
+
+        assert_eq!(
+            "<p>HELLO <b>WORLD</b><!-- note --></p>",
+            render(&node).unwrap()
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::i18n::PluralCategory;
+use crate::i18n::Translation;
+use crate::i18n::TranslationArg;
+use crate::i18n::Translator;
+use ::std::collections::HashMap;
+use ::std::fmt::Write;
+use ::std::mem;
+
+/// A `Translator` holding the messages for one language,
+/// written in a subset of the [Fluent](https://projectfluent.org) syntax.
+///
+/// ```text
+/// Hello { $name }
+///
+/// { $count ->
+///     [0] No items
+///     [one] One item
+///    *[other] { $count } items
+/// }
+/// ```
+///
+/// A variant is chosen by the exact number first, then by the `PluralCategory` of the number
+/// in the catalog's language, and otherwise the variant marked with `*` is used.
+/// A message which can't be parsed is written out as it is.
+#[derive(Clone, Debug)]
+pub struct Catalog {
+    lang: String,
+    messages: HashMap<String, Pattern>,
+}
+
+impl Catalog {
+    pub fn new<S>(lang: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            lang: lang.into(),
+            messages: HashMap::new(),
+        }
+    }
+
+    pub fn with_message<K, M>(mut self, key: K, message: M) -> Self
+    where
+        K: Into<String>,
+        M: AsRef<str>,
+    {
+        self.insert(key, message);
+        self
+    }
+
+    /// Adds the message, replacing any with the same key.
+    pub fn insert<K, M>(&mut self, key: K, message: M)
+    where
+        K: Into<String>,
+        M: AsRef<str>,
+    {
+        let message = message.as_ref();
+        let pattern = Parser::new(message)
+            .parse()
+            .unwrap_or_else(|| vec![Element::Text(message.to_string())]);
+
+        self.messages.insert(key.into(), pattern);
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.messages.contains_key(key)
+    }
+}
+
+impl Translator for Catalog {
+    fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    fn translate(&self, translation: &Translation) -> Option<String> {
+        let pattern = self.messages.get(translation.key())?;
+        let mut text = String::new();
+        self.format(&mut text, pattern, translation);
+
+        Some(text)
+    }
+}
+
+impl Catalog {
+    fn format(&self, text: &mut String, pattern: &[Element], translation: &Translation) {
+        for element in pattern {
+            match element {
+                Element::Text(contents) => text.push_str(contents),
+                Element::Variable(name) => match translation.get(name) {
+                    Some(value) => {
+                        let _ = write!(text, "{}", value);
+                    }
+                    None => {
+                        let _ = write!(text, "{{${}}}", name);
+                    }
+                },
+                Element::Select { variable, variants } => {
+                    let variant = self.select(variants, translation.get(variable));
+                    self.format(text, &variant.pattern, translation);
+                }
+            }
+        }
+    }
+
+    fn select<'a>(
+        &self,
+        variants: &'a [Variant],
+        maybe_value: Option<&TranslationArg>,
+    ) -> &'a Variant {
+        let matching = match maybe_value {
+            Some(TranslationArg::Number(n)) => variants
+                .iter()
+                .find(|variant| variant.key.parse::<f64>().ok() == Some(*n))
+                .or_else(|| {
+                    let category = PluralCategory::of(&self.lang, *n);
+                    variants
+                        .iter()
+                        .find(|variant| variant.key == category.as_str())
+                }),
+            Some(TranslationArg::Text(text)) => {
+                variants.iter().find(|variant| variant.key == text.as_ref())
+            }
+            None => None,
+        };
+
+        // The parser only builds a select with a default.
+        matching
+            .or_else(|| variants.iter().find(|variant| variant.is_default))
+            .unwrap_or(&variants[0])
+    }
+}
+
+type Pattern = Vec<Element>;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Element {
+    Text(String),
+    /// i.e. `{ $name }`.
+    Variable(String),
+    /// i.e. `{ $count -> [one] ... *[other] ... }`.
+    Select {
+        variable: String,
+        variants: Vec<Variant>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Variant {
+    key: String,
+    is_default: bool,
+    pattern: Pattern,
+}
+
+struct Parser<'a> {
+    message: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(message: &'a str) -> Self {
+        Self {
+            message,
+            position: 0,
+        }
+    }
+
+    fn parse(mut self) -> Option<Pattern> {
+        let pattern = self.parse_pattern(false)?;
+
+        if self.position == self.message.len() {
+            Some(pattern)
+        } else {
+            None
+        }
+    }
+
+    /// Within a variant, the pattern ends at the next variant or the end of the select.
+    fn parse_pattern(&mut self, is_variant: bool) -> Option<Pattern> {
+        let mut pattern = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = self.peek() {
+            if is_variant && (c == '[' || c == '*' || c == '}') {
+                break;
+            }
+
+            if c == '{' {
+                if !text.is_empty() {
+                    pattern.push(Element::Text(mem::take(&mut text)));
+                }
+                pattern.push(self.parse_placeable()?);
+            } else {
+                text.push(c);
+                self.position += c.len_utf8();
+            }
+        }
+
+        if !text.is_empty() {
+            pattern.push(Element::Text(text));
+        }
+
+        if is_variant {
+            trim_pattern(&mut pattern);
+        }
+
+        Some(pattern)
+    }
+
+    fn parse_placeable(&mut self) -> Option<Element> {
+        self.expect("{")?;
+        self.skip_whitespace();
+        self.expect("$")?;
+        let variable = self.parse_identifier()?;
+        self.skip_whitespace();
+
+        if self.expect("}").is_some() {
+            return Some(Element::Variable(variable));
+        }
+
+        self.expect("->")?;
+        let mut variants = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.expect("}").is_some() {
+                break;
+            }
+
+            let is_default = self.expect("*").is_some();
+            self.expect("[")?;
+            self.skip_whitespace();
+            let key = self.parse_identifier()?;
+            self.skip_whitespace();
+            self.expect("]")?;
+
+            variants.push(Variant {
+                key,
+                is_default,
+                pattern: self.parse_pattern(true)?,
+            });
+        }
+
+        let defaults = variants.iter().filter(|variant| variant.is_default).count();
+        if defaults != 1 {
+            return None;
+        }
+
+        Some(Element::Select { variable, variants })
+    }
+
+    /// Names of variables, and variant keys, which may be a number.
+    fn parse_identifier(&mut self) -> Option<String> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.') {
+                break;
+            }
+            self.position += c.len_utf8();
+        }
+
+        if start == self.position {
+            None
+        } else {
+            Some(self.message[start..self.position].to_string())
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Option<()> {
+        if self.message[self.position..].starts_with(token) {
+            self.position += token.len();
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.message[self.position..].chars().next()
+    }
+}
+
+/// Removes the whitespace around a variant, which is there to lay out the message.
+fn trim_pattern(pattern: &mut Pattern) {
+    if let Some(Element::Text(text)) = pattern.first_mut() {
+        *text = text.trim_start().to_string();
+    }
+    if let Some(Element::Text(text)) = pattern.last_mut() {
+        *text = text.trim_end().to_string();
+    }
+    pattern.retain(|element| !matches!(element, Element::Text(text) if text.is_empty()));
+}
+
+#[cfg(test)]
+mod catalog {
+    use super::*;
+
+    const ITEMS: &str =
+        "{ $count ->\n    [0] No items\n    [one] One item\n   *[other] { $count } items\n}";
+
+    fn translate(catalog: &Catalog, translation: Translation) -> String {
+        catalog.translate(&translation).unwrap()
+    }
+
+    #[test]
+    fn it_should_fill_in_variables() {
+        let catalog = Catalog::new("en").with_message("hello", "Hello { $name }, welcome!");
+
+        assert_eq!(
+            "Hello Anna, welcome!",
+            translate(&catalog, Translation::new("hello").arg("name", "Anna"))
+        );
+        assert_eq!(
+            "Hello {$name}, welcome!",
+            translate(&catalog, Translation::new("hello"))
+        );
+    }
+
+    #[test]
+    fn it_should_choose_the_plural_variant() {
+        let catalog = Catalog::new("en").with_message("items", ITEMS);
+        let items = |count: u32| translate(&catalog, Translation::new("items").arg("count", count));
+
+        assert_eq!("No items", items(0));
+        assert_eq!("One item", items(1));
+        assert_eq!("5 items", items(5));
+    }
+
+    #[test]
+    fn it_should_use_the_plural_rules_of_the_language() {
+        let catalog = Catalog::new("pl").with_message(
+            "files",
+            "{ $n -> [one] { $n } plik [few] { $n } pliki *[many] { $n } plików }",
+        );
+        let files = |n: u32| translate(&catalog, Translation::new("files").arg("n", n));
+
+        assert_eq!("1 plik", files(1));
+        assert_eq!("3 pliki", files(3));
+        assert_eq!("12 plików", files(12));
+    }
+
+    #[test]
+    fn it_should_select_on_text() {
+        let catalog = Catalog::new("en").with_message(
+            "shared",
+            "{ $gender -> [female] She *[other] They } shared a post",
+        );
+
+        assert_eq!(
+            "She shared a post",
+            translate(&catalog, Translation::new("shared").arg("gender", "female"))
+        );
+        assert_eq!(
+            "They shared a post",
+            translate(&catalog, Translation::new("shared"))
+        );
+    }
+
+    #[test]
+    fn it_should_write_messages_which_cannot_be_parsed_as_they_are() {
+        let catalog = Catalog::new("en").with_message("broken", "{ $count -> [one] a }");
+
+        assert_eq!(
+            "{ $count -> [one] a }",
+            translate(&catalog, Translation::new("broken"))
+        );
+        assert_eq!(None, catalog.translate(&Translation::new("missing")));
+    }
+}
@@ -0,0 +1,190 @@
+//! Translating text when rendering.
+//!
+//! `t!("key", name = value)` is used in place of text, within `rsx!` children and attribute values.
+//! It's looked up when rendering, using the `Translator` given to the `Render`.
+//!
+//! ```
+//! use ::core::i18n::Catalog;
+//! use ::core::dom::ToNode;
+//! use ::core::render::render_translated;
+//! use ::core::t;
+//! use ::std::sync::Arc;
+//!
+//! let catalog = Catalog::new("en")
+//!     .with_message("cart", "{ $count ->\n [one] One item\n *[other] { $count } items\n}");
+//!
+//! let node = t!("cart", count = 3).to_node();
+//! assert_eq!(render_translated(&node, Arc::new(catalog)).unwrap(), "3 items");
+//! ```
+
+use crate::dom::AttributeValue;
+use crate::dom::Node;
+use crate::dom::ToAttributeValue;
+use crate::dom::ToNode;
+use ::std::borrow::Cow;
+use ::std::fmt;
+
+mod catalog;
+pub use self::catalog::*;
+
+mod plural;
+pub use self::plural::*;
+
+/// Builds a `Translation`, i.e. `t!("greeting", name = user.name)`.
+#[macro_export]
+macro_rules! t {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::Translation::new($key)
+            $(.arg(::std::stringify!($name), $value))*
+    };
+}
+
+/// Languages written right to left.
+const RTL_LANGUAGES: &[&str] = &["ar", "dv", "fa", "he", "ps", "ur", "yi"];
+
+/// Looks up the text for a `Translation`, in one language.
+pub trait Translator: fmt::Debug + Send + Sync {
+    /// The language being translated to, i.e. `en-GB`.
+    /// This is set as the `lang` of the `<html>` element.
+    fn lang(&self) -> &str;
+
+    /// Returns `None` if the key isn't known,
+    /// in which case the key is written instead.
+    fn translate(&self, translation: &Translation) -> Option<String>;
+
+    /// Set as the `dir` of the `<html>` element.
+    fn dir(&self) -> Direction {
+        Direction::of(self.lang())
+    }
+}
+
+/// The direction text is written in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    /// The direction for the language, i.e. right to left for `ar`.
+    pub fn of(lang: &str) -> Self {
+        if RTL_LANGUAGES.contains(&base_language(lang).as_str()) {
+            Self::RightToLeft
+        } else {
+            Self::LeftToRight
+        }
+    }
+
+    /// The value of the `dir` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::LeftToRight => "ltr",
+            Self::RightToLeft => "rtl",
+        }
+    }
+}
+
+/// Text which is translated when it is rendered. This is built with `t!`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Translation {
+    key: Cow<'static, str>,
+    args: Vec<(Cow<'static, str>, TranslationArg)>,
+}
+
+impl Translation {
+    pub fn new<S>(key: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Adds a value for the message to use, i.e. the `count` in `{ $count } items`.
+    pub fn arg<S, V>(mut self, name: S, value: V) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+        V: Into<TranslationArg>,
+    {
+        self.args.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the value of the arg with this name.
+    pub fn get(&self, name: &str) -> Option<&TranslationArg> {
+        self.args
+            .iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// The translated text, or the key if there is no translation.
+    pub fn text(&self, maybe_translator: Option<&dyn Translator>) -> Cow<'_, str> {
+        maybe_translator
+            .and_then(|translator| translator.translate(self))
+            .map(Cow::Owned)
+            .unwrap_or(Cow::Borrowed(&self.key))
+    }
+}
+
+impl ToNode for Translation {
+    fn to_node(self) -> Node {
+        Node::Translation {
+            translation: Box::new(self),
+        }
+    }
+}
+
+impl ToAttributeValue for Translation {
+    fn to_attribute_value(self) -> AttributeValue {
+        AttributeValue::Translation(Box::new(self))
+    }
+}
+
+/// A value given to a `Translation`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslationArg {
+    Text(Cow<'static, str>),
+    Number(f64),
+}
+
+impl fmt::Display for TranslationArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "{}", text),
+            Self::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl From<&'static str> for TranslationArg {
+    fn from(text: &'static str) -> Self {
+        Self::Text(Cow::Borrowed(text))
+    }
+}
+
+impl From<String> for TranslationArg {
+    fn from(text: String) -> Self {
+        Self::Text(Cow::Owned(text))
+    }
+}
+
+macro_rules! impl_number_arg {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for TranslationArg {
+                fn from(n: $number) -> Self {
+                    Self::Number(n as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_number_arg!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
@@ -0,0 +1,163 @@
+/// The CLDR plural categories, which pick the form of a word for a number.
+/// i.e. English has `One` for "1 item", and `Other` for "2 items".
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Returns the category for the number, in the language given, i.e. `en` or `pt-BR`.
+    ///
+    /// This covers the cardinal rules of the most common languages.
+    /// Languages which aren't known use the English rules.
+    pub fn of(lang: &str, n: f64) -> Self {
+        let is_integer = n.fract() == 0.0;
+        let i = n.abs().trunc() as u64;
+
+        match base_language(lang).as_str() {
+            "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" => Self::Other,
+            "fr" | "pt" if !is_european_portuguese(lang) => match i {
+                0 | 1 => Self::One,
+                _ => Self::Other,
+            },
+            "ru" | "uk" | "be" if is_integer => match (i % 10, i % 100) {
+                (1, 11) => Self::Many,
+                (1, _) => Self::One,
+                (2..=4, 12..=14) => Self::Many,
+                (2..=4, _) => Self::Few,
+                _ => Self::Many,
+            },
+            "pl" if is_integer => match (i, i % 10, i % 100) {
+                (1, _, _) => Self::One,
+                (_, 2..=4, 12..=14) => Self::Many,
+                (_, 2..=4, _) => Self::Few,
+                _ => Self::Many,
+            },
+            "cs" | "sk" if is_integer => match i {
+                1 => Self::One,
+                2..=4 => Self::Few,
+                _ => Self::Other,
+            },
+            "cs" | "sk" => Self::Many,
+            "ar" if is_integer => match (i, i % 100) {
+                (0, _) => Self::Zero,
+                (1, _) => Self::One,
+                (2, _) => Self::Two,
+                (_, 3..=10) => Self::Few,
+                (_, 11..=99) => Self::Many,
+                _ => Self::Other,
+            },
+            "he" if is_integer => match i {
+                1 => Self::One,
+                2 => Self::Two,
+                _ => Self::Other,
+            },
+            "ru" | "uk" | "be" | "pl" | "ar" | "he" => Self::Other,
+            _ if is_integer && i == 1 => Self::One,
+            _ => Self::Other,
+        }
+    }
+
+    /// The name used for the category in messages, i.e. `one`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// i.e. `pt` for `pt-BR`.
+pub(crate) fn base_language(lang: &str) -> String {
+    lang.split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// CLDR gives `pt-PT` the English rules, rather than those of `pt`.
+fn is_european_portuguese(lang: &str) -> bool {
+    let mut parts = lang.split(['-', '_']);
+
+    parts
+        .next()
+        .is_some_and(|base| base.eq_ignore_ascii_case("pt"))
+        && parts
+            .next()
+            .is_some_and(|region| region.eq_ignore_ascii_case("pt"))
+}
+
+#[cfg(test)]
+mod plural_category {
+    use super::*;
+
+    fn categories(lang: &str, numbers: &[f64]) -> Vec<&'static str> {
+        numbers
+            .iter()
+            .map(|n| PluralCategory::of(lang, *n).as_str())
+            .collect()
+    }
+
+    #[test]
+    fn it_should_use_one_and_other_for_english() {
+        assert_eq!(
+            vec!["other", "one", "other", "other"],
+            categories("en-GB", &[0.0, 1.0, 2.0, 1.5])
+        );
+    }
+
+    #[test]
+    fn it_should_treat_zero_as_one_in_french() {
+        assert_eq!(
+            vec!["one", "one", "one", "other"],
+            categories("fr", &[0.0, 1.0, 1.5, 2.0])
+        );
+    }
+
+    #[test]
+    fn it_should_treat_zero_as_one_in_portuguese() {
+        assert_eq!(
+            vec!["one", "one", "one", "other"],
+            categories("pt-BR", &[0.0, 1.0, 1.5, 2.0])
+        );
+        assert_eq!(vec!["one", "other"], categories("pt", &[0.0, 2.0]));
+        assert_eq!(
+            vec!["other", "one", "other"],
+            categories("pt-PT", &[0.0, 1.0, 2.0])
+        );
+    }
+
+    #[test]
+    fn it_should_use_few_and_many_in_russian_and_polish() {
+        assert_eq!(
+            vec!["one", "few", "many", "many", "one", "few", "other"],
+            categories("ru", &[1.0, 3.0, 5.0, 11.0, 21.0, 22.0, 1.5])
+        );
+        assert_eq!(
+            vec!["one", "few", "many", "many", "many", "few"],
+            categories("pl", &[1.0, 3.0, 5.0, 12.0, 21.0, 22.0])
+        );
+    }
+
+    #[test]
+    fn it_should_use_every_category_in_arabic() {
+        assert_eq!(
+            vec!["zero", "one", "two", "few", "many", "other"],
+            categories("ar", &[0.0, 1.0, 2.0, 5.0, 11.0, 100.0])
+        );
+    }
+
+    #[test]
+    fn it_should_only_use_other_in_japanese() {
+        assert_eq!(vec!["other", "other"], categories("ja", &[1.0, 2.0]));
+    }
+}
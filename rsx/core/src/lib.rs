@@ -1,5 +1,20 @@
+/// Pre-rendered HTML for tests, with its tree built by parsing it.
+/// `rsx!` builds the tree from the nodes it was given instead.
+#[cfg(test)]
+macro_rules! new_static {
+    ($html:expr) => {
+        $crate::dom::Node::new_static($html, || $crate::parse::parse_html($html).unwrap())
+    };
+}
+
 pub mod dom;
+pub mod i18n;
+pub mod parse;
 pub mod render;
+pub mod response;
+pub mod sanitize;
+pub mod select;
+pub mod testing;
 
 mod component;
 pub use self::component::*;
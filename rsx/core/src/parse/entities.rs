@@ -0,0 +1,135 @@
+use ::std::borrow::Cow;
+
+include!("entity_table.rs");
+
+/// The longest named entity, i.e. `&CounterClockwiseContourIntegral;`.
+const MAX_ENTITY_LENGTH: usize = 31;
+
+/// Replaces entities, such as `&amp;` and `&#169;`, with the characters they represent.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        match decode_entity(rest) {
+            Some((entity, length)) => {
+                decoded.push_str(&entity);
+                rest = &rest[length..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest["&".len()..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Decodes the entity at the start of `text`,
+/// returning what it represents and how many bytes the entity took up.
+fn decode_entity(text: &str) -> Option<(Cow<'static, str>, usize)> {
+    if let Some(number) = text.strip_prefix("&#") {
+        return decode_numeric_entity(number)
+            .map(|(c, length)| (Cow::Owned(c.to_string()), "&#".len() + length));
+    }
+
+    let end = text
+        .char_indices()
+        .take(MAX_ENTITY_LENGTH + 1)
+        .find(|(_, c)| *c == ';')
+        .map(|(index, _)| index)?;
+    let name = &text["&".len()..end];
+    let length = end + ";".len();
+
+    named_entity(name).map(|entity| (Cow::Borrowed(entity), length))
+}
+
+/// Looks up a named entity, without the `&` and `;`.
+fn named_entity(name: &str) -> Option<&'static str> {
+    NAMED_ENTITIES
+        .binary_search_by(|(entity, _)| entity.cmp(&name))
+        .ok()
+        .map(|index| NAMED_ENTITIES[index].1)
+}
+
+/// Decodes the digits of a numeric entity, i.e. the `169;` of `&#169;`.
+///
+/// The closing `;` is optional, as browsers will decode `&#169` too.
+fn decode_numeric_entity(text: &str) -> Option<(char, usize)> {
+    let (prefix_length, radix) = if text.starts_with('x') || text.starts_with('X') {
+        ("x".len(), 16)
+    } else {
+        (0, 10)
+    };
+
+    let digits = &text[prefix_length..];
+    let digits_length = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    if digits_length == 0 {
+        return None;
+    }
+
+    let c = u32::from_str_radix(&digits[..digits_length], radix)
+        .ok()
+        .filter(|code| *code != 0)
+        .and_then(::std::char::from_u32)
+        .unwrap_or('\u{fffd}');
+
+    let mut length = prefix_length + digits_length;
+    if digits[digits_length..].starts_with(';') {
+        length += ";".len();
+    }
+
+    Some((c, length))
+}
+
+#[cfg(test)]
+mod decode_entities {
+    use super::*;
+
+    #[test]
+    fn it_should_decode_named_entities() {
+        assert_eq!(
+            "Fish & Chips <©>",
+            decode_entities("Fish &amp; Chips &lt;&copy;&gt;")
+        );
+    }
+
+    #[test]
+    fn it_should_decode_every_named_entity() {
+        assert_eq!(
+            "\u{a0}—♥≂̸",
+            decode_entities("&nbsp;&mdash;&hearts;&NotEqualTilde;")
+        );
+        assert!(NAMED_ENTITIES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn it_should_decode_numeric_entities() {
+        assert_eq!("© © ©", decode_entities("&#169; &#xA9; &#XA9;"));
+    }
+
+    #[test]
+    fn it_should_leave_unknown_entities() {
+        assert_eq!(
+            "&unknown; & &; &#xZZ; a&b",
+            decode_entities("&unknown; & &; &#xZZ; a&b")
+        );
+    }
+
+    #[test]
+    fn it_should_decode_numeric_entities_without_semicolons() {
+        assert_eq!("javascript:", decode_entities("&#106avascript&#x3A"));
+    }
+
+    #[test]
+    fn it_should_replace_invalid_code_points() {
+        assert_eq!("\u{fffd}\u{fffd}", decode_entities("&#0;&#xD800;"));
+    }
+}
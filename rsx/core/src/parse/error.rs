@@ -0,0 +1,84 @@
+use ::std::error::Error;
+use ::std::fmt;
+
+pub type Result<N> = ::std::result::Result<N, ParseError>;
+
+/// Why some HTML could not be parsed.
+///
+/// Each position is the byte offset into the HTML, where the problem begins.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParseError {
+    /// An element was opened, and the HTML ended before it was closed.
+    UnclosedElement { name: String, position: usize },
+
+    /// A closing tag was found for an element further out,
+    /// whilst the element which is open needs its own closing tag.
+    MismatchedClosingTag {
+        expected: String,
+        found: String,
+        position: usize,
+    },
+
+    /// The HTML ended part way through a tag.
+    UnclosedTag { position: usize },
+
+    /// A `<!--` was found without a matching `-->`.
+    UnclosedComment { position: usize },
+
+    /// A `<![CDATA[` was found without a matching `]]>`.
+    UnclosedCData { position: usize },
+
+    /// A `</`, `<!`, or `<?` was found without a name after it.
+    ExpectedTagName { position: usize },
+
+    /// An attribute had an `=`, but no value after it.
+    ExpectedAttributeValue { position: usize },
+}
+
+impl ParseError {
+    pub fn position(&self) -> usize {
+        match self {
+            Self::UnclosedElement { position, .. } => *position,
+            Self::MismatchedClosingTag { position, .. } => *position,
+            Self::UnclosedTag { position } => *position,
+            Self::UnclosedComment { position } => *position,
+            Self::UnclosedCData { position } => *position,
+            Self::ExpectedTagName { position } => *position,
+            Self::ExpectedAttributeValue { position } => *position,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedElement { name, .. } => {
+                write!(f, "<{}> is never closed", name)?;
+            }
+            Self::MismatchedClosingTag {
+                expected, found, ..
+            } => {
+                write!(f, "expected </{}>, found </{}>", expected, found)?;
+            }
+            Self::UnclosedTag { .. } => {
+                write!(f, "tag is missing a closing >")?;
+            }
+            Self::UnclosedComment { .. } => {
+                write!(f, "comment is missing a closing -->")?;
+            }
+            Self::UnclosedCData { .. } => {
+                write!(f, "CDATA section is missing a closing ]]>")?;
+            }
+            Self::ExpectedTagName { .. } => {
+                write!(f, "expected a tag name")?;
+            }
+            Self::ExpectedAttributeValue { .. } => {
+                write!(f, "expected an attribute value after =")?;
+            }
+        }
+
+        write!(f, ", at byte {}", self.position())
+    }
+}
+
+impl Error for ParseError {}
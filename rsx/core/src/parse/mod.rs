@@ -0,0 +1,870 @@
+//! Parses HTML at runtime, into a `Node`.
+//!
+//! This is for HTML which is only known at runtime,
+//! such as content from a CMS, or the output of a markdown renderer.
+//!
+//! ```
+//! use ::core::parse::parse_html;
+//! use ::core::render::render;
+//!
+//! let node = parse_html("<p class=intro>Fish &amp; Chips<br></p>").unwrap();
+//! assert_eq!(render(&node).unwrap(), r#"<p class="intro">Fish &amp; Chips<br/></p>"#);
+//! ```
+//!
+//! Entities in text and attribute values are decoded, and escaped again when rendered.
+//! This means rendering the result, and parsing it again, gives back the same HTML.
+//!
+//! End tags which can be left out, such as those of `<p>`, `<li>`, and `<td>`, are implied,
+//! and closing tags which match no open element are skipped.
+//! So this is not used for the HTML `rsx!` pre-renders, which keeps the tree it was rendered from.
+
+use crate::dom::Attribute;
+use crate::dom::AttributeValue;
+use crate::dom::Attributes;
+use crate::dom::Node;
+use crate::render::TextMode;
+
+mod error;
+pub use self::error::*;
+
+mod entities;
+pub(crate) use self::entities::decode_entities;
+
+include!("void_elements.rs");
+
+/// Elements whose end tag can be left out, and the elements whose start tag closes them,
+/// i.e. `<li>a<li>b` is two items. They are also closed by the end of the element
+/// they are within, or by the end of the input.
+///
+/// See https://html.spec.whatwg.org/multipage/syntax.html#optional-tags
+const OPTIONAL_END_TAGS: &[(&str, &[&str])] = &[
+    (
+        "p",
+        &[
+            "address",
+            "article",
+            "aside",
+            "blockquote",
+            "dd",
+            "details",
+            "dialog",
+            "div",
+            "dl",
+            "dt",
+            "fieldset",
+            "figcaption",
+            "figure",
+            "footer",
+            "form",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "header",
+            "hgroup",
+            "hr",
+            "li",
+            "main",
+            "menu",
+            "nav",
+            "ol",
+            "p",
+            "pre",
+            "section",
+            "table",
+            "ul",
+        ],
+    ),
+    ("li", &["li"]),
+    ("dt", &["dt", "dd"]),
+    ("dd", &["dt", "dd"]),
+    ("option", &["option", "optgroup"]),
+    ("optgroup", &["optgroup"]),
+    ("tr", &["tr"]),
+    ("td", &["td", "th", "tr"]),
+    ("th", &["td", "th", "tr"]),
+];
+
+/// Parses a fragment of HTML.
+///
+/// A single element is returned as is,
+/// and multiple top level nodes are returned within a `Node::Fragment`.
+pub fn parse_html(html: &str) -> Result<Node> {
+    let mut parser = Parser::new(html);
+    let mut nodes = parser.parse_nodes()?;
+
+    let node = match nodes.len() {
+        0 => Node::Empty,
+        1 => nodes.remove(0),
+        _ => Node::Fragment { children: nodes },
+    };
+
+    Ok(node)
+}
+
+fn is_void_element(name: &str) -> bool {
+    VOID_ELEMENTS
+        .iter()
+        .any(|void| void.eq_ignore_ascii_case(name))
+}
+
+/// The elements which close `name` when they start, if its end tag can be left out.
+fn closed_by(name: &str) -> Option<&'static [&'static str]> {
+    OPTIONAL_END_TAGS
+        .iter()
+        .find(|(element, _)| element.eq_ignore_ascii_case(name))
+        .map(|(_, closed_by)| *closed_by)
+}
+
+fn has_optional_end_tag(name: &str) -> bool {
+    closed_by(name).is_some()
+}
+
+fn is_closed_by(name: &str, next: &str) -> bool {
+    closed_by(name).is_some_and(|closed_by| {
+        closed_by
+            .iter()
+            .any(|element| element.eq_ignore_ascii_case(next))
+    })
+}
+
+struct Parser<'a> {
+    html: &'a str,
+    position: usize,
+    /// The elements which are open, from the outermost to the innermost.
+    open: Vec<OpenElement<'a>>,
+}
+
+/// The name of an open element, and where it was opened.
+type OpenElement<'a> = (&'a str, usize);
+
+impl<'a> Parser<'a> {
+    fn new(html: &'a str) -> Self {
+        Self {
+            html,
+            position: 0,
+            open: Vec::new(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.html[self.position..]
+    }
+
+    fn is_end(&self) -> bool {
+        self.position >= self.html.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_after_less_than(&self) -> Option<char> {
+        self.rest()["<".len()..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Moves past everything up until `is_end` is true, or the input ends.
+    fn take_until<F>(&mut self, is_end: F) -> &'a str
+    where
+        F: Fn(char) -> bool,
+    {
+        let rest = self.rest();
+        let length = rest.find(is_end).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    /// Parses nodes, until the innermost open element is closed.
+    /// When no element is open, this parses until the end of the input.
+    ///
+    /// A closing tag which matches no open element is skipped.
+    fn parse_nodes(&mut self) -> Result<Vec<Node>> {
+        let parent = self.open.last().copied();
+        let mut nodes = Vec::new();
+
+        loop {
+            if self.is_end() {
+                return match parent {
+                    Some((name, position)) if !has_optional_end_tag(name) => {
+                        Err(ParseError::UnclosedElement {
+                            name: name.to_string(),
+                            position,
+                        })
+                    }
+                    _ => Ok(nodes),
+                };
+            }
+
+            if self.rest().starts_with("</") {
+                let position = self.position;
+                let name = self.parse_closing_tag()?;
+                let (parent_name, _) = match parent {
+                    Some(parent) if self.is_open(name) => parent,
+                    _ => continue,
+                };
+
+                if parent_name.eq_ignore_ascii_case(name) {
+                    return Ok(nodes);
+                } else if has_optional_end_tag(parent_name) {
+                    // The closing tag is for an element further out, which closes this one.
+                    self.position = position;
+                    return Ok(nodes);
+                } else {
+                    return Err(ParseError::MismatchedClosingTag {
+                        expected: parent_name.to_string(),
+                        found: name.to_string(),
+                        position,
+                    });
+                }
+            }
+
+            if let Some((parent_name, _)) = parent {
+                if self.is_next_element() && is_closed_by(parent_name, self.peek_element_name()) {
+                    return Ok(nodes);
+                }
+            }
+
+            let node = if self.rest().starts_with("<!--") {
+                self.parse_comment()?
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.parse_cdata()?
+            } else if self.rest().starts_with("<!") {
+                self.parse_declaration()?
+            } else if self.rest().starts_with("<?") {
+                self.parse_processing_instruction()?
+            } else if self.is_next_element() {
+                self.parse_element()?
+            } else {
+                self.parse_text()
+            };
+
+            push_node(&mut nodes, node);
+        }
+    }
+
+    fn is_open(&self, name: &str) -> bool {
+        self.open
+            .iter()
+            .any(|(open_name, _)| open_name.eq_ignore_ascii_case(name))
+    }
+
+    /// The name of the element which starts next, without moving past it.
+    fn peek_element_name(&self) -> &'a str {
+        let rest = &self.rest()["<".len()..];
+        let length = rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+
+        &rest[..length]
+    }
+
+    fn is_next_element(&self) -> bool {
+        self.rest().starts_with('<')
+            && self
+                .peek_after_less_than()
+                .map(|c| c.is_ascii_alphabetic())
+                .unwrap_or(false)
+    }
+
+    /// Parses text up to the next tag.
+    /// A `<` which doesn't start a tag, i.e. `a < b`, is kept as text.
+    fn parse_text(&mut self) -> Node {
+        let start = self.position;
+        if self.rest().starts_with('<') {
+            self.position += "<".len();
+        }
+        self.take_until(|c| c == '<');
+
+        Node::new_text(decode_entities(&self.html[start..self.position]))
+    }
+
+    fn parse_comment(&mut self) -> Result<Node> {
+        let start = self.position;
+        self.position += "<!--".len();
+
+        let length = self
+            .rest()
+            .find("-->")
+            .ok_or(ParseError::UnclosedComment { position: start })?;
+        let contents = self.rest()[..length].trim();
+        self.position += length + "-->".len();
+
+        let children = if contents.is_empty() {
+            None
+        } else {
+            Some(vec![Node::new_text(contents.to_string())])
+        };
+
+        Ok(Node::Comment { children })
+    }
+
+    /// Parses a `<![CDATA[ ... ]]>`, keeping the contents as they are.
+    fn parse_cdata(&mut self) -> Result<Node> {
+        let start = self.position;
+        self.position += "<![CDATA[".len();
+
+        let length = self
+            .rest()
+            .find("]]>")
+            .ok_or(ParseError::UnclosedCData { position: start })?;
+        let contents = &self.rest()[..length];
+        self.position += length + "]]>".len();
+
+        let children = if contents.is_empty() {
+            None
+        } else {
+            Some(vec![Node::new_text(contents.to_string())])
+        };
+
+        Ok(Node::CData { children })
+    }
+
+    /// Parses a `<!doctype html>`, or similar declaration.
+    fn parse_declaration(&mut self) -> Result<Node> {
+        let start = self.position;
+        self.position += "<!".len();
+
+        let name = self.take_until(|c| c.is_whitespace() || c == '>');
+        if name.is_empty() {
+            return Err(ParseError::ExpectedTagName {
+                position: self.position,
+            });
+        }
+
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                None => return Err(ParseError::UnclosedTag { position: start }),
+                Some('>') => {
+                    self.position += ">".len();
+                    break;
+                }
+                Some(quote @ '"') | Some(quote @ '\'') => {
+                    let word_start = self.position;
+                    self.position += quote.len_utf8();
+                    self.take_until(|c| c == quote);
+                    if self.is_end() {
+                        return Err(ParseError::UnclosedTag { position: start });
+                    }
+                    self.position += quote.len_utf8();
+
+                    let word = &self.html[word_start..self.position];
+                    attributes.push(Attribute::new_owned(
+                        word.to_string(),
+                        AttributeValue::ImplicitTrue,
+                    ));
+                }
+                Some(_) => {
+                    let word = self.take_until(|c| c.is_whitespace() || c == '>');
+                    attributes.push(Attribute::new_owned(
+                        word.to_string(),
+                        AttributeValue::ImplicitTrue,
+                    ));
+                }
+            }
+        }
+
+        Ok(Node::Doctype {
+            name: name.to_string().into(),
+            attributes: to_maybe_attributes(attributes),
+        })
+    }
+
+    /// Parses a `<?xml version="1.0"?>`, or similar processing instruction.
+    /// The contents are read as attributes, as they are for `<?xml` and `<?xml-stylesheet`.
+    fn parse_processing_instruction(&mut self) -> Result<Node> {
+        let start = self.position;
+        self.position += "<?".len();
+
+        let target = self.take_until(|c| c.is_whitespace() || c == '?' || c == '>');
+        if target.is_empty() {
+            return Err(ParseError::ExpectedTagName {
+                position: self.position,
+            });
+        }
+
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+
+            let rest = self.rest();
+            if rest.starts_with("?>") {
+                self.position += "?>".len();
+                break;
+            }
+
+            let key = self.take_until(|c| c.is_whitespace() || c == '=' || c == '?' || c == '>');
+            if key.is_empty() {
+                return Err(ParseError::UnclosedTag { position: start });
+            }
+
+            self.skip_whitespace();
+            let value = if self.peek() == Some('=') {
+                self.position += "=".len();
+                self.skip_whitespace();
+                let value = self.parse_attribute_value(start)?;
+                AttributeValue::Text(decode_entities(value).into())
+            } else {
+                AttributeValue::ImplicitTrue
+            };
+
+            attributes.push(Attribute::new_owned(key.to_string(), value));
+        }
+
+        Ok(Node::ProcessingInstruction {
+            target: target.to_string().into(),
+            attributes: to_maybe_attributes(attributes),
+        })
+    }
+
+    fn parse_element(&mut self) -> Result<Node> {
+        let start = self.position;
+        self.position += "<".len();
+
+        let name = self.take_until(|c| c.is_whitespace() || c == '/' || c == '>');
+        let (attributes, is_self_closing) = self.parse_attributes(start)?;
+
+        if is_self_closing || is_void_element(name) {
+            return Ok(Node::new_self_closing(name.to_string(), attributes));
+        }
+
+        let children = match TextMode::for_element(name) {
+            TextMode::Normal | TextMode::Comment | TextMode::Xml | TextMode::CData => {
+                self.open.push((name, start));
+                let children = self.parse_nodes()?;
+                self.open.pop();
+                children
+            }
+            TextMode::RawText { .. } => {
+                let text = self.parse_raw_text((name, start))?;
+                vec![Node::new_text(text.to_string())]
+            }
+            TextMode::EscapableRawText => {
+                let text = self.parse_raw_text((name, start))?;
+                vec![Node::new_text(decode_entities(text))]
+            }
+        };
+
+        let node = match children.as_slice() {
+            [] => Node::OpenEmpty {
+                name: name.to_string().into(),
+                attributes,
+            },
+            [Node::Text { contents }] if contents.is_empty() => Node::OpenEmpty {
+                name: name.to_string().into(),
+                attributes,
+            },
+            _ => Node::OpenWithChildren {
+                name: name.to_string().into(),
+                attributes,
+                children,
+            },
+        };
+
+        Ok(node)
+    }
+
+    /// Parses the attributes of a tag, up to and including the closing `>`.
+    /// Returns the attributes, and if the tag was closed with `/>`.
+    fn parse_attributes(&mut self, start: usize) -> Result<(Option<Attributes>, bool)> {
+        let mut attributes = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(ParseError::UnclosedTag { position: start });
+            } else if rest.starts_with("/>") {
+                self.position += "/>".len();
+                return Ok((to_maybe_attributes(attributes), true));
+            } else if rest.starts_with('>') {
+                self.position += ">".len();
+                return Ok((to_maybe_attributes(attributes), false));
+            } else if rest.starts_with('/') {
+                self.position += "/".len();
+                continue;
+            }
+
+            let key = self.take_until(|c| c.is_whitespace() || c == '=' || c == '/' || c == '>');
+            if key.is_empty() {
+                // A lone `=`, which has no key.
+                self.position += "=".len();
+                continue;
+            }
+
+            self.skip_whitespace();
+            let value = if self.peek() == Some('=') {
+                self.position += "=".len();
+                self.skip_whitespace();
+                let value = self.parse_attribute_value(start)?;
+                AttributeValue::Text(decode_entities(value).into())
+            } else {
+                AttributeValue::ImplicitTrue
+            };
+
+            attributes.push(Attribute::new_owned(key.to_string(), value));
+        }
+    }
+
+    fn parse_attribute_value(&mut self, start: usize) -> Result<&'a str> {
+        match self.peek() {
+            None => Err(ParseError::UnclosedTag { position: start }),
+            Some(quote @ '"') | Some(quote @ '\'') => {
+                self.position += quote.len_utf8();
+                let value = self.take_until(|c| c == quote);
+                if self.is_end() {
+                    return Err(ParseError::UnclosedTag { position: start });
+                }
+                self.position += quote.len_utf8();
+
+                Ok(value)
+            }
+            Some(_) => {
+                let position = self.position;
+                let value = self.take_until(|c| c.is_whitespace() || c == '>');
+                if value.is_empty() {
+                    return Err(ParseError::ExpectedAttributeValue { position });
+                }
+
+                Ok(value)
+            }
+        }
+    }
+
+    /// Parses the contents of an element, such as `<script>`, which cannot hold other elements.
+    /// This includes the closing tag.
+    fn parse_raw_text(&mut self, element: OpenElement<'a>) -> Result<&'a str> {
+        let (name, position) = element;
+        let rest = self.rest();
+        let length = find_closing_tag(rest, name).ok_or_else(|| ParseError::UnclosedElement {
+            name: name.to_string(),
+            position,
+        })?;
+
+        self.position += length;
+        self.parse_closing_tag()?;
+
+        Ok(&rest[..length])
+    }
+
+    /// Parses a `</name>`, and returns the name.
+    fn parse_closing_tag(&mut self) -> Result<&'a str> {
+        let start = self.position;
+        self.position += "</".len();
+
+        let name = self.take_until(|c| c.is_whitespace() || c == '>');
+        if name.is_empty() {
+            return Err(ParseError::ExpectedTagName {
+                position: self.position,
+            });
+        }
+
+        self.skip_whitespace();
+        if self.peek() != Some('>') {
+            return Err(ParseError::UnclosedTag { position: start });
+        }
+        self.position += ">".len();
+
+        Ok(name)
+    }
+}
+
+/// Adds the node, merging it into the previous node if they are both text.
+fn push_node(nodes: &mut Vec<Node>, node: Node) {
+    if let Node::Text { contents } = &node {
+        if let Some(Node::Text {
+            contents: last_contents,
+        }) = nodes.last_mut()
+        {
+            last_contents.to_mut().push_str(contents);
+            return;
+        }
+    }
+
+    nodes.push(node);
+}
+
+fn to_maybe_attributes(attributes: Vec<Attribute>) -> Option<Attributes> {
+    if attributes.is_empty() {
+        None
+    } else {
+        Some(Attributes::Owned(attributes))
+    }
+}
+
+/// Finds `</name`, ignoring case, followed by the end of the tag name.
+fn find_closing_tag(text: &str, name: &str) -> Option<usize> {
+    text.match_indices("</")
+        .map(|(index, _)| index)
+        .find(|index| {
+            let after = &text[index + "</".len()..];
+            after.len() >= name.len()
+                && after.as_bytes()[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+                && after[name.len()..]
+                    .chars()
+                    .next()
+                    .map(|c| c.is_whitespace() || c == '>')
+                    .unwrap_or(true)
+        })
+}
+
+#[cfg(test)]
+mod parse_html {
+    use super::*;
+    use crate::render::render;
+
+    fn parse_and_render(html: &str) -> String {
+        render(parse_html(html).unwrap()).unwrap()
+    }
+
+    fn assert_round_trip(html: &str, expected: &str) {
+        let rendered = parse_and_render(html);
+        assert_eq!(expected, rendered);
+        assert_eq!(expected, parse_and_render(&rendered));
+    }
+
+    #[test]
+    fn it_should_parse_elements() {
+        assert_round_trip(
+            "<article><h1>Title</h1><p>Hello <b>world</b>!</p></article>",
+            "<article><h1>Title</h1><p>Hello <b>world</b>!</p></article>",
+        );
+    }
+
+    #[test]
+    fn it_should_parse_empty_input() {
+        assert!(matches!(parse_html("").unwrap(), Node::Empty));
+    }
+
+    #[test]
+    fn it_should_parse_multiple_top_level_nodes_as_a_fragment() {
+        let node = parse_html("<p>a</p> <p>b</p>").unwrap();
+
+        assert!(matches!(&node, Node::Fragment { children } if children.len() == 3));
+        assert_eq!("<p>a</p> <p>b</p>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_keep_whitespace() {
+        assert_round_trip("<ul>\n  <li>a</li>\n</ul>\n", "<ul>\n  <li>a</li>\n</ul>\n");
+    }
+
+    #[test]
+    fn it_should_parse_void_elements() {
+        assert_round_trip(
+            r#"<p>a<br>b<img src="a.png" alt=""><BR></p>"#,
+            r#"<p>a<br/>b<img src="a.png" alt=""/><BR/></p>"#,
+        );
+    }
+
+    #[test]
+    fn it_should_parse_self_closing_elements() {
+        assert_round_trip("<div /><span/>", "<div/><span/>");
+    }
+
+    #[test]
+    fn it_should_parse_empty_elements() {
+        let node = parse_html("<div></div>").unwrap();
+
+        assert!(matches!(&node, Node::OpenEmpty { name, .. } if name == "div"));
+        assert_eq!("<div></div>", render(&node).unwrap());
+    }
+
+    #[test]
+    fn it_should_parse_attributes() {
+        assert_round_trip(
+            r#"<input type="text" name='q' value=search disabled data-x = "1">"#,
+            r#"<input type="text" name="q" value="search" disabled data-x="1"/>"#,
+        );
+    }
+
+    #[test]
+    fn it_should_escape_quotes_in_attributes() {
+        assert_round_trip(
+            r#"<a title='He said "hi"' href="/?a=1&b=2">x</a>"#,
+            r#"<a title="He said &quot;hi&quot;" href="/?a=1&amp;b=2">x</a>"#,
+        );
+    }
+
+    #[test]
+    fn it_should_decode_entities_in_text() {
+        assert_round_trip(
+            "<p>Fish &amp; Chips &copy; &#8364;5 &lt;b&gt; a & b</p>",
+            "<p>Fish &amp; Chips © €5 &lt;b&gt; a &amp; b</p>",
+        );
+    }
+
+    #[test]
+    fn it_should_keep_a_lone_less_than_as_text() {
+        assert_round_trip("<p>a < b</p>", "<p>a &lt; b</p>");
+    }
+
+    #[test]
+    fn it_should_parse_comments() {
+        assert_round_trip(
+            "<!--Hello--><p>a</p><!-- -->",
+            "<!-- Hello --><p>a</p><!-- -->",
+        );
+    }
+
+    #[test]
+    fn it_should_parse_doctypes() {
+        assert_round_trip(
+            "<!DOCTYPE html>\n<html><body></body></html>",
+            "<!DOCTYPE html>\n<html><body></body></html>",
+        );
+    }
+
+    #[test]
+    fn it_should_parse_cdata() {
+        assert_round_trip(
+            "<svg><text><![CDATA[a < b & c]]></text><![CDATA[]]></svg>",
+            "<svg><text><![CDATA[a < b & c]]></text><![CDATA[]]></svg>",
+        );
+        assert_eq!(
+            ParseError::UnclosedCData { position: 0 },
+            parse_html("<![CDATA[ a").unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn it_should_parse_processing_instructions() {
+        assert_round_trip(
+            r#"<?xml version='1.0' encoding="UTF-8" ?><?xml-stylesheet href="a.xsl"?><rss></rss>"#,
+            r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet href="a.xsl"?><rss></rss>"#,
+        );
+        assert_eq!(
+            ParseError::UnclosedTag { position: 0 },
+            parse_html(r#"<?xml version="1.0">"#).unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn it_should_keep_raw_text_verbatim() {
+        assert_round_trip(
+            "<script>if (a < b && c) { x = '</b>'; }</script><style>a > b { }</style>",
+            "<script>if (a < b && c) { x = '</b>'; }</script><style>a > b { }</style>",
+        );
+    }
+
+    #[test]
+    fn it_should_decode_escapable_raw_text() {
+        assert_round_trip(
+            "<textarea><b>a &amp; b</textarea><title>A &amp; B</title>",
+            "<textarea>&lt;b>a &amp; b</textarea><title>A &amp; B</title>",
+        );
+    }
+
+    #[test]
+    fn it_should_match_closing_tags_ignoring_case() {
+        assert_round_trip("<DIV>a</div>", "<DIV>a</DIV>");
+    }
+
+    #[test]
+    fn it_should_close_paragraphs_without_end_tags() {
+        assert_round_trip("<p>a<p>b", "<p>a</p><p>b</p>");
+        assert_round_trip(
+            "<div><p>a<h2>b</h2><p>c<ul><li>d</ul></div>",
+            "<div><p>a</p><h2>b</h2><p>c</p><ul><li>d</li></ul></div>",
+        );
+        assert_round_trip("<p>a <b>b</b> c</P>", "<p>a <b>b</b> c</p>");
+    }
+
+    #[test]
+    fn it_should_close_list_items_without_end_tags() {
+        assert_round_trip("<ul><li>a<li>b</ul>", "<ul><li>a</li><li>b</li></ul>");
+        assert_round_trip(
+            "<ol><li>a<ul><li>b<li>c</ul><li>d</ol>",
+            "<ol><li>a<ul><li>b</li><li>c</li></ul></li><li>d</li></ol>",
+        );
+        assert_round_trip(
+            "<ul><li><p>a<li>b</ul>",
+            "<ul><li><p>a</p></li><li>b</li></ul>",
+        );
+    }
+
+    #[test]
+    fn it_should_close_definitions_without_end_tags() {
+        assert_round_trip(
+            "<dl><dt>a<dt>b<dd>c<dd>d<dt>e</dl>",
+            "<dl><dt>a</dt><dt>b</dt><dd>c</dd><dd>d</dd><dt>e</dt></dl>",
+        );
+    }
+
+    #[test]
+    fn it_should_close_options_without_end_tags() {
+        assert_round_trip(
+            "<select><option>a<option selected>b<optgroup><option>c<optgroup><option>d</select>",
+            concat!(
+                "<select><option>a</option><option selected>b</option>",
+                "<optgroup><option>c</option></optgroup><optgroup><option>d</option></optgroup></select>",
+            ),
+        );
+    }
+
+    #[test]
+    fn it_should_close_table_rows_and_cells_without_end_tags() {
+        assert_round_trip(
+            "<table><tr><th>a<th>b<tr><td>c<td>d</table>",
+            "<table><tr><th>a</th><th>b</th></tr><tr><td>c</td><td>d</td></tr></table>",
+        );
+    }
+
+    #[test]
+    fn it_should_skip_stray_closing_tags() {
+        assert_round_trip("a</br><div>b</span></div></div>", "a<div>b</div>");
+    }
+
+    #[test]
+    fn it_should_error_on_unclosed_elements() {
+        assert_eq!(
+            ParseError::UnclosedElement {
+                name: "span".to_string(),
+                position: 5,
+            },
+            parse_html("<div><span>a").unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn it_should_error_on_mismatched_closing_tags() {
+        assert_eq!(
+            ParseError::MismatchedClosingTag {
+                expected: "b".to_string(),
+                found: "div".to_string(),
+                position: 9,
+            },
+            parse_html("<div><b>a</div>").unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn it_should_error_on_unclosed_tags_and_comments() {
+        assert_eq!(
+            ParseError::UnclosedTag { position: 3 },
+            parse_html(r#"<p><a href="/""#).unwrap_err(),
+        );
+        assert_eq!(
+            ParseError::UnclosedComment { position: 0 },
+            parse_html("<!-- a").unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn it_should_error_on_missing_attribute_values() {
+        assert_eq!(
+            ParseError::ExpectedAttributeValue { position: 8 },
+            parse_html("<a href=>x</a>").unwrap_err(),
+        );
+    }
+}
@@ -0,0 +1,9 @@
+// Elements which never have children, and so are never closed.
+// See https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+//
+// This file is also included by the `rsx!` parser, so both agree on which elements are void.
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
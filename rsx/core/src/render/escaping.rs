@@ -0,0 +1,197 @@
+// Writes text and attribute values, escaping them for where they are written.
+//
+// This file is also included by the `rsx!` parser, so HTML which is pre-rendered
+// at compile time is escaped the same as HTML rendered at runtime.
+// It must not `use` anything from the crate it is included in.
+
+/// How text is written out, which depends on the element it is within.
+///
+/// See https://html.spec.whatwg.org/multipage/syntax.html#elements-2
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TextMode {
+    /// `&`, `<`, and `>` are escaped.
+    Normal,
+
+    /// For `<script>` and `<style>`.
+    /// Text is written verbatim, except for anything that would close the element early.
+    /// i.e. `</script` is written as `<\/script`.
+    RawText { element: &'static str },
+
+    /// For `<textarea>` and `<title>`.
+    /// These cannot contain elements, so `&` and `<` are escaped.
+    EscapableRawText,
+
+    /// For the contents of `<!-- -->` comments.
+    /// Text is written as given, except for anything that would end the comment early.
+    /// i.e. `-->` is written as `-- >`.
+    Comment,
+
+    /// Text within XML, where `&`, `<`, and `>` are escaped.
+    Xml,
+
+    /// For the contents of `<![CDATA[ ]]>`, and of `<script>` and `<style>` within XML.
+    /// Text is written as given, except `]]>` is split across two sections.
+    CData,
+}
+
+impl TextMode {
+    pub fn for_element(name: &str) -> Self {
+        if name.eq_ignore_ascii_case("script") {
+            Self::RawText { element: "script" }
+        } else if name.eq_ignore_ascii_case("style") {
+            Self::RawText { element: "style" }
+        } else if name.eq_ignore_ascii_case("textarea") || name.eq_ignore_ascii_case("title") {
+            Self::EscapableRawText
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// The same as `for_element`, for XML. Names are case sensitive in XML.
+    pub fn for_xml_element(name: &str) -> Self {
+        match name {
+            "script" | "style" => Self::CData,
+            _ => Self::Xml,
+        }
+    }
+
+    /// Returns true for `<script>` and `<style>`, where text split across nodes
+    /// could hide a `</script`, and so must be written in one go.
+    pub fn is_raw_text(self) -> bool {
+        matches!(self, Self::RawText { .. })
+    }
+
+    pub fn write_text(self, buffer: &mut String, text: &str) {
+        match self {
+            Self::Normal => write_escaped_text(buffer, text),
+            Self::RawText { element } => write_raw_text(buffer, element, text),
+            Self::EscapableRawText => write_escapable_raw_text(buffer, text),
+            Self::Comment => write_comment_text(buffer, text),
+            Self::Xml => write_escaped_xml_text(buffer, text),
+            Self::CData => write_cdata_text(buffer, text),
+        }
+    }
+}
+
+fn write_raw_text(buffer: &mut String, element: &str, text: &str) {
+    let mut rest = text;
+
+    while let Some(index) = find_closing_tag(rest, element) {
+        buffer.push_str(&rest[..index]);
+        buffer.push_str("<\\/");
+        rest = &rest[index + "</".len()..];
+    }
+
+    buffer.push_str(rest);
+}
+
+/// Finds `</element`, ignoring case.
+fn find_closing_tag(text: &str, element: &str) -> Option<usize> {
+    text.match_indices("</")
+        .map(|(index, _)| index)
+        .find(|index| {
+            let after = &text.as_bytes()[index + "</".len()..];
+            after.len() >= element.len()
+                && after[..element.len()].eq_ignore_ascii_case(element.as_bytes())
+        })
+}
+
+/// Writes text within a comment, as given, except for anything which would end it early.
+/// i.e. `-->` is written as `-- >`, and `--!>` as `--! >`.
+///
+/// This checks what has been written, so it also finds them when split across nodes.
+fn write_comment_text(buffer: &mut String, text: &str) {
+    for c in text.chars() {
+        if c == '>' && (buffer.ends_with("--") || buffer.ends_with("--!")) {
+            buffer.push(' ');
+        }
+        buffer.push(c);
+    }
+}
+
+fn write_escapable_raw_text(buffer: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            c => buffer.push(c),
+        }
+    }
+}
+
+/// Writes text which is within an element, escaping `&`, `<`, and `>`.
+pub(crate) fn write_escaped_text(buffer: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            c => buffer.push(c),
+        }
+    }
+}
+
+/// Writes the value of an attribute, escaping `&`, `<`, `>`, and `"`.
+pub(crate) fn write_escaped_attribute(buffer: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            '"' => buffer.push_str("&quot;"),
+            c => buffer.push(c),
+        }
+    }
+}
+
+/// Writes text within an XML element, escaping `&`, `<`, and `>`.
+/// Characters which cannot appear in XML, such as most control characters, are left out.
+pub(crate) fn write_escaped_xml_text(buffer: &mut String, text: &str) {
+    for c in text.chars().filter(|c| is_xml_char(*c)) {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            c => buffer.push(c),
+        }
+    }
+}
+
+/// Writes the value of an XML attribute.
+///
+/// As well as `&`, `<`, `>`, and `"`, tabs and newlines are escaped,
+/// as XML parsers would otherwise turn them into spaces.
+pub(crate) fn write_escaped_xml_attribute(buffer: &mut String, text: &str) {
+    for c in text.chars().filter(|c| is_xml_char(*c)) {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            '"' => buffer.push_str("&quot;"),
+            '\t' => buffer.push_str("&#9;"),
+            '\n' => buffer.push_str("&#10;"),
+            '\r' => buffer.push_str("&#13;"),
+            c => buffer.push(c),
+        }
+    }
+}
+
+/// Writes text within a `<![CDATA[ ... ]]>` section.
+/// A `]]>` is split across two sections, so it cannot end the section early.
+pub(crate) fn write_cdata_text(buffer: &mut String, text: &str) {
+    for c in text.chars().filter(|c| is_xml_char(*c)) {
+        buffer.push(c);
+        if c == '>' && buffer.ends_with("]]>") {
+            buffer.truncate(buffer.len() - ">".len());
+            buffer.push_str("]]><![CDATA[>");
+        }
+    }
+}
+
+/// See https://www.w3.org/TR/xml/#charsets
+fn is_xml_char(c: char) -> bool {
+    matches!(
+        c,
+        '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..
+    )
+}
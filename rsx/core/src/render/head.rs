@@ -0,0 +1,201 @@
+use crate::dom::deduplication_key;
+use crate::dom::for_each_element;
+use crate::dom::AttributeValue;
+use crate::dom::Node;
+
+/// `<meta>` attributes which say what the meta is for.
+/// A later `<meta>` with the same one of these replaces the one before it.
+const META_KEYS: &[&str] = &["name", "property", "http-equiv", "charset"];
+
+/// The elements of the document's `<head>`.
+///
+/// Whilst rendering, the children of `<head>`, and any `<slot:head>` content which no layout has taken,
+/// are collected here. They are written into the `<head>` once the rest of the document has been rendered,
+/// so components anywhere in the page can set them.
+/// When streaming with `Render::render_to`, they are collected first, and written when the `<head>` is reached.
+///
+/// A later `<title>` replaces the one before it, as does a `<meta>` with the same
+/// `name`, `property`, `http-equiv`, or `charset`, and a later `<link rel="canonical">`.
+/// Unless that value is translated, as it isn't known until it's rendered.
+/// Repeated `<link>` and `<script>` elements are only kept once.
+#[derive(Clone, Debug, Default)]
+pub struct Head {
+    elements: Vec<HeadElement>,
+}
+
+#[derive(Clone, Debug)]
+struct HeadElement {
+    /// Elements with the same key replace each other.
+    key: Option<String>,
+    node: Node,
+}
+
+impl Head {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the node to the end of the head,
+    /// or in place of the element it replaces.
+    pub fn push(&mut self, node: Node) {
+        for_each_element(node, &mut |node| {
+            let key = head_key(&node);
+            let maybe_existing = key.as_ref().and_then(|key| {
+                self.elements
+                    .iter_mut()
+                    .find(|element| element.key.as_ref() == Some(key))
+            });
+
+            match maybe_existing {
+                Some(existing) => existing.node = node,
+                None => self.elements.push(HeadElement { key, node }),
+            }
+        });
+    }
+
+    /// The text of the `<title>`.
+    pub fn title(&self) -> Option<String> {
+        self.iter()
+            .find(|node| is_named(node, "title"))
+            .map(Node::text_content)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        self.elements.iter().map(|element| &element.node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+}
+
+/// What the element is for, where only one of them should be in the head.
+fn head_key(node: &Node) -> Option<String> {
+    let name = node.name()?;
+
+    if name.eq_ignore_ascii_case("title") {
+        return Some("title".to_string());
+    }
+
+    if name.eq_ignore_ascii_case("meta") {
+        let (key, value) = META_KEYS.iter().find_map(|key| {
+            node.attribute(key)
+                .filter(|value| !matches!(value, AttributeValue::ImplicitFalse))
+                .map(|value| (key, value))
+        })?;
+
+        return attribute_text(value)
+            .map(|value| format!("meta {}={}", key, value.to_ascii_lowercase()));
+    }
+
+    if name.eq_ignore_ascii_case("link") {
+        if let Some(AttributeValue::Text(rel)) = node.attribute("rel") {
+            if rel.eq_ignore_ascii_case("canonical") {
+                return Some("link canonical".to_string());
+            }
+        }
+    }
+
+    deduplication_key(node)
+}
+
+/// The value as it is rendered.
+/// Translations are `None`, as what they are depends on the `Translator`.
+fn attribute_text(value: &AttributeValue) -> Option<String> {
+    let text = match value {
+        AttributeValue::ImplicitFalse | AttributeValue::ImplicitTrue => String::new(),
+        AttributeValue::Text(text) => text.to_string(),
+        AttributeValue::UnsignedInteger(num) => num.to_string(),
+        AttributeValue::SignedInteger(num) => num.to_string(),
+        AttributeValue::UnsignedInteger128(num) => num.to_string(),
+        AttributeValue::SignedInteger128(num) => num.to_string(),
+        AttributeValue::Float(num) => num.to_string(),
+        AttributeValue::FloatWithPrecision(num, precision) => format!("{:.*}", precision, num),
+        AttributeValue::Char(c) => c.to_string(),
+        AttributeValue::Translation(_) => return None,
+    };
+
+    Some(text)
+}
+
+fn is_named(node: &Node, name: &str) -> bool {
+    node.name()
+        .is_some_and(|node_name| node_name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod head {
+    use super::*;
+    use crate::parse::parse_html;
+    use crate::render::render;
+
+    fn head(html: &str) -> Head {
+        let mut head = Head::new();
+        head.push(parse_html(html).unwrap());
+        head
+    }
+
+    fn render_head(head: &Head) -> String {
+        head.iter().map(|node| render(node).unwrap()).collect()
+    }
+
+    #[test]
+    fn it_should_keep_the_last_title() {
+        let head = head("<title>Example</title><meta charset=utf-8><title>Post</title>");
+
+        assert_eq!(Some("Post".to_string()), head.title());
+        assert_eq!(
+            r#"<title>Post</title><meta charset="utf-8"/>"#,
+            render_head(&head)
+        );
+    }
+
+    #[test]
+    fn it_should_replace_meta_with_the_same_name_or_property() {
+        let head = head(concat!(
+            r#"<meta name="description" content="a">"#,
+            r#"<meta property="og:title" content="b">"#,
+            r#"<meta name="Description" content="c">"#,
+            r#"<meta property="og:image" content="d">"#,
+            r#"<meta property="og:title" content="e">"#,
+        ));
+
+        assert_eq!(
+            concat!(
+                r#"<meta name="Description" content="c"/>"#,
+                r#"<meta property="og:title" content="e"/>"#,
+                r#"<meta property="og:image" content="d"/>"#,
+            ),
+            render_head(&head)
+        );
+    }
+
+    #[test]
+    fn it_should_keep_one_canonical_link_and_no_repeated_links_or_scripts() {
+        let head = head(concat!(
+            r#"<link rel="canonical" href="/a">"#,
+            r#"<link rel="stylesheet" href="/app.css">"#,
+            r#"<script src="/app.js"></script>"#,
+            r#"<link rel="stylesheet" href="/app.css">"#,
+            r#"<link rel="canonical" href="/b">"#,
+            r#"<script src="/app.js"></script>"#,
+        ));
+
+        assert_eq!(
+            concat!(
+                r#"<link rel="canonical" href="/b"/>"#,
+                r#"<link rel="stylesheet" href="/app.css"/>"#,
+                r#"<script src="/app.js"></script>"#,
+            ),
+            render_head(&head)
+        );
+    }
+}
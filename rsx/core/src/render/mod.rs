@@ -1,41 +1,339 @@
+use crate::component::ComponentError;
+use crate::component::ErrorBoundary;
+use crate::dom::is_normalized;
+use crate::dom::normalize_nodes;
 use crate::dom::Attribute;
 use crate::dom::AttributeValue;
-use crate::dom::Child;
+use crate::dom::Attributes;
 use crate::dom::Node;
+use crate::i18n::Translator;
 use ::std::convert::AsRef;
 use ::std::convert::Into;
 use ::std::fmt::Write;
+use ::std::io;
+use ::std::mem;
+use ::std::panic;
+use ::std::panic::AssertUnwindSafe;
+use ::std::sync::Arc;
 
-type Result<T = ()> = ::std::result::Result<T, ::std::fmt::Error>;
+mod render_pool;
+pub use self::render_pool::*;
+
+mod render_mode;
+pub use self::render_mode::*;
+
+mod head;
+pub use self::head::*;
+
+mod render_error;
+pub use self::render_error::*;
+
+mod render_hook;
+pub use self::render_hook::*;
+
+mod text_mode;
+pub(crate) use self::text_mode::*;
+
+mod stream;
+pub(crate) use self::stream::*;
+
+type Result<T = ()> = ::std::result::Result<T, RenderError>;
+
+/// Written at the start of `render_xml`, unless the node starts with its own.
+pub const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
 
 pub fn render<N>(node: N) -> Result<String>
 where
     N: AsRef<Node>,
 {
-    let mut render = Render::new();
-    render.render(node.as_ref())?;
+    let node = node.as_ref();
+    let mut render = Render::with_capacity(node.size_hint());
+    render.render(node)?;
     Ok(render.into())
 }
 
+/// Renders the node with its `t!` text translated by the `Translator`.
+///
+/// The `<html>` element is given the `lang` and `dir` of the translator,
+/// unless it has its own.
+pub fn render_translated<N>(node: N, translator: Arc<dyn Translator>) -> Result<String>
+where
+    N: AsRef<Node>,
+{
+    let node = node.as_ref();
+    let mut render = Render::with_capacity(node.size_hint());
+    render.set_translator(translator);
+    render.render(node)?;
+    Ok(render.into())
+}
+
+/// Renders the node as an XML document, such as an RSS feed or a sitemap.
+///
+/// This starts with the `XML_DECLARATION`,
+/// unless the node starts with a `<?xml ...?>` of its own. See `RenderMode::Xml`.
+pub fn render_xml<N>(node: N) -> Result<String>
+where
+    N: AsRef<Node>,
+{
+    let node = node.as_ref();
+    let mut render = Render::with_capacity(XML_DECLARATION.len() + node.size_hint());
+    render.set_mode(RenderMode::Xml);
+
+    if !starts_with_xml_declaration(node) {
+        render.buffer.push_str(XML_DECLARATION);
+    }
+    render.render(node)?;
+
+    Ok(render.into())
+}
+
+fn starts_with_xml_declaration(node: &Node) -> bool {
+    match node.resolve() {
+        Node::ProcessingInstruction { target, .. } => target.eq_ignore_ascii_case("xml"),
+        Node::Fragment { children } => children
+            .iter()
+            .find(|child| !matches!(child, Node::Empty))
+            .map(starts_with_xml_declaration)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Render {
     buffer: String,
+    mode: RenderMode,
+    text_mode: TextMode,
+    head: Head,
+    /// Where the collected head elements are written, once rendering is done.
+    head_end: Option<usize>,
+    translator: Option<Arc<dyn Translator>>,
+    hooks: Vec<Arc<dyn RenderHook>>,
+    /// Where the output is written as it is rendered, by `render_to`.
+    stream: Option<Stream>,
 }
 
 impl Render {
     pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a `Render` with a buffer pre-sized to hold `capacity` bytes.
+    ///
+    /// `Node::size_hint` can be used to estimate this.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            buffer: String::new(),
+            buffer: String::with_capacity(capacity),
+            mode: RenderMode::Html,
+            text_mode: TextMode::Normal,
+            head: Head::new(),
+            head_end: None,
+            translator: None,
+            hooks: Vec::new(),
+            stream: None,
         }
     }
 
+    /// Empties what has been rendered so far,
+    /// whilst keeping the buffer allocated for the next render.
+    /// The mode goes back to HTML, and the translator and hooks are removed.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.head.clear();
+        self.translator = None;
+        self.hooks.clear();
+        self.set_mode(RenderMode::Html);
+    }
+
+    pub fn mode(&self) -> RenderMode {
+        self.mode
+    }
+
+    /// Changes how the nodes rendered from here on are written.
+    pub fn set_mode(&mut self, mode: RenderMode) {
+        self.mode = mode;
+        self.text_mode = match mode {
+            RenderMode::Html => TextMode::Normal,
+            RenderMode::Xml => TextMode::Xml,
+        };
+    }
+
+    pub fn translator(&self) -> Option<&dyn Translator> {
+        self.translator.as_deref()
+    }
+
+    /// Sets what `t!` text is translated with.
+    /// Without one, the key of the translation is written.
+    pub fn set_translator(&mut self, translator: Arc<dyn Translator>) {
+        self.translator = Some(translator);
+    }
+
+    pub fn hooks(&self) -> &[Arc<dyn RenderHook>] {
+        &self.hooks
+    }
+
+    /// Adds a hook, which is called for every element rendered from here on.
+    /// Hooks are called in the order they are added. See `RenderHook`.
+    pub fn add_hook(&mut self, hook: Arc<dyn RenderHook>) {
+        self.hooks.push(hook);
+    }
+
+    /// Grows the buffer, so at least `additional` more bytes can be rendered without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns everything rendered so far.
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// The `<head>` elements collected by the last render. See `Head`.
+    pub fn head(&self) -> &Head {
+        &self.head
+    }
+
     pub fn render(&mut self, node: &Node) -> Result {
-        self.render_node(node)
+        self.head.clear();
+        self.head_end = None;
+
+        self.render_node(node)?;
+        self.render_head_elements()
+    }
+
+    /// Renders the node into the writer, a chunk at a time as it is rendered,
+    /// rather than all of it at the end. What is written is taken out of the buffer.
+    ///
+    /// Output which may still be taken back is kept until it can't be.
+    /// Such as what is within an `ErrorBoundary`, or an XML element which is self-closed if it's empty.
+    ///
+    /// The `Head` is written when the `<head>` is reached.
+    /// So when there is head content from outside of the `<head>`, or an `ErrorBoundary` whose fallback may add some,
+    /// the node is first rendered without writing anything, to collect it.
+    /// Then the hooks are called twice for each element.
+    ///
+    /// If this fails, what was written before it failed is left as it is.
+    pub fn render_to<W>(&mut self, node: &Node, writer: W) -> Result
+    where
+        W: io::Write + Send + 'static,
+    {
+        let is_head_collected = has_head_content_outside_head(node);
+        if is_head_collected {
+            let buffer = mem::take(&mut self.buffer);
+            let result = self.render_stream(node, Stream::new(io::sink()));
+            self.buffer = buffer;
+            result?;
+        }
+
+        let mut stream = Stream::new(writer);
+        stream.is_head_collected = is_head_collected;
+        self.render_stream(node, stream)
+    }
+
+    fn render_stream(&mut self, node: &Node, stream: Stream) -> Result {
+        if !stream.is_head_collected {
+            self.head.clear();
+        }
+        self.head_end = None;
+        self.stream = Some(stream);
+
+        let result = self.render_node(node).and_then(|()| self.end_stream());
+        self.stream = None;
+        result
+    }
+
+    /// Writes the rest of the output.
+    fn end_stream(&mut self) -> Result {
+        if let Some(stream) = &mut self.stream {
+            stream.write(&mut self.buffer)?;
+            stream.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes what can no longer be taken back, once there is a chunk of it.
+    fn write_chunk(&mut self) -> Result {
+        match &mut self.stream {
+            Some(stream) if self.buffer.len() >= STREAM_CHUNK_SIZE => {
+                Ok(stream.write(&mut self.buffer)?)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Where the output is up to, including what has been written by `render_to`.
+    fn position(&self) -> usize {
+        let written = self.stream.as_ref().map_or(0, Stream::written);
+        written + self.buffer.len()
+    }
+
+    /// Takes back the output after the position, which must have been held if streaming.
+    fn truncate(&mut self, position: usize) {
+        let written = self.stream.as_ref().map_or(0, Stream::written);
+        self.buffer.truncate(position - written);
+    }
+
+    /// Keeps the output from `from` in the buffer, until it is released,
+    /// or until the output goes past `until_past`.
+    /// Returns what to release it with.
+    fn hold(&mut self, from: usize, until_past: Option<usize>) -> Option<usize> {
+        self.stream
+            .as_mut()
+            .map(|stream| stream.hold(from, until_past))
+    }
+
+    fn release(&mut self, maybe_holds_len: Option<usize>) {
+        if let (Some(stream), Some(holds_len)) = (&mut self.stream, maybe_holds_len) {
+            stream.release(holds_len);
+        }
+    }
+
+    fn is_head_collected(&self) -> bool {
+        self.stream
+            .as_ref()
+            .is_some_and(|stream| stream.is_head_collected)
+    }
+
+    /// Writes the collected head elements into the `<head>`,
+    /// now that the rest of the document has been rendered.
+    /// If no `<head>` was rendered, they are not written.
+    fn render_head_elements(&mut self) -> Result {
+        let head_end = match self.head_end.take() {
+            Some(head_end) => head_end,
+            None => return Ok(()),
+        };
+
+        let rest = self.buffer.split_off(head_end);
+        self.render_head_nodes()?;
+        self.buffer.push_str(&rest);
+
+        Ok(())
+    }
+
+    fn render_head_nodes(&mut self) -> Result {
+        let head = mem::take(&mut self.head);
+        let result = head.iter().try_for_each(|node| self.render_node(node));
+        self.head = head;
+        result
     }
 
     fn render_node(&mut self, node: &Node) -> Result {
         match node {
-            Node::Empty => {}
+            // Slots are filled by the layout, and anything left over isn't written.
+            // Except for the head, which is collected to be written into the `<head>`.
+            Node::Empty | Node::Slot { .. } => {}
+            Node::SlotContent { name, children } => {
+                if name == "head" && !self.is_head_collected() {
+                    for child in children {
+                        self.head.push(child.clone());
+                    }
+                }
+            }
             Node::Doctype { name, attributes } => {
                 write!(self.buffer, "<!{}", name)?;
                 self.render_doctype_attributes(attributes)?;
@@ -44,48 +342,229 @@ impl Render {
             Node::Comment { children } => match children {
                 Some(children) => {
                     write!(self.buffer, "<!-- ")?;
+                    let parent_text_mode = self.text_mode;
+                    self.text_mode = TextMode::Comment;
                     self.render_nodes(children)?;
+                    self.text_mode = parent_text_mode;
                     write!(self.buffer, " -->")?;
                 }
                 None => {
                     write!(self.buffer, "<!-- -->")?;
                 }
             },
+            Node::CData { children } => {
+                write!(self.buffer, "<![CDATA[")?;
+                if let Some(children) = children {
+                    let parent_text_mode = self.text_mode;
+                    self.text_mode = TextMode::CData;
+                    self.render_nodes(children)?;
+                    self.text_mode = parent_text_mode;
+                }
+                write!(self.buffer, "]]>")?;
+            }
+            Node::ProcessingInstruction { target, attributes } => {
+                write!(self.buffer, "<?{}", target)?;
+                self.render_maybe_attributes(attributes)?;
+                write!(self.buffer, "?>")?;
+            }
             Node::Fragment { children } => {
                 self.render_nodes(children)?;
             }
             Node::SelfClosing { name, attributes } => {
-                write!(self.buffer, "<{}", name)?;
-                self.render_maybe_attributes(attributes)?;
-                write!(self.buffer, "/>")?;
+                self.render_element(name, attributes, Element::SelfClosing)?;
             }
             Node::OpenEmpty { name, attributes } => {
-                write!(self.buffer, "<{}", name)?;
-                self.render_maybe_attributes(attributes)?;
-                write!(self.buffer, ">")?;
-                write!(self.buffer, "</{}>", name)?;
+                self.render_element(name, attributes, Element::Open(&[]))?;
             }
             Node::OpenWithChildren {
                 name,
                 attributes,
-                child,
+                children,
             } => {
+                self.render_element(name, attributes, Element::Open(children))?;
+            }
+            Node::Text { contents } => self.text_mode.write_text(&mut self.buffer, contents),
+            Node::Translation { translation } => {
+                let text = translation.text(self.translator.as_deref());
+                self.text_mode.write_text(&mut self.buffer, &text);
+            }
+            // The pre-rendered HTML is not always valid XML, and hooks need to see its elements,
+            // so then it's written from the tree it was rendered from.
+            Node::Static { html, .. } => match self.mode {
+                RenderMode::Html if self.hooks.is_empty() => self.buffer.push_str(html),
+                _ => self.render_node(node.resolve())?,
+            },
+            Node::Error { error } => return Err(RenderError::Component(error.clone())),
+            Node::ErrorBoundary { boundary } => self.render_error_boundary(boundary)?,
+        }
+
+        Ok(())
+    }
+
+    /// Calls the hooks on the element, if there are any, and then writes it.
+    fn render_element(
+        &mut self,
+        name: &str,
+        maybe_attributes: &Option<Attributes>,
+        element: Element<'_>,
+    ) -> Result {
+        let attributes = maybe_attributes.as_deref().unwrap_or(&[]);
+        if self.hooks.is_empty() {
+            return self.write_element(name, attributes, element);
+        }
+
+        let mut start = ElementStart::new(name, attributes);
+        for hook in &self.hooks {
+            hook.on_element(&mut start);
+            if start.is_skipped() {
+                return Ok(());
+            }
+        }
+
+        self.write_element(name, start.attributes(), element)
+    }
+
+    fn write_element(
+        &mut self,
+        name: &str,
+        attributes: &[Attribute],
+        element: Element<'_>,
+    ) -> Result {
+        let children = match element {
+            Element::SelfClosing => {
                 write!(self.buffer, "<{}", name)?;
-                self.render_maybe_attributes(attributes)?;
-                write!(self.buffer, ">")?;
-                self.render_child(child)?;
-                write!(self.buffer, "</{}>", name)?;
+                self.render_attributes(attributes)?;
+                write!(self.buffer, "/>")?;
+                return Ok(());
+            }
+            Element::Open(children) if self.is_document_head(name) => {
+                return self.render_head(name, attributes, children);
             }
-            Node::Text { contents } => write!(self.buffer, "{}", contents)?,
+            Element::Open(children) => children,
+        };
+
+        write!(self.buffer, "<{}", name)?;
+        self.render_element_attributes(name, attributes)?;
+        if children.is_empty() {
+            match self.mode {
+                RenderMode::Html => write!(self.buffer, "></{}>", name)?,
+                RenderMode::Xml => write!(self.buffer, "/>")?,
+            }
+            return Ok(());
+        }
+
+        write!(self.buffer, ">")?;
+        let children_start = self.position();
+        let holds_len = match self.mode {
+            RenderMode::Html => None,
+            RenderMode::Xml => self.hold(children_start - ">".len(), Some(children_start)),
+        };
+
+        let parent_text_mode = self.text_mode;
+        self.text_mode = match self.mode {
+            RenderMode::Html => TextMode::for_element(name),
+            RenderMode::Xml => TextMode::for_xml_element(name),
+        };
+        if self.text_mode == TextMode::CData {
+            self.render_cdata_children(children)?;
+        } else {
+            self.render_nodes(children)?;
+        }
+        self.text_mode = parent_text_mode;
+        self.release(holds_len);
+
+        if self.mode == RenderMode::Xml && self.position() == children_start {
+            self.truncate(children_start - ">".len());
+            write!(self.buffer, "/>")?;
+        } else {
+            write!(self.buffer, "</{}>", name)?;
         }
 
         Ok(())
     }
 
-    fn render_doctype_attributes(&mut self, maybe_attributes: &Option<Vec<Attribute>>) -> Result {
+    /// Renders the children of the boundary, or its fallback if they fail.
+    ///
+    /// Anything written by the children is taken back before the fallback is rendered,
+    /// including what they added to the `Head`.
+    fn render_error_boundary(&mut self, boundary: &ErrorBoundary) -> Result {
+        let position = self.position();
+        let holds_len = self.hold(position, None);
+        let head = self.head.clone();
+        let head_end = self.head_end;
+        let text_mode = self.text_mode;
+
+        let result = if boundary.catch_panics() {
+            panic::catch_unwind(AssertUnwindSafe(|| self.render_nodes(boundary.children())))
+                .unwrap_or_else(|payload| Err(ComponentError::from_panic(payload).into()))
+        } else {
+            self.render_nodes(boundary.children())
+        };
+        self.release(holds_len);
+
+        let error = match result {
+            Err(RenderError::Component(error)) => error,
+            result => return result,
+        };
+
+        ::log::error!("Rendering the fallback of an ErrorBoundary, {}", error);
+        self.truncate(position);
+        self.head = head;
+        self.head_end = head_end;
+        self.text_mode = text_mode;
+
+        self.render_node(&boundary.fallback(&error))
+    }
+
+    /// The first `<head>` is where the `Head` is written.
+    fn is_document_head(&self, name: &str) -> bool {
+        self.head_end.is_none() && name.eq_ignore_ascii_case("head")
+    }
+
+    /// The children are collected into the `Head`, and written at the end of the render.
+    /// When streaming, the head is written here instead.
+    fn render_head(&mut self, name: &str, attributes: &[Attribute], children: &[Node]) -> Result {
+        write!(self.buffer, "<{}", name)?;
+        self.render_attributes(attributes)?;
+        write!(self.buffer, ">")?;
+
+        if !self.is_head_collected() {
+            for child in children {
+                self.head.push(child.clone());
+            }
+        }
+
+        self.head_end = Some(self.buffer.len());
+        if self.stream.is_some() {
+            self.render_head_nodes()?;
+        }
+        write!(self.buffer, "</{}>", name)?;
+        Ok(())
+    }
+
+    /// Renders the children within a `<![CDATA[ ]]>`, unless they are empty.
+    fn render_cdata_children(&mut self, children: &[Node]) -> Result {
+        let start = self.position();
+        write!(self.buffer, "<![CDATA[")?;
+        let contents_start = self.position();
+        let holds_len = self.hold(start, Some(contents_start));
+
+        self.render_nodes(children)?;
+        self.release(holds_len);
+
+        if self.position() == contents_start {
+            self.truncate(start);
+        } else {
+            write!(self.buffer, "]]>")?;
+        }
+
+        Ok(())
+    }
+
+    fn render_doctype_attributes(&mut self, maybe_attributes: &Option<Attributes>) -> Result {
         match maybe_attributes {
             Some(attributes) => {
-                for attribute in attributes {
+                for attribute in attributes.iter() {
                     self.render_doctype_attribute(attribute)?;
                 }
             }
@@ -96,25 +575,60 @@ impl Render {
     }
 
     fn render_doctype_attribute(&mut self, attribute: &Attribute) -> Result {
-        write!(self.buffer, " {}", attribute.key)
+        write!(self.buffer, " {}", attribute.key)?;
+        Ok(())
+    }
+
+    /// The `<html>` element is given the `lang` and `dir` of the translator.
+    fn render_element_attributes(&mut self, name: &str, attributes: &[Attribute]) -> Result {
+        self.render_attributes(attributes)?;
+
+        let translator = match &self.translator {
+            Some(translator) if name.eq_ignore_ascii_case("html") => translator,
+            _ => return Ok(()),
+        };
+        let has_attribute = |key: &str| {
+            attributes
+                .iter()
+                .any(|attribute| attribute.key.eq_ignore_ascii_case(key))
+        };
+
+        if !has_attribute("lang") {
+            write!(self.buffer, " lang=\"")?;
+            write_escaped_attribute(&mut self.buffer, translator.lang());
+            write!(self.buffer, "\"")?;
+        }
+        if !has_attribute("dir") {
+            write!(self.buffer, " dir=\"{}\"", translator.dir().as_str())?;
+        }
+
+        Ok(())
     }
 
-    fn render_maybe_attributes(&mut self, maybe_attributes: &Option<Vec<Attribute>>) -> Result {
+    fn render_maybe_attributes(&mut self, maybe_attributes: &Option<Attributes>) -> Result {
         match maybe_attributes {
             Some(attributes) => self.render_attributes(attributes),
             None => Ok(()),
         }
     }
 
-    fn render_attributes(&mut self, attributes: &Vec<Attribute>) -> Result {
+    fn render_attributes(&mut self, attributes: &[Attribute]) -> Result {
         for attribute in attributes {
-            match attribute.value {
+            match &attribute.value {
                 AttributeValue::ImplicitFalse => { /* Skip */ }
-                AttributeValue::ImplicitTrue => {
-                    write!(self.buffer, " {}", attribute.key)?;
-                }
+                AttributeValue::ImplicitTrue => match self.mode {
+                    RenderMode::Html => write!(self.buffer, " {}", attribute.key)?,
+                    // XML has no empty attributes, so they are written as in XHTML.
+                    RenderMode::Xml => {
+                        write!(self.buffer, " {}=\"{}\"", attribute.key, attribute.key)?
+                    }
+                },
                 AttributeValue::Text(text) => {
-                    write!(self.buffer, " {}=\"{}\"", attribute.key, text)?;
+                    self.render_text_attribute(&attribute.key, text)?;
+                }
+                AttributeValue::Translation(translation) => {
+                    let text = translation.text(self.translator.as_deref());
+                    self.render_text_attribute(&attribute.key, &text)?;
                 }
                 AttributeValue::UnsignedInteger(num) => {
                     write!(self.buffer, " {}=\"{}\"", attribute.key, num)?;
@@ -122,51 +636,431 @@ impl Render {
                 AttributeValue::SignedInteger(num) => {
                     write!(self.buffer, " {}=\"{}\"", attribute.key, num)?;
                 }
+                AttributeValue::UnsignedInteger128(num) => {
+                    write!(self.buffer, " {}=\"{}\"", attribute.key, num)?;
+                }
+                AttributeValue::SignedInteger128(num) => {
+                    write!(self.buffer, " {}=\"{}\"", attribute.key, num)?;
+                }
                 AttributeValue::Float(num) => {
                     write!(self.buffer, " {}=\"{}\"", attribute.key, num)?;
                 }
+                AttributeValue::FloatWithPrecision(num, precision) => {
+                    write!(self.buffer, " {}=\"{:.*}\"", attribute.key, precision, num)?;
+                }
+                AttributeValue::Char(c) => {
+                    self.render_text_attribute(&attribute.key, c.encode_utf8(&mut [0; 4]))?;
+                }
             }
         }
 
         Ok(())
     }
 
-    fn render_maybe_nodes(&mut self, maybe_nodes: &Option<Vec<Node>>) -> Result {
-        match maybe_nodes {
-            Some(nodes) => self.render_nodes(nodes),
-            None => Ok(()),
+    fn render_text_attribute(&mut self, key: &str, text: &str) -> Result {
+        write!(self.buffer, " {}=\"", key)?;
+        match self.mode {
+            RenderMode::Html => write_escaped_attribute(&mut self.buffer, text),
+            RenderMode::Xml => write_escaped_xml_attribute(&mut self.buffer, text),
         }
+        write!(self.buffer, "\"")?;
+        Ok(())
     }
 
-    fn render_nodes(&mut self, nodes: &Vec<Node>) -> Result {
+    /// Children built by `Node::new_open_with_children` are already normalised.
+    /// Raw text which isn't, i.e. children pushed onto `children_mut`, is normalised first,
+    /// so that text split across nodes is escaped as a whole.
+    fn render_nodes(&mut self, nodes: &[Node]) -> Result {
+        if self.text_mode.is_raw_text() && !is_normalized(nodes) {
+            let nodes = normalize_nodes(nodes.to_vec());
+            return self.render_nodes(&nodes);
+        }
+
         for node in nodes {
             self.render_node(node)?;
+            self.write_chunk()?;
         }
 
         Ok(())
     }
+}
 
-    fn render_maybe_child(&mut self, maybe_child: &Option<Child>) -> Result {
-        match maybe_child {
-            Some(child) => self.render_child(child),
-            None => Ok(()),
+/// If the head needs collecting before a streamed render can write it.
+///
+/// Static HTML is left out, as it can't hold slot content or error boundaries.
+fn has_head_content_outside_head(node: &Node) -> bool {
+    match node {
+        Node::SlotContent { name, .. } => name == "head",
+        Node::ErrorBoundary { .. } => true,
+        Node::OpenWithChildren { name, .. } if name.eq_ignore_ascii_case("head") => false,
+        Node::OpenWithChildren { children, .. } | Node::Fragment { children } => {
+            children.iter().any(has_head_content_outside_head)
         }
-    }
-
-    fn render_child(&mut self, child: &Child) -> Result {
-        match child {
-            Child::None => Ok(()),
-            Child::Nodes { nodes } => self.render_nodes(nodes),
-            Child::Text { contents } => {
-                write!(self.buffer, "{}", contents)?;
-                Ok(())
-            }
+        Node::Comment {
+            children: Some(children),
         }
+        | Node::CData {
+            children: Some(children),
+        } => children.iter().any(has_head_content_outside_head),
+        _ => false,
     }
 }
 
+/// What follows the start tag of an element.
+#[derive(Clone, Copy)]
+enum Element<'a> {
+    SelfClosing,
+    Open(&'a [Node]),
+}
+
 impl Into<String> for Render {
     fn into(self) -> String {
         self.buffer
     }
 }
+
+#[cfg(test)]
+mod render_xml {
+    use super::*;
+    use crate::parse::parse_html;
+
+    fn xml(html: &str) -> String {
+        render_xml(parse_html(html).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn it_should_add_the_xml_declaration() {
+        assert_eq!(
+            format!("{}<urlset/>", XML_DECLARATION),
+            xml("<urlset></urlset>")
+        );
+    }
+
+    #[test]
+    fn it_should_keep_a_given_xml_declaration() {
+        assert_eq!(
+            r#"<?xml version="1.1"?><urlset/>"#,
+            xml(r#"<?xml version="1.1"?><urlset></urlset>"#)
+        );
+    }
+
+    #[test]
+    fn it_should_self_close_every_empty_element() {
+        assert_eq!(
+            format!("{}<feed><link/><entry/><br/></feed>", XML_DECLARATION),
+            xml("<feed><link><entry></entry><br></feed>")
+        );
+    }
+
+    #[test]
+    fn it_should_give_attributes_values() {
+        assert_eq!(
+            format!(
+                r#"{}<input disabled="disabled" title="a&#10;b &amp; c"/>"#,
+                XML_DECLARATION
+            ),
+            xml("<input disabled title=\"a\nb &amp; c\">")
+        );
+    }
+
+    #[test]
+    fn it_should_write_scripts_as_cdata() {
+        assert_eq!(
+            format!(
+                "{}<html><script><![CDATA[if (a < b && c) {{}}]]></script><p>a &lt; b</p></html>",
+                XML_DECLARATION
+            ),
+            xml("<html><script>if (a < b && c) {}</script><p>a &lt; b</p></html>")
+        );
+    }
+
+    #[test]
+    fn it_should_render_static_html_as_xml() {
+        assert_eq!(
+            format!(
+                "{}<div><input checked=\"checked\"/><p/></div>",
+                XML_DECLARATION
+            ),
+            render_xml(new_static!("<div><input checked><p></p></div>")).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_go_back_to_html_when_cleared() {
+        let mut render = Render::new();
+        render.set_mode(RenderMode::Xml);
+        render.clear();
+        render.render(&parse_html("<p></p>").unwrap()).unwrap();
+
+        assert_eq!(RenderMode::Html, render.mode());
+        assert_eq!("<p></p>", render.as_str());
+    }
+}
+
+#[cfg(test)]
+mod render_head {
+    use super::*;
+    use crate::parse::parse_html;
+
+    fn page(body: Node) -> Node {
+        let head = parse_html("<head><meta charset=utf-8><title>Example</title></head>").unwrap();
+        let body = Node::new_open("body", None, Some(vec![body]));
+
+        Node::new_open("html", None, Some(vec![head, body]))
+    }
+
+    #[test]
+    fn it_should_write_head_content_from_the_body_into_the_head() {
+        let body = Node::new_open(
+            "main",
+            None,
+            Some(vec![Node::new_slot_content(
+                "head",
+                vec![
+                    Node::new_open("title", None, Some(vec![Node::new_text("Post")])),
+                    parse_html(r#"<link rel="canonical" href="/post">"#).unwrap(),
+                ],
+            )]),
+        );
+
+        let mut render = Render::new();
+        render.render(&page(body)).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"<html><head><meta charset="utf-8"/><title>Post</title>"#,
+                r#"<link rel="canonical" href="/post"/></head><body><main></main></body></html>"#,
+            ),
+            render.as_str()
+        );
+        assert_eq!(Some("Post".to_string()), render.head().title());
+    }
+
+    #[test]
+    fn it_should_not_write_head_content_without_a_head() {
+        let node = Node::new_slot_content("head", vec![Node::new_text("a")]);
+
+        assert_eq!("", render(node).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod render_to {
+    use super::*;
+    use ::std::sync::Mutex;
+
+    /// Keeps each write as a chunk.
+    #[derive(Clone, Default)]
+    struct Chunks(Arc<Mutex<Vec<String>>>);
+
+    impl Chunks {
+        fn take(&self) -> Vec<String> {
+            mem::take(&mut self.0.lock().unwrap())
+        }
+    }
+
+    impl io::Write for Chunks {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            let chunk = String::from_utf8(bytes.to_vec()).unwrap();
+            self.0.lock().unwrap().push(chunk);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn items() -> Node {
+        let items = (0..2000).map(|i| Node::new_open("li", None, Some(i.to_string())));
+        Node::new_open("ul", None, Some(items.collect::<Vec<Node>>()))
+    }
+
+    fn stream(render: &mut Render, node: &Node) -> Vec<String> {
+        let chunks = Chunks::default();
+        render.render_to(node, chunks.clone()).unwrap();
+        chunks.take()
+    }
+
+    #[test]
+    fn it_should_write_a_chunk_at_a_time() {
+        let node = items();
+        let mut render = Render::new();
+        let chunks = stream(&mut render, &node);
+
+        assert!(chunks.len() > 1);
+        assert!(chunks[0].len() >= STREAM_CHUNK_SIZE);
+        assert_eq!(crate::render::render(&node).unwrap(), chunks.concat());
+        assert_eq!("", render.as_str());
+    }
+
+    #[test]
+    fn it_should_write_head_content_from_the_body_into_the_head() {
+        let head = Node::new_open(
+            "head",
+            None,
+            Some(Node::new_open("title", None, Some("Home"))),
+        );
+        let body = Node::new_open(
+            "body",
+            None,
+            Some(vec![
+                items(),
+                Node::new_slot_content("head", vec![Node::new_open("title", None, Some("Items"))]),
+            ]),
+        );
+        let node = Node::new_open("html", None, Some(vec![head, body]));
+
+        let mut render = Render::new();
+        let chunks = stream(&mut render, &node);
+
+        assert!(chunks[0].starts_with("<html><head><title>Items</title></head><body><ul>"));
+        assert_eq!(crate::render::render(&node).unwrap(), chunks.concat());
+        assert_eq!(Some("Items".to_string()), render.head().title());
+    }
+
+    #[test]
+    fn it_should_not_write_what_an_error_boundary_takes_back() {
+        let boundary = Node::new_error_boundary(
+            |_: &ComponentError| Node::new_open("p", None, Some("Sorry")),
+            Node::new_fragment(vec![
+                items(),
+                Node::new_error(ComponentError::new("user not found")),
+            ]),
+        );
+        let node = Node::new_open("div", None, Some(boundary));
+
+        let chunks = stream(&mut Render::new(), &node);
+
+        assert_eq!("<div><p>Sorry</p></div>", chunks.concat());
+    }
+
+    #[test]
+    fn it_should_self_close_xml_elements_left_empty_by_an_error_boundary() {
+        let boundary = || {
+            Node::new_error_boundary(
+                |_: &ComponentError| Node::Empty,
+                Node::new_fragment(vec![
+                    items(),
+                    Node::new_error(ComponentError::new("feed not found")),
+                ]),
+            )
+        };
+        let node = Node::new_open(
+            "feed",
+            None,
+            Some(vec![
+                Node::new_open("summary", None, Some(boundary())),
+                Node::new_open("script", None, Some(boundary())),
+            ]),
+        );
+
+        let mut render = Render::new();
+        render.set_mode(RenderMode::Xml);
+        let chunks = stream(&mut render, &node);
+
+        assert_eq!("<feed><summary/><script/></feed>", chunks.concat());
+    }
+}
+
+#[cfg(test)]
+mod render_translated {
+    use super::*;
+    use crate::dom::ToAttributeValue;
+    use crate::dom::ToNode;
+    use crate::i18n::Catalog;
+    use crate::t;
+
+    fn catalog(lang: &str) -> Arc<dyn Translator> {
+        Arc::new(
+            Catalog::new(lang)
+                .with_message("greeting", "Hello { $name } & welcome")
+                .with_message("search", "Search"),
+        )
+    }
+
+    fn page() -> Node {
+        let input = Node::new_self_closing(
+            "input",
+            Some(Attributes::Owned(vec![Attribute::new(
+                "placeholder",
+                t!("search").to_attribute_value(),
+            )])),
+        );
+        let body = Node::new_open(
+            "body",
+            None,
+            Some(vec![t!("greeting", name = "<Anna>").to_node(), input]),
+        );
+
+        Node::new_open("html", None, Some(vec![body]))
+    }
+
+    #[test]
+    fn it_should_translate_text_and_attributes() {
+        assert_eq!(
+            concat!(
+                r#"<html lang="en" dir="ltr"><body>Hello &lt;Anna&gt; &amp; welcome"#,
+                r#"<input placeholder="Search"/></body></html>"#,
+            ),
+            render_translated(page(), catalog("en")).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_write_the_key_without_a_translation() {
+        assert_eq!(
+            r#"<html><body>greeting<input placeholder="search"/></body></html>"#,
+            render(page()).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_set_the_direction_and_keep_a_given_lang() {
+        let html = Node::new_open(
+            "html",
+            Some(Attributes::Owned(vec![Attribute::new(
+                "lang",
+                AttributeValue::Text("ar-EG".into()),
+            )])),
+            Option::<Vec<Node>>::None,
+        );
+
+        assert_eq!(
+            r#"<html lang="ar-EG" dir="rtl"></html>"#,
+            render_translated(html, catalog("ar")).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_remove_the_translator_when_cleared() {
+        let mut render = Render::new();
+        render.set_translator(catalog("en"));
+        render.clear();
+        render.render(&t!("search").to_node()).unwrap();
+
+        assert!(render.translator().is_none());
+        assert_eq!("search", render.as_str());
+    }
+}
+
+#[cfg(test)]
+mod render_raw_text {
+    use super::*;
+
+    #[test]
+    fn it_should_escape_text_split_across_nodes_as_a_whole() {
+        let mut script = Node::new_open_empty("script", None);
+        let children = script.children_mut().unwrap();
+        children.push(Node::new_text("a = '</scr"));
+        children.push(Node::new_fragment(vec![
+            Node::new_text("ipt><b>"),
+            Node::new_text("';"),
+        ]));
+
+        assert_eq!(
+            r"<script>a = '<\/script><b>';</script>",
+            render(&script).unwrap()
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::component::ComponentError;
+use ::std::error::Error;
+use ::std::fmt;
+use ::std::io;
+use ::std::sync::Arc;
+
+/// Why rendering failed.
+#[derive(Clone, Debug)]
+pub enum RenderError {
+    /// Writing the output failed.
+    Fmt(fmt::Error),
+
+    /// Writing to the writer given to `Render::render_to` failed.
+    Io(Arc<io::Error>),
+
+    /// A component returned an error, which no `ErrorBoundary` caught.
+    Component(ComponentError),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fmt(error) => write!(f, "failed writing the output, {}", error),
+            Self::Io(error) => write!(f, "failed writing the output, {}", error),
+            Self::Component(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for RenderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Fmt(error) => Some(error),
+            Self::Io(error) => Some(&**error),
+            Self::Component(error) => Some(error),
+        }
+    }
+}
+
+impl From<fmt::Error> for RenderError {
+    fn from(error: fmt::Error) -> Self {
+        Self::Fmt(error)
+    }
+}
+
+impl From<io::Error> for RenderError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(Arc::new(error))
+    }
+}
+
+impl From<ComponentError> for RenderError {
+    fn from(error: ComponentError) -> Self {
+        Self::Component(error)
+    }
+}
+
+/// So code returning a `fmt::Result`, such as a `Display` implementation, can still use `?` on a render.
+impl From<RenderError> for fmt::Error {
+    fn from(_: RenderError) -> Self {
+        fmt::Error
+    }
+}
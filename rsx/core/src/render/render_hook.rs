@@ -0,0 +1,231 @@
+use crate::dom::attribute_index;
+use crate::dom::find_attribute;
+use crate::dom::insert_attribute;
+use crate::dom::Attribute;
+use crate::dom::AttributeValue;
+use crate::dom::ToAttributeValue;
+use ::std::borrow::Cow;
+use ::std::fmt::Debug;
+use ::std::sync::Arc;
+
+/// Called by `Render` for every element, just before its start tag is written.
+///
+/// ```
+/// use ::core::dom::Node;
+/// use ::core::render::ElementStart;
+/// use ::core::render::Render;
+/// use ::core::render::RenderHook;
+/// use ::std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct LazyImages;
+///
+/// impl RenderHook for LazyImages {
+///     fn on_element(&self, element: &mut ElementStart<'_>) {
+///         if element.name() == "img" && !element.has_attribute("loading") {
+///             element.set_attribute("loading", "lazy");
+///         }
+///     }
+/// }
+///
+/// let mut render = Render::new();
+/// render.add_hook(Arc::new(LazyImages));
+/// render.render(&Node::new_self_closing("img", None)).unwrap();
+///
+/// assert_eq!(render.as_str(), r#"<img loading="lazy"/>"#);
+/// ```
+///
+/// Hooks are called in the order they were added, each seeing the changes of those before it.
+/// Without any hooks, elements are written without this step,
+/// and pre-rendered HTML is written as it is, rather than from its tree.
+pub trait RenderHook: Debug + Send + Sync {
+    fn on_element(&self, element: &mut ElementStart<'_>);
+}
+
+/// Hooks can be grouped, and added to a `Render` as one.
+impl RenderHook for Vec<Arc<dyn RenderHook>> {
+    fn on_element(&self, element: &mut ElementStart<'_>) {
+        for hook in self {
+            if element.is_skipped() {
+                return;
+            }
+            hook.on_element(element);
+        }
+    }
+}
+
+/// An element which is about to be rendered.
+///
+/// The attributes are only copied if a hook changes them.
+#[derive(Clone, Debug)]
+pub struct ElementStart<'a> {
+    name: &'a str,
+    attributes: Cow<'a, [Attribute]>,
+    is_skipped: bool,
+}
+
+impl<'a> ElementStart<'a> {
+    pub(crate) fn new(name: &'a str, attributes: &'a [Attribute]) -> Self {
+        Self {
+            name,
+            attributes: Cow::Borrowed(attributes),
+            is_skipped: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// See `Node::attribute`.
+    pub fn attribute(&self, key: &str) -> Option<&AttributeValue> {
+        find_attribute(&self.attributes, key)
+    }
+
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.attribute(key).is_some()
+    }
+
+    /// See `Node::set_attribute`.
+    pub fn set_attribute<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<Cow<'static, str>>,
+        V: ToAttributeValue,
+    {
+        insert_attribute(
+            self.attributes.to_mut(),
+            key.into(),
+            value.to_attribute_value(),
+        );
+    }
+
+    /// See `Node::remove_attribute`.
+    /// The attributes are only copied if one is removed.
+    pub fn remove_attribute(&mut self, key: &str) -> Option<AttributeValue> {
+        let index = attribute_index(&self.attributes, key)?;
+
+        Some(self.attributes.to_mut().remove(index).value)
+    }
+
+    /// The element, and everything within it, is not rendered.
+    /// Hooks after this one are not called.
+    pub fn skip(&mut self) {
+        self.is_skipped = true;
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.is_skipped
+    }
+}
+
+#[cfg(test)]
+mod render_hook {
+    use super::*;
+    use crate::dom::Attributes;
+    use crate::dom::Node;
+    use crate::render::Render;
+
+    /// Adds a nonce to scripts, for a Content Security Policy.
+    #[derive(Debug)]
+    struct ScriptNonce(&'static str);
+
+    impl RenderHook for ScriptNonce {
+        fn on_element(&self, element: &mut ElementStart<'_>) {
+            if element.name() == "script" {
+                element.set_attribute("nonce", self.0);
+            }
+        }
+    }
+
+    /// Serves assets from a CDN.
+    #[derive(Debug)]
+    struct Cdn;
+
+    impl RenderHook for Cdn {
+        fn on_element(&self, element: &mut ElementStart<'_>) {
+            let src = match element.attribute("src") {
+                Some(AttributeValue::Text(src)) if src.starts_with("/assets/") => {
+                    format!("https://cdn.example.com{}", src)
+                }
+                _ => return,
+            };
+            element.set_attribute("src", src);
+        }
+    }
+
+    #[derive(Debug)]
+    struct SkipTracking;
+
+    impl RenderHook for SkipTracking {
+        fn on_element(&self, element: &mut ElementStart<'_>) {
+            if element.remove_attribute("data-tracking").is_some() {
+                element.skip();
+            }
+        }
+    }
+
+    fn attributes(attributes: &[(&'static str, &'static str)]) -> Option<Attributes> {
+        let attributes = attributes
+            .iter()
+            .map(|(key, value)| Attribute::new(key, AttributeValue::Text((*value).into())))
+            .collect::<Vec<_>>();
+
+        Some(attributes.into())
+    }
+
+    fn page() -> Node {
+        Node::new_open(
+            "body",
+            None,
+            Some(vec![
+                Node::new_open_empty("script", attributes(&[("src", "/assets/app.js")])),
+                Node::new_self_closing("img", attributes(&[("src", "/logo.png")])),
+                Node::new_open("div", attributes(&[("data-tracking", "1")]), Some("ad")),
+                new_static!(r#"<p><script src="/assets/static.js"></script></p>"#),
+            ]),
+        )
+    }
+
+    fn render_with(hooks: Vec<Arc<dyn RenderHook>>) -> String {
+        let mut render = Render::new();
+        for hook in hooks {
+            render.add_hook(hook);
+        }
+        render.render(&page()).unwrap();
+        render.into()
+    }
+
+    #[test]
+    fn it_should_render_as_it_is_without_hooks() {
+        assert_eq!(
+            r#"<body><script src="/assets/app.js"></script><img src="/logo.png"/><div data-tracking="1">ad</div><p><script src="/assets/static.js"></script></p></body>"#,
+            render_with(vec![])
+        );
+    }
+
+    #[test]
+    fn it_should_call_each_hook_in_order() {
+        assert_eq!(
+            r#"<body><script src="https://cdn.example.com/assets/app.js" nonce="abc"></script><img src="/logo.png"/><p><script src="https://cdn.example.com/assets/static.js" nonce="abc"></script></p></body>"#,
+            render_with(vec![
+                Arc::new(SkipTracking),
+                Arc::new(Cdn),
+                Arc::new(ScriptNonce("abc")),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_call_a_chain_of_hooks_as_one() {
+        let chain: Vec<Arc<dyn RenderHook>> = vec![Arc::new(SkipTracking), Arc::new(Cdn)];
+
+        assert_eq!(
+            r#"<body><script src="https://cdn.example.com/assets/app.js"></script><img src="/logo.png"/><p><script src="https://cdn.example.com/assets/static.js"></script></p></body>"#,
+            render_with(vec![Arc::new(chain)])
+        );
+    }
+}
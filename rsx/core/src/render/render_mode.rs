@@ -0,0 +1,13 @@
+/// What is being rendered, which changes how nodes are written out.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum RenderMode {
+    /// HTML, as it is read by the browser.
+    #[default]
+    Html,
+
+    /// Well formed XML, for feeds, sitemaps, and XHTML.
+    ///
+    /// Every empty element is written as `<tag/>`, attributes always have a value,
+    /// and the contents of `<script>` and `<style>` are written as CDATA.
+    Xml,
+}
@@ -0,0 +1,147 @@
+use crate::render::Render;
+use ::std::ops::Deref;
+use ::std::ops::DerefMut;
+use ::std::sync::Mutex;
+
+const DEFAULT_MAX_POOLED: usize = 32;
+
+/// A pool of `Render`s, so their buffers can be reused across requests.
+///
+/// ```
+/// use ::core::dom::Node;
+/// use ::core::render::RenderPool;
+///
+/// let pool = RenderPool::new();
+///
+/// let mut render = pool.take();
+/// render.render(&Node::new_text("Hello")).unwrap();
+/// assert_eq!(render.as_str(), "Hello");
+/// ```
+///
+/// When the `PooledRender` is dropped, it is cleared and returned to the pool.
+#[derive(Debug)]
+pub struct RenderPool {
+    renders: Mutex<Vec<Render>>,
+    max_pooled: usize,
+}
+
+impl RenderPool {
+    pub fn new() -> Self {
+        Self::with_max_pooled(DEFAULT_MAX_POOLED)
+    }
+
+    /// Creates a pool which holds on to at most `max_pooled` idle `Render`s.
+    pub fn with_max_pooled(max_pooled: usize) -> Self {
+        Self {
+            renders: Mutex::new(Vec::new()),
+            max_pooled,
+        }
+    }
+
+    /// Takes a `Render` from the pool, or creates a new one if the pool is empty.
+    pub fn take(&self) -> PooledRender<'_> {
+        let maybe_render = self.lock().pop();
+
+        PooledRender {
+            pool: self,
+            render: Some(maybe_render.unwrap_or_else(Render::new)),
+        }
+    }
+
+    /// The number of idle `Render`s held by the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn give(&self, mut render: Render) {
+        render.clear();
+
+        let mut renders = self.lock();
+        if renders.len() < self.max_pooled {
+            renders.push(render);
+        }
+    }
+
+    fn lock(&self) -> ::std::sync::MutexGuard<'_, Vec<Render>> {
+        // A panic whilst holding the lock cannot leave the Vec in a broken state,
+        // so a poisoned lock is safe to carry on using.
+        self.renders
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for RenderPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A `Render` borrowed from a `RenderPool`.
+/// It is returned to the pool when dropped.
+#[derive(Debug)]
+pub struct PooledRender<'a> {
+    pool: &'a RenderPool,
+    render: Option<Render>,
+}
+
+impl<'a> Deref for PooledRender<'a> {
+    type Target = Render;
+
+    fn deref(&self) -> &Render {
+        self.render.as_ref().unwrap()
+    }
+}
+
+impl<'a> DerefMut for PooledRender<'a> {
+    fn deref_mut(&mut self) -> &mut Render {
+        self.render.as_mut().unwrap()
+    }
+}
+
+impl<'a> Drop for PooledRender<'a> {
+    fn drop(&mut self) {
+        if let Some(render) = self.render.take() {
+            self.pool.give(render);
+        }
+    }
+}
+
+#[cfg(test)]
+mod render_pool {
+    use super::*;
+    use crate::dom::Node;
+
+    #[test]
+    fn it_should_reuse_returned_renders() {
+        let pool = RenderPool::new();
+
+        {
+            let mut render = pool.take();
+            render.render(&Node::new_text("Hello world!")).unwrap();
+        }
+
+        assert_eq!(pool.len(), 1);
+
+        let render = pool.take();
+        assert_eq!(render.as_str(), "");
+        assert!(render.capacity() >= "Hello world!".len());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn it_should_not_pool_more_than_the_max() {
+        let pool = RenderPool::with_max_pooled(1);
+
+        let first = pool.take();
+        let second = pool.take();
+        drop(first);
+        drop(second);
+
+        assert_eq!(pool.len(), 1);
+    }
+}
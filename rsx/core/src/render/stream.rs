@@ -0,0 +1,119 @@
+use ::std::fmt;
+use ::std::io;
+use ::std::sync::Arc;
+use ::std::sync::Mutex;
+
+/// Rendered output is written once this much of it can be.
+pub(crate) const STREAM_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Where `Render::render_to` writes the output, as it is rendered.
+///
+/// Output which may still be taken back is held in the buffer of the `Render`,
+/// such as within an `ErrorBoundary`, and written once it can no longer change.
+#[derive(Clone)]
+pub(crate) struct Stream {
+    writer: Arc<Mutex<dyn io::Write + Send>>,
+
+    /// How much of the output has been written, and so removed from the buffer.
+    written: usize,
+
+    holds: Vec<Hold>,
+
+    /// The head was collected by rendering once before,
+    /// so it is written at the `<head>`, and not collected again.
+    pub(crate) is_head_collected: bool,
+}
+
+/// Output from `from` on is kept, until the hold is released.
+#[derive(Clone, Copy, Debug)]
+struct Hold {
+    from: usize,
+
+    /// Once the output goes past this, the hold is no longer needed.
+    until_past: Option<usize>,
+}
+
+impl Stream {
+    pub(crate) fn new<W>(writer: W) -> Self
+    where
+        W: io::Write + Send + 'static,
+    {
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+            written: 0,
+            holds: Vec::new(),
+            is_head_collected: false,
+        }
+    }
+
+    pub(crate) fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Holds the output from `from`, returning what to `release` to end it.
+    pub(crate) fn hold(&mut self, from: usize, until_past: Option<usize>) -> usize {
+        let holds_len = self.holds.len();
+        self.holds.push(Hold { from, until_past });
+        holds_len
+    }
+
+    /// Ends the holds made since `hold` returned `holds_len`.
+    pub(crate) fn release(&mut self, holds_len: usize) {
+        self.holds.truncate(holds_len);
+    }
+
+    /// How much of the buffer can be written, now that the output has reached `position`.
+    ///
+    /// This is up to the earliest the output can still be taken back to.
+    /// A hold which the output has gone past is still needed,
+    /// if a later hold can take the output back before it.
+    fn writable_len(&self, buffer_len: usize, position: usize) -> usize {
+        let mut earliest = None;
+        for hold in self.holds.iter().rev() {
+            let is_needed = hold.until_past.is_none_or(|until| {
+                position <= until || earliest.is_some_and(|earliest| earliest <= until)
+            });
+            if is_needed {
+                earliest = Some(hold.from);
+            }
+        }
+
+        earliest.map_or(buffer_len, |earliest| earliest - self.written)
+    }
+
+    /// Writes the start of the buffer which is no longer held, and removes it from the buffer.
+    pub(crate) fn write(&mut self, buffer: &mut String) -> io::Result<()> {
+        let position = self.written + buffer.len();
+        let len = self.writable_len(buffer.len(), position);
+        if len == 0 {
+            return Ok(());
+        }
+
+        self.lock().write_all(&buffer.as_bytes()[..len])?;
+        buffer.drain(..len);
+        self.written += len;
+
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.lock().flush()
+    }
+
+    fn lock(&self) -> ::std::sync::MutexGuard<'_, dyn io::Write + Send + 'static> {
+        // A panic whilst writing may leave half a chunk written, which is no worse than failing to write it.
+        self.writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stream")
+            .field("written", &self.written)
+            .field("holds", &self.holds)
+            .field("is_head_collected", &self.is_head_collected)
+            .finish_non_exhaustive()
+    }
+}
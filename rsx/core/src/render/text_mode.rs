@@ -0,0 +1,126 @@
+include!("escaping.rs");
+
+#[cfg(test)]
+mod write_text {
+    use super::*;
+
+    fn write(mode: TextMode, text: &str) -> String {
+        let mut buffer = String::new();
+        mode.write_text(&mut buffer, text);
+        buffer
+    }
+
+    #[test]
+    fn it_should_escape_normal_text() {
+        assert_eq!(
+            "a &lt; b &amp;&amp; c",
+            write(TextMode::Normal, "a < b && c")
+        );
+    }
+
+    #[test]
+    fn it_should_not_escape_comments() {
+        assert_eq!("a < b &amp;", write(TextMode::Comment, "a < b &amp;"));
+    }
+
+    #[test]
+    fn it_should_guard_the_end_of_comments() {
+        assert_eq!(
+            "a -- ><b>x</b> --! > c - > d -- >",
+            write(TextMode::Comment, "a --><b>x</b> --!> c - > d -->")
+        );
+    }
+
+    #[test]
+    fn it_should_guard_the_end_of_comments_split_across_text() {
+        let mut buffer = String::new();
+        TextMode::Comment.write_text(&mut buffer, "a --");
+        TextMode::Comment.write_text(&mut buffer, "><b>x</b>");
+
+        assert_eq!("a -- ><b>x</b>", buffer);
+    }
+
+    #[test]
+    fn it_should_guard_closing_script_tags() {
+        let mode = TextMode::for_element("script");
+
+        assert_eq!(
+            r#"{"html": "<b>hi</b><\/script><\/SCRIPT>"}"#,
+            write(mode, r#"{"html": "<b>hi</b></script></SCRIPT>"}"#)
+        );
+    }
+
+    #[test]
+    fn it_should_not_escape_entities_in_style() {
+        let mode = TextMode::for_element("style");
+
+        assert_eq!(
+            r#"a > b::after { content: "&"; }"#,
+            write(mode, r#"a > b::after { content: "&"; }"#)
+        );
+    }
+
+    #[test]
+    fn it_should_escape_textarea_contents() {
+        let mode = TextMode::for_element("textarea");
+
+        assert_eq!("&lt;/textarea> &amp;", write(mode, "</textarea> &"));
+    }
+
+    #[test]
+    fn it_should_write_xml_scripts_as_cdata() {
+        assert_eq!(
+            "if (a < b) {}",
+            write(TextMode::for_xml_element("script"), "if (a < b) {}")
+        );
+        assert_eq!(
+            "if (a &lt; b) {}",
+            write(TextMode::for_xml_element("Script"), "if (a < b) {}")
+        );
+    }
+}
+
+#[cfg(test)]
+mod escape {
+    use super::*;
+
+    #[test]
+    fn it_should_escape_text() {
+        let mut buffer = String::new();
+        write_escaped_text(&mut buffer, r#"<b>"Fish" & Chips</b>"#);
+
+        assert_eq!(r#"&lt;b&gt;"Fish" &amp; Chips&lt;/b&gt;"#, buffer);
+    }
+
+    #[test]
+    fn it_should_escape_attributes() {
+        let mut buffer = String::new();
+        write_escaped_attribute(&mut buffer, r#""><script>&'"#);
+
+        assert_eq!("&quot;&gt;&lt;script&gt;&amp;'", buffer);
+    }
+
+    #[test]
+    fn it_should_leave_out_characters_xml_does_not_allow() {
+        let mut buffer = String::new();
+        write_escaped_xml_text(&mut buffer, "a\u{0}\u{8}\tb & c\u{FFFE}");
+
+        assert_eq!("a\tb &amp; c", buffer);
+    }
+
+    #[test]
+    fn it_should_escape_whitespace_in_xml_attributes() {
+        let mut buffer = String::new();
+        write_escaped_xml_attribute(&mut buffer, "a\tb\n\"c\"");
+
+        assert_eq!("a&#9;b&#10;&quot;c&quot;", buffer);
+    }
+
+    #[test]
+    fn it_should_split_cdata_end_markers() {
+        let mut buffer = String::new();
+        write_cdata_text(&mut buffer, "if (a[b[0]]>c) {}");
+
+        assert_eq!("if (a[b[0]]]]><![CDATA[>c) {}", buffer);
+    }
+}
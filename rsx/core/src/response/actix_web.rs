@@ -0,0 +1,99 @@
+use crate::dom::Node;
+use crate::render::RenderError;
+use crate::response::render_response;
+use crate::response::HTML_CONTENT_TYPE;
+use ::actix_web::body::BoxBody;
+use ::actix_web::HttpRequest;
+use ::actix_web::HttpResponse;
+use ::actix_web::Responder;
+use ::actix_web::ResponseError;
+
+/// The body is streamed as it is rendered.
+/// A node which fails to render before the first chunk is a `500 Internal Server Error`,
+/// with the `RenderError` as the error of the response.
+impl Responder for Node {
+    type Body = BoxBody;
+
+    fn respond_to(self, _request: &HttpRequest) -> HttpResponse<Self::Body> {
+        match render_response(self) {
+            Ok(stream) => HttpResponse::Ok()
+                .content_type(HTML_CONTENT_TYPE)
+                .streaming(stream),
+            Err(error) => HttpResponse::from_error(error),
+        }
+    }
+}
+
+impl ResponseError for RenderError {}
+
+#[cfg(test)]
+mod responder {
+    use super::*;
+    use crate::component::ComponentError;
+    use crate::render::render;
+    use crate::response::long_page;
+    use ::actix_web::body;
+    use ::actix_web::body::MessageBody;
+    use ::actix_web::http::header::CONTENT_TYPE;
+    use ::actix_web::http::StatusCode;
+    use ::actix_web::test;
+    use ::actix_web::web;
+    use ::actix_web::App;
+
+    #[actix_rt::test]
+    async fn it_should_respond_with_rendered_html() {
+        let app = test::init_service(App::new().route(
+            "/",
+            web::get().to(|| async { Node::new_open("p", None, Some(vec![Node::new_text("Hi")])) }),
+        ))
+        .await;
+
+        let request = test::TestRequest::get().uri("/").to_request();
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            HTML_CONTENT_TYPE,
+            response.headers().get(CONTENT_TYPE).unwrap()
+        );
+
+        let body = test::read_body(response).await;
+        assert_eq!("<p>Hi</p>", body);
+    }
+
+    #[actix_rt::test]
+    async fn it_should_keep_the_error_when_rendering_fails() {
+        let request = test::TestRequest::get().to_http_request();
+        let response = Node::new_error(ComponentError::new("user not found")).respond_to(&request);
+
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+        assert_eq!("user not found", response.error().unwrap().to_string());
+    }
+
+    #[actix_rt::test]
+    async fn it_should_stream_the_html_a_chunk_at_a_time() {
+        let request = test::TestRequest::get().to_http_request();
+        let mut body = long_page(Node::Empty).respond_to(&request).into_body();
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = ::std::future::poll_fn(|cx| body.as_pin_mut().poll_next(cx)).await {
+            chunks.push(chunk.unwrap());
+        }
+
+        assert!(chunks.len() > 1);
+        assert_eq!(
+            render(long_page(Node::Empty)).unwrap().as_bytes(),
+            chunks.concat()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn it_should_end_the_body_with_the_error_when_rendering_fails_part_way() {
+        let request = test::TestRequest::get().to_http_request();
+        let page = long_page(Node::new_error(ComponentError::new("user not found")));
+        let response = page.respond_to(&request);
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(body::to_bytes(response.into_body()).await.is_err());
+    }
+}
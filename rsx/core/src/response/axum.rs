@@ -0,0 +1,114 @@
+use crate::dom::Node;
+use crate::response::render_response;
+use crate::response::HTML_CONTENT_TYPE;
+use ::axum_core::body::Body;
+use ::axum_core::response::IntoResponse;
+use ::axum_core::response::Response;
+use ::http::header::CONTENT_TYPE;
+use ::http::HeaderValue;
+use ::http::StatusCode;
+
+/// The body is streamed as it is rendered.
+/// A node which fails to render before the first chunk is a `500 Internal Server Error`,
+/// with the `RenderError` in the extensions of the response.
+impl IntoResponse for Node {
+    fn into_response(self) -> Response {
+        match render_response(self) {
+            Ok(stream) => (
+                [(CONTENT_TYPE, HeaderValue::from_static(HTML_CONTENT_TYPE))],
+                Body::from_stream(stream),
+            )
+                .into_response(),
+            Err(error) => {
+                let mut response = StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                response.extensions_mut().insert(error);
+                response
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod into_response {
+    use super::*;
+    use crate::component::ComponentError;
+    use crate::render::render;
+    use crate::render::RenderError;
+    use crate::response::long_page;
+    use ::axum::routing::get;
+    use ::axum::Router;
+    use ::http::Request;
+    use ::http_body_util::BodyExt;
+    use ::tower::ServiceExt;
+
+    #[tokio::test]
+    async fn it_should_respond_with_rendered_html() {
+        let router = Router::new().route(
+            "/",
+            get(|| async { Node::new_open("p", None, Some(vec![Node::new_text("Hi")])) }),
+        );
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(HTML_CONTENT_TYPE, response.headers()[CONTENT_TYPE]);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!("<p>Hi</p>", body);
+    }
+
+    #[tokio::test]
+    async fn it_should_keep_the_error_when_rendering_fails() {
+        let router = Router::new().route(
+            "/",
+            get(|| async { Node::new_error(ComponentError::new("user not found")) }),
+        );
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+        assert_eq!(
+            "user not found",
+            response
+                .extensions()
+                .get::<RenderError>()
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_stream_the_html_a_chunk_at_a_time() {
+        let router = Router::new().route("/", get(|| async { long_page(Node::Empty) }));
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let mut body = router.oneshot(request).await.unwrap().into_body();
+
+        let mut chunks = Vec::new();
+        while let Some(frame) = body.frame().await {
+            chunks.push(frame.unwrap().into_data().unwrap());
+        }
+
+        assert!(chunks.len() > 1);
+        assert_eq!(
+            render(long_page(Node::Empty)).unwrap().as_bytes(),
+            chunks.concat()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_end_the_body_with_the_error_when_rendering_fails_part_way() {
+        let router = Router::new().route(
+            "/",
+            get(|| async { long_page(Node::new_error(ComponentError::new("user not found"))) }),
+        );
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(response.into_body().collect().await.is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! Returning a `Node` from the handlers of web frameworks.
+//!
+//! Each framework is behind a cargo feature of the same name,
+//! i.e. `axum`, `actix-web`, and `warp`.
+//!
+//! The node is rendered as HTML, with a `Content-Type` of `text/html; charset=utf-8`.
+//!
+//! Responses are streamed. The node is rendered with `Render::render_to` on a blocking thread,
+//! and each chunk is sent as soon as it is rendered.
+//! The response waits for the first chunk, so if the node fails to render before then,
+//! the response is a `500 Internal Server Error` instead. The `RenderError` is logged,
+//! and with axum and actix-web, it is also kept on the response for middleware to find.
+//! If it fails after the response has started, the body ends with the error.
+
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use crate::component::ComponentError;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use crate::dom::Node;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use crate::render::Render;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use crate::render::RenderError;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use ::std::io;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use ::std::panic;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use ::std::panic::AssertUnwindSafe;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use ::std::sync::mpsc;
+
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+mod render_stream;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+use self::render_stream::*;
+
+#[cfg(feature = "actix-web")]
+mod actix_web;
+
+#[cfg(feature = "axum")]
+mod axum;
+
+#[cfg(feature = "warp")]
+mod warp;
+
+/// The `Content-Type` of a rendered `Node`.
+pub const HTML_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+/// How many rendered chunks can be waiting to be sent, before rendering waits for them.
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+const UNSENT_CHUNKS: usize = 4;
+
+/// Starts rendering the node for a response, and waits for the first chunk of it.
+///
+/// An error before then is returned, and one after ends the `RenderStream`.
+/// Either way it is logged.
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "warp"))]
+fn render_response(node: Node) -> Result<RenderStream, RenderError> {
+    let (started_sender, started) = mpsc::sync_channel(0);
+    let (chunk_sender, chunks) = ::tokio::sync::mpsc::channel(UNSENT_CHUNKS);
+    let writer = ChunkWriter::new(started_sender.clone(), chunk_sender.clone());
+
+    ::tokio::task::spawn_blocking(move || {
+        let mut render = Render::with_capacity(node.size_hint());
+        let result = panic::catch_unwind(AssertUnwindSafe(|| render.render_to(&node, writer)))
+            .unwrap_or_else(|payload| Err(ComponentError::from_panic(payload).into()));
+
+        if let Err(error) = &result {
+            ::log::error!("Failed to render the response, {}", error);
+        }
+
+        // Once the response has started, nothing is waiting for this,
+        // and so the error goes to the end of the body instead.
+        if let Err(mpsc::SendError(Err(error))) = started_sender.send(result) {
+            let _ = chunk_sender.blocking_send(Err(error));
+        }
+    });
+
+    match started.recv() {
+        Ok(result) => result.map(|()| RenderStream::new(chunks)),
+        Err(mpsc::RecvError) => Err(io::Error::other("the render was never run").into()),
+    }
+}
+
+/// A page long enough to be sent in more than one chunk.
+#[cfg(all(test, any(feature = "actix-web", feature = "axum", feature = "warp")))]
+fn long_page(ending: Node) -> Node {
+    let items = (0..2000).map(|i| Node::new_open("li", None, Some(i.to_string())));
+    let list = Node::new_open("ul", None, Some(items.collect::<Vec<Node>>()));
+
+    Node::new_fragment(vec![list, ending])
+}
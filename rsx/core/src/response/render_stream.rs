@@ -0,0 +1,69 @@
+use crate::render::RenderError;
+use ::bytes::Bytes;
+use ::futures_core::Stream;
+use ::std::io;
+use ::std::pin::Pin;
+use ::std::sync::mpsc::SyncSender;
+use ::std::task::Context;
+use ::std::task::Poll;
+use ::tokio::sync::mpsc::Receiver;
+use ::tokio::sync::mpsc::Sender;
+
+/// The body of a response, as it is rendered.
+///
+/// If rendering fails part way, this ends with the `RenderError`.
+#[derive(Debug)]
+pub(crate) struct RenderStream {
+    chunks: Receiver<Result<Bytes, RenderError>>,
+}
+
+impl RenderStream {
+    pub(crate) fn new(chunks: Receiver<Result<Bytes, RenderError>>) -> Self {
+        Self { chunks }
+    }
+}
+
+impl Stream for RenderStream {
+    type Item = Result<Bytes, RenderError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.chunks.poll_recv(cx)
+    }
+}
+
+/// What the render writes to, sending each chunk on to the `RenderStream`.
+pub(crate) struct ChunkWriter {
+    /// Told when the first chunk is written, as the response is then started.
+    started: Option<SyncSender<Result<(), RenderError>>>,
+    chunks: Sender<Result<Bytes, RenderError>>,
+}
+
+impl ChunkWriter {
+    pub(crate) fn new(
+        started: SyncSender<Result<(), RenderError>>,
+        chunks: Sender<Result<Bytes, RenderError>>,
+    ) -> Self {
+        Self {
+            started: Some(started),
+            chunks,
+        }
+    }
+}
+
+impl io::Write for ChunkWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if let Some(started) = self.started.take() {
+            let _ = started.send(Ok(()));
+        }
+
+        self.chunks
+            .blocking_send(Ok(Bytes::copy_from_slice(bytes)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "the response was dropped"))?;
+
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::dom::Node;
+use crate::response::render_response;
+use crate::response::HTML_CONTENT_TYPE;
+use ::warp::http::header::CONTENT_TYPE;
+use ::warp::http::HeaderValue;
+use ::warp::http::StatusCode;
+use ::warp::hyper::Body;
+use ::warp::reply::Reply;
+use ::warp::reply::Response;
+
+/// The body is streamed as it is rendered.
+/// A node which fails to render before the first chunk is a `500 Internal Server Error`.
+/// Replies cannot carry an error, so the `RenderError` is only logged.
+impl Reply for Node {
+    fn into_response(self) -> Response {
+        match render_response(self) {
+            Ok(stream) => {
+                let mut response = Response::new(Body::wrap_stream(stream));
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static(HTML_CONTENT_TYPE));
+                response
+            }
+            Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod reply {
+    use super::*;
+    use crate::component::ComponentError;
+    use crate::render::render;
+    use crate::response::long_page;
+    use ::warp::Filter;
+
+    #[tokio::test]
+    async fn it_should_respond_with_rendered_html() {
+        let filter =
+            ::warp::path::end().map(|| Node::new_open("p", None, Some(vec![Node::new_text("Hi")])));
+
+        let response = ::warp::test::request().path("/").reply(&filter).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(HTML_CONTENT_TYPE, response.headers()[CONTENT_TYPE]);
+        assert_eq!("<p>Hi</p>", response.body());
+    }
+
+    #[tokio::test]
+    async fn it_should_respond_with_an_error_when_rendering_fails() {
+        let filter =
+            ::warp::path::end().map(|| Node::new_error(ComponentError::new("user not found")));
+
+        let response = ::warp::test::request().path("/").reply(&filter).await;
+
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+    }
+
+    #[tokio::test]
+    async fn it_should_stream_the_whole_html() {
+        let filter = ::warp::path::end().map(|| long_page(Node::Empty));
+
+        let response = ::warp::test::request().path("/").reply(&filter).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            render(long_page(Node::Empty)).unwrap().as_bytes(),
+            response.body()
+        );
+    }
+}
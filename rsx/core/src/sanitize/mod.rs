@@ -0,0 +1,547 @@
+//! Removes the dangerous parts of a `Node` tree, before it is rendered.
+//!
+//! ```
+//! use ::core::parse::parse_html;
+//! use ::core::render::render;
+//! use ::core::sanitize::Sanitizer;
+//!
+//! let node = parse_html(r#"<p onclick="steal()">Hi<script>steal()</script></p>"#).unwrap();
+//! let node = Sanitizer::default().sanitize(node);
+//!
+//! assert_eq!(render(&node).unwrap(), "<p>Hi</p>");
+//! ```
+
+use crate::dom::Attribute;
+use crate::dom::AttributeValue;
+use crate::dom::Attributes;
+use crate::dom::Node;
+use ::std::collections::HashMap;
+use ::std::collections::HashSet;
+use ::std::mem;
+
+mod url;
+pub(crate) use self::url::url_scheme;
+
+const DEFAULT_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Elements where the contents are dangerous, or make no sense, without the element.
+const DEFAULT_REMOVED_TAGS: &[&str] = &[
+    "applet",
+    "embed",
+    "frame",
+    "frameset",
+    "head",
+    "iframe",
+    "math",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "plaintext",
+    "script",
+    "select",
+    "style",
+    "svg",
+    "template",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+const DEFAULT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["href", "title"]),
+    ("abbr", &["title"]),
+    ("blockquote", &["cite"]),
+    ("del", &["cite"]),
+    ("img", &["src", "alt", "title", "width", "height"]),
+    ("ins", &["cite"]),
+    ("ol", &["start"]),
+    ("q", &["cite"]),
+    ("td", &["colspan", "rowspan"]),
+    ("th", &["colspan", "rowspan", "scope"]),
+];
+
+const DEFAULT_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Attributes which hold a URL, and so have their scheme checked.
+const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "classid",
+    "codebase",
+    "data",
+    "dynsrc",
+    "formaction",
+    "href",
+    "longdesc",
+    "lowsrc",
+    "manifest",
+    "ping",
+    "poster",
+    "profile",
+    "src",
+    "usemap",
+    "xlink:href",
+];
+
+/// Strips elements, attributes, and URLs which are not allowed.
+///
+/// `Sanitizer::new` allows nothing but text, and `Sanitizer::default` has a conservative policy
+/// for user content (links, images, tables, and text formatting).
+///
+///  * Elements which are not allowed are unwrapped, keeping their contents.
+///  * Elements which are removed (i.e. `<script>`) are dropped along with their contents.
+///  * Attributes which are not allowed are dropped.
+///    Event handlers (`on*`) are always dropped, even when allowed.
+///  * URL attributes, such as `href`, are dropped unless they are relative,
+///    or use an allowed scheme.
+///  * Comments and doctypes are dropped, unless comments are allowed.
+///
+/// Text is escaped when it is rendered, so it is left as it is.
+/// Pre-rendered HTML is written out verbatim, so it is parsed, and sanitized as well.
+#[derive(Clone, Debug)]
+pub struct Sanitizer {
+    tags: HashSet<String>,
+    removed_tags: HashSet<String>,
+    attributes: HashMap<String, HashSet<String>>,
+    global_attributes: HashSet<String>,
+    url_schemes: HashSet<String>,
+    allow_comments: bool,
+}
+
+/// What happens to an element.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum ElementAction {
+    Keep,
+    Unwrap,
+    Remove,
+}
+
+impl Sanitizer {
+    /// Creates a `Sanitizer` which allows nothing but text.
+    pub fn new() -> Self {
+        Self {
+            tags: HashSet::new(),
+            removed_tags: HashSet::new(),
+            attributes: HashMap::new(),
+            global_attributes: HashSet::new(),
+            url_schemes: HashSet::new(),
+            allow_comments: false,
+        }
+    }
+
+    pub fn allow_tags(mut self, tags: &[&str]) -> Self {
+        self.tags
+            .extend(tags.iter().map(|tag| tag.to_ascii_lowercase()));
+        self
+    }
+
+    /// Elements which are removed along with their contents, rather than unwrapped.
+    pub fn remove_tags(mut self, tags: &[&str]) -> Self {
+        self.removed_tags
+            .extend(tags.iter().map(|tag| tag.to_ascii_lowercase()));
+        self
+    }
+
+    /// Allows the attributes on the given element.
+    pub fn allow_attributes(mut self, tag: &str, attributes: &[&str]) -> Self {
+        self.attributes
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .extend(attributes.iter().map(|key| key.to_ascii_lowercase()));
+        self
+    }
+
+    /// Allows the attributes on every element.
+    pub fn allow_global_attributes(mut self, attributes: &[&str]) -> Self {
+        self.global_attributes
+            .extend(attributes.iter().map(|key| key.to_ascii_lowercase()));
+        self
+    }
+
+    /// Allows URLs with these schemes, i.e. `https`.
+    /// Relative URLs are always allowed.
+    pub fn allow_url_schemes(mut self, schemes: &[&str]) -> Self {
+        self.url_schemes
+            .extend(schemes.iter().map(|scheme| scheme.to_ascii_lowercase()));
+        self
+    }
+
+    pub fn allow_comments(mut self, allow_comments: bool) -> Self {
+        self.allow_comments = allow_comments;
+        self
+    }
+
+    pub fn sanitize(&self, node: Node) -> Node {
+        let mut nodes = Vec::new();
+        self.sanitize_node(node, &mut nodes);
+
+        match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.remove(0),
+            _ => Node::Fragment { children: nodes },
+        }
+    }
+
+    /// Sanitizes the node, and pushes what is left to `output`.
+    fn sanitize_node(&self, node: Node, output: &mut Vec<Node>) {
+        match node {
+            Node::Empty
+            | Node::Doctype { .. }
+            | Node::ProcessingInstruction { .. }
+            | Node::Slot { .. }
+            | Node::SlotContent { .. } => {}
+            Node::Comment { children } => {
+                if let Some(comment) = self.sanitize_comment(children) {
+                    output.push(comment);
+                }
+            }
+            // The contents are text, which is escaped once outside of the `<![CDATA[`.
+            Node::CData { children } => {
+                let text: String = children.iter().flatten().filter_map(Node::text).collect();
+                if !text.is_empty() {
+                    output.push(Node::new_text(text));
+                }
+            }
+            Node::Fragment { children } => self.sanitize_nodes(children, output),
+            Node::SelfClosing { name, attributes } => {
+                if self.element_action(&name) == ElementAction::Keep {
+                    let attributes = self.sanitize_attributes(&name, attributes);
+                    output.push(Node::SelfClosing { name, attributes });
+                }
+            }
+            Node::OpenEmpty { name, attributes } => {
+                if self.element_action(&name) == ElementAction::Keep {
+                    let attributes = self.sanitize_attributes(&name, attributes);
+                    output.push(Node::OpenEmpty { name, attributes });
+                }
+            }
+            Node::OpenWithChildren {
+                name,
+                attributes,
+                children,
+            } => match self.element_action(&name) {
+                ElementAction::Keep => {
+                    let attributes = self.sanitize_attributes(&name, attributes);
+                    let mut sanitized = Vec::new();
+                    self.sanitize_nodes(children, &mut sanitized);
+                    output.push(Node::OpenWithChildren {
+                        name,
+                        attributes,
+                        children: sanitized,
+                    });
+                }
+                ElementAction::Unwrap => self.sanitize_nodes(children, output),
+                ElementAction::Remove => {}
+            },
+            Node::Text { contents } => output.push(Node::Text { contents }),
+            Node::Translation { translation } => output.push(Node::Translation { translation }),
+            // The fallback is written by the app, so only the children are sanitized.
+            Node::ErrorBoundary { mut boundary } => {
+                let children = mem::take(boundary.children_mut());
+                self.sanitize_nodes(children, boundary.children_mut());
+                output.push(Node::ErrorBoundary { boundary });
+            }
+            Node::Error { error } => output.push(Node::Error { error }),
+            Node::Static {
+                build, mut tree, ..
+            } => {
+                let node = tree.take().map_or_else(build, |node| *node);
+                self.sanitize_node(node, output);
+            }
+        }
+    }
+
+    fn sanitize_nodes(&self, nodes: Vec<Node>, output: &mut Vec<Node>) {
+        for node in nodes {
+            self.sanitize_node(node, output);
+        }
+    }
+
+    /// Comments are only kept if they hold plain text, which cannot end the comment early.
+    fn sanitize_comment(&self, children: Option<Vec<Node>>) -> Option<Node> {
+        if !self.allow_comments {
+            return None;
+        }
+
+        let children = match children {
+            None => return Some(Node::Comment { children: None }),
+            Some(children) => children,
+        };
+
+        let is_safe = children.iter().all(|child| match child {
+            Node::Text { contents } => !contents.contains("--") && !contents.contains('>'),
+            _ => false,
+        });
+
+        if is_safe {
+            Some(Node::Comment {
+                children: Some(children),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn element_action(&self, name: &str) -> ElementAction {
+        let name = name.to_ascii_lowercase();
+
+        if self.tags.contains(&name) {
+            ElementAction::Keep
+        } else if self.removed_tags.contains(&name) {
+            ElementAction::Remove
+        } else {
+            ElementAction::Unwrap
+        }
+    }
+
+    fn sanitize_attributes(
+        &self,
+        tag: &str,
+        maybe_attributes: Option<Attributes>,
+    ) -> Option<Attributes> {
+        let tag = tag.to_ascii_lowercase();
+        let attributes: Vec<Attribute> = maybe_attributes?
+            .iter()
+            .filter(|attribute| self.is_allowed_attribute(&tag, attribute))
+            .cloned()
+            .collect();
+
+        if attributes.is_empty() {
+            None
+        } else {
+            Some(Attributes::Owned(attributes))
+        }
+    }
+
+    fn is_allowed_attribute(&self, tag: &str, attribute: &Attribute) -> bool {
+        let key = attribute.key.to_ascii_lowercase();
+        if key.starts_with("on") {
+            return false;
+        }
+
+        let is_allowed = self.global_attributes.contains(&key)
+            || self
+                .attributes
+                .get(tag)
+                .map(|attributes| attributes.contains(&key))
+                .unwrap_or(false);
+        if !is_allowed {
+            return false;
+        }
+
+        match &attribute.value {
+            AttributeValue::Text(url) if URL_ATTRIBUTES.contains(&key.as_str()) => {
+                match url_scheme(url) {
+                    None => true,
+                    Some(scheme) => self.url_schemes.contains(&scheme),
+                }
+            }
+            _ => true,
+        }
+    }
+}
+
+impl Default for Sanitizer {
+    /// A conservative policy, for content written by users.
+    fn default() -> Self {
+        let mut sanitizer = Self::new()
+            .allow_tags(DEFAULT_TAGS)
+            .remove_tags(DEFAULT_REMOVED_TAGS)
+            .allow_url_schemes(DEFAULT_URL_SCHEMES);
+
+        for (tag, attributes) in DEFAULT_ATTRIBUTES {
+            sanitizer = sanitizer.allow_attributes(tag, attributes);
+        }
+
+        sanitizer
+    }
+}
+
+#[cfg(test)]
+mod sanitize {
+    use super::*;
+    use crate::parse::parse_html;
+    use crate::render::render;
+
+    fn sanitize_html(sanitizer: &Sanitizer, html: &str) -> String {
+        render(sanitizer.sanitize(parse_html(html).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn it_should_allow_only_text_by_default_when_new() {
+        assert_eq!(
+            "Hello world!",
+            sanitize_html(&Sanitizer::new(), "<p>Hello <b>world</b>!</p>")
+        );
+    }
+
+    #[test]
+    fn it_should_keep_allowed_tags_and_attributes() {
+        let sanitizer = Sanitizer::new()
+            .allow_tags(&["p", "a"])
+            .allow_attributes("a", &["href"])
+            .allow_url_schemes(&["https"]);
+
+        assert_eq!(
+            r#"<p><a href="https://example.com">Hi</a></p>"#,
+            sanitize_html(
+                &sanitizer,
+                r#"<p class="x"><a href="https://example.com" target="_blank">Hi</a></p>"#
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_unwrap_disallowed_tags() {
+        assert_eq!(
+            "<p>Hello world</p>",
+            sanitize_html(
+                &Sanitizer::default(),
+                "<p><font color=red>Hello <blink>world</blink></font></p>"
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_remove_tags_with_their_contents() {
+        assert_eq!(
+            "<p>Hi</p>",
+            sanitize_html(
+                &Sanitizer::default(),
+                "<p>Hi<style>p { color: red }</style><iframe><p>a</p></iframe></p>"
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_drop_event_handlers_even_when_allowed() {
+        let sanitizer = Sanitizer::new()
+            .allow_tags(&["p"])
+            .allow_global_attributes(&["onclick", "title"]);
+
+        assert_eq!(
+            r#"<p title="a">Hi</p>"#,
+            sanitize_html(&sanitizer, r#"<p onclick="steal()" title="a">Hi</p>"#)
+        );
+    }
+
+    #[test]
+    fn it_should_drop_urls_with_disallowed_schemes() {
+        assert_eq!(
+            r#"<a>a</a><a href="/page">b</a><a href="mailto:a@example.com">c</a>"#,
+            sanitize_html(
+                &Sanitizer::default(),
+                r#"<a href="javascript:alert(1)">a</a><a href="/page">b</a><a href="mailto:a@example.com">c</a>"#
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_drop_comments_unless_allowed() {
+        let html = "<p>a<!-- note --></p>";
+
+        assert_eq!("<p>a</p>", sanitize_html(&Sanitizer::default(), html));
+        assert_eq!(
+            "<p>a<!-- note --></p>",
+            sanitize_html(&Sanitizer::default().allow_comments(true), html)
+        );
+    }
+
+    #[test]
+    fn it_should_leave_markup_within_text_nodes_to_be_escaped() {
+        let node = Node::new_open(
+            "p",
+            None,
+            Some(vec![Node::new_text("<img src=x onerror=alert(1)>")]),
+        );
+
+        assert_eq!(
+            "<p>&lt;img src=x onerror=alert(1)&gt;</p>",
+            render(Sanitizer::default().sanitize(node)).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_turn_cdata_into_escaped_text() {
+        let node = Node::new_open(
+            "p",
+            None,
+            Some(vec![Node::CData {
+                children: Some(vec![Node::new_text("]]><script>alert(1)</script>")]),
+            }]),
+        );
+
+        assert_eq!(
+            "<p>]]&gt;&lt;script&gt;alert(1)&lt;/script&gt;</p>",
+            render(Sanitizer::default().sanitize(node)).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_escape_quotes_within_attribute_values() {
+        let node = Node::new_open(
+            "a",
+            Some(Attributes::Owned(vec![Attribute::new(
+                "title",
+                AttributeValue::Text(r#"" onclick="alert(1)"#.into()),
+            )])),
+            Some(vec![Node::new_text("a")]),
+        );
+
+        assert_eq!(
+            r#"<a title="&quot; onclick=&quot;alert(1)">a</a>"#,
+            render(Sanitizer::default().sanitize(node)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::parse::decode_entities;
+
+/// Returns the scheme of the URL, in lower case, i.e. `https` for `https://example.com`.
+/// Relative URLs have no scheme.
+///
+/// The URL is read the way a browser would read it, within an attribute.
+/// So entities are decoded, and whitespace and control characters are ignored.
+/// i.e. `java&#x09;script:` is read as `javascript:`.
+pub fn url_scheme(url: &str) -> Option<String> {
+    let url: String = decode_entities(url)
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+
+    let end = url.find([':', '/', '?', '#'])?;
+    if url[end..].starts_with(':') {
+        Some(url[..end].to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod url_scheme {
+    use super::*;
+
+    #[test]
+    fn it_should_find_schemes() {
+        assert_eq!(Some("https".to_string()), url_scheme("https://example.com"));
+        assert_eq!(
+            Some("mailto".to_string()),
+            url_scheme("MailTo:a@example.com")
+        );
+    }
+
+    #[test]
+    fn it_should_find_no_scheme_for_relative_urls() {
+        assert_eq!(None, url_scheme("/page?next=javascript:alert(1)"));
+        assert_eq!(None, url_scheme("#top"));
+        assert_eq!(None, url_scheme("page.html"));
+        assert_eq!(None, url_scheme(""));
+    }
+
+    #[test]
+    fn it_should_find_obfuscated_schemes() {
+        let cases = [
+            " javascript:alert(1)",
+            "JaVaScRiPt:alert(1)",
+            "java\tscript:alert(1)",
+            "java\nscript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "java&#x09;script:alert(1)",
+            "&#106;&#97;&#118;&#97;&#115;&#99;&#114;&#105;&#112;&#116;&#58;alert(1)",
+            "&#106avascript&#58alert(1)",
+            "javascript&colon;alert(1)",
+        ];
+
+        for case in cases.iter() {
+            assert_eq!(
+                Some("javascript".to_string()),
+                url_scheme(case),
+                "{:?}",
+                case
+            );
+        }
+    }
+}
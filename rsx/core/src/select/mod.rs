@@ -0,0 +1,365 @@
+//! Finds nodes in a `Node` tree using CSS selectors.
+//!
+//! ```
+//! use ::core::parse::parse_html;
+//!
+//! let list = parse_html(r#"<ul><li>a</li><li class="active">b</li></ul>"#).unwrap();
+//! let active = list.select_first("ul > li.active").unwrap();
+//!
+//! assert_eq!(active.text_content(), "b");
+//! ```
+//!
+//! Supported are type, class, id, and attribute selectors,
+//! descendant and child combinators, selector lists,
+//! and the `:first-child`, `:last-child`, and `:nth-child` pseudo classes.
+
+use crate::dom::AttributeValue;
+use crate::dom::Node;
+use ::std::borrow::Cow;
+use ::std::str::FromStr;
+
+mod parse;
+pub use self::parse::Result;
+pub use self::parse::SelectorError;
+use self::parse::*;
+
+/// A parsed CSS selector, which can be used many times.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Selector {
+    selectors: Vec<ComplexSelector>,
+}
+
+impl Selector {
+    pub fn parse(selectors: &str) -> Result<Self> {
+        Ok(Self {
+            selectors: parse_selector_list(selectors)?,
+        })
+    }
+
+    /// Returns every element matching this selector, in document order.
+    ///
+    /// The node itself is included, so `li` will match a node which is an `<li>`.
+    pub fn select<'a>(&self, node: &'a Node) -> Vec<&'a Node> {
+        let mut found = Vec::new();
+        self.find(node, &mut found, false);
+        found
+    }
+
+    /// Returns the first element matching this selector, in document order.
+    pub fn select_first<'a>(&self, node: &'a Node) -> Option<&'a Node> {
+        let mut found = Vec::new();
+        self.find(node, &mut found, true);
+        found.pop()
+    }
+
+    fn find<'a>(&self, node: &'a Node, found: &mut Vec<&'a Node>, first_only: bool) {
+        let node = node.resolve();
+        let top: Vec<&'a Node> = if node.is_element() {
+            vec![node]
+        } else {
+            node.children().collect()
+        };
+
+        self.find_in_siblings(top, &mut Vec::new(), found, first_only);
+    }
+
+    /// Returns true if it should stop, because the first match has been found.
+    fn find_in_siblings<'a>(
+        &self,
+        siblings: Vec<&'a Node>,
+        path: &mut Vec<Position<'a>>,
+        found: &mut Vec<&'a Node>,
+        first_only: bool,
+    ) -> bool {
+        let elements: Vec<&'a Node> = siblings.into_iter().filter(|n| n.is_element()).collect();
+        let count = elements.len();
+
+        for (index, element) in elements.into_iter().enumerate() {
+            path.push(Position {
+                element,
+                index: index + 1,
+                count,
+            });
+
+            if self.selectors.iter().any(|s| matches_path(s, path)) {
+                found.push(element);
+                if first_only {
+                    return true;
+                }
+            }
+
+            let children = element.children().collect();
+            if self.find_in_siblings(children, path, found, first_only) {
+                return true;
+            }
+
+            path.pop();
+        }
+
+        false
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selectors: &str) -> Result<Self> {
+        Self::parse(selectors)
+    }
+}
+
+impl Node {
+    /// Returns every element matching the CSS selector, in document order.
+    /// This includes the node itself.
+    ///
+    /// # Panics
+    ///
+    /// If the selector is not valid.
+    /// Use `Selector::parse` to handle invalid selectors.
+    pub fn select(&self, selectors: &str) -> Vec<&Node> {
+        parse_or_panic(selectors).select(self)
+    }
+
+    /// Returns the first element matching the CSS selector, in document order.
+    ///
+    /// # Panics
+    ///
+    /// If the selector is not valid.
+    /// Use `Selector::parse` to handle invalid selectors.
+    pub fn select_first(&self, selectors: &str) -> Option<&Node> {
+        parse_or_panic(selectors).select_first(self)
+    }
+}
+
+fn parse_or_panic(selectors: &str) -> Selector {
+    Selector::parse(selectors)
+        .unwrap_or_else(|err| panic!("invalid selector '{}', {}", selectors, err))
+}
+
+/// Where an element is, among the elements next to it.
+struct Position<'a> {
+    element: &'a Node,
+    /// Starts at 1, the same as `:nth-child`.
+    index: usize,
+    count: usize,
+}
+
+/// Checks the selector against the last element in the path.
+/// The rest of the path are its ancestors.
+fn matches_path(selector: &ComplexSelector, path: &[Position<'_>]) -> bool {
+    matches_from(selector, selector.compounds.len() - 1, path)
+}
+
+fn matches_from(selector: &ComplexSelector, compound: usize, path: &[Position<'_>]) -> bool {
+    let (position, ancestors) = match path.split_last() {
+        Some(split) => split,
+        None => return false,
+    };
+
+    if !matches_compound(&selector.compounds[compound], position) {
+        return false;
+    }
+    if compound == 0 {
+        return true;
+    }
+
+    match selector.combinators[compound - 1] {
+        Combinator::Child => matches_from(selector, compound - 1, ancestors),
+        Combinator::Descendant => {
+            (1..=ancestors.len()).any(|end| matches_from(selector, compound - 1, &ancestors[..end]))
+        }
+    }
+}
+
+fn matches_compound(compound: &CompoundSelector, position: &Position<'_>) -> bool {
+    let element = position.element;
+
+    if let Some(name) = &compound.name {
+        match element.name() {
+            Some(element_name) if element_name.eq_ignore_ascii_case(name) => {}
+            _ => return false,
+        }
+    }
+
+    let ids_match = compound
+        .ids
+        .iter()
+        .all(|id| attribute_text(element, "id").as_deref() == Some(id.as_str()));
+    let classes_match = compound.classes.iter().all(|class| {
+        attribute_text(element, "class")
+            .map(|classes| classes.split_whitespace().any(|c| c == class))
+            .unwrap_or(false)
+    });
+
+    ids_match
+        && classes_match
+        && compound
+            .attributes
+            .iter()
+            .all(|attribute| matches_attribute(attribute, element))
+        && compound
+            .pseudo_classes
+            .iter()
+            .all(|pseudo_class| matches_pseudo_class(*pseudo_class, position))
+}
+
+fn matches_attribute(selector: &AttributeSelector, element: &Node) -> bool {
+    let value = match attribute_text(element, &selector.key) {
+        Some(value) => value,
+        None => return false,
+    };
+
+    let (operator, expected) = match &selector.matcher {
+        Some(matcher) => matcher,
+        None => return true,
+    };
+
+    match operator {
+        AttributeOperator::Equals => value == expected.as_str(),
+        AttributeOperator::Includes => value.split_whitespace().any(|v| v == expected),
+        AttributeOperator::DashMatch => {
+            value == expected.as_str()
+                || (value.starts_with(expected.as_str())
+                    && value[expected.len()..].starts_with('-'))
+        }
+        AttributeOperator::Prefix => !expected.is_empty() && value.starts_with(expected.as_str()),
+        AttributeOperator::Suffix => !expected.is_empty() && value.ends_with(expected.as_str()),
+        AttributeOperator::Contains => !expected.is_empty() && value.contains(expected.as_str()),
+    }
+}
+
+fn matches_pseudo_class(pseudo_class: PseudoClass, position: &Position<'_>) -> bool {
+    let index = position.index as i64;
+
+    match pseudo_class {
+        PseudoClass::First => position.index == 1,
+        PseudoClass::Last => position.index == position.count,
+        PseudoClass::Nth { a: 0, b } => index == b,
+        PseudoClass::Nth { a, b } => {
+            let steps = index - b;
+            steps % a == 0 && steps / a >= 0
+        }
+    }
+}
+
+/// Returns the value of an attribute as it would be written out.
+fn attribute_text<'a>(element: &'a Node, key: &str) -> Option<Cow<'a, str>> {
+    Some(match element.attribute(key)? {
+        AttributeValue::ImplicitFalse => return None,
+        AttributeValue::ImplicitTrue => Cow::Borrowed(""),
+        AttributeValue::Text(text) => Cow::Borrowed(text),
+        AttributeValue::UnsignedInteger(n) => Cow::Owned(n.to_string()),
+        AttributeValue::SignedInteger(n) => Cow::Owned(n.to_string()),
+        AttributeValue::UnsignedInteger128(n) => Cow::Owned(n.to_string()),
+        AttributeValue::SignedInteger128(n) => Cow::Owned(n.to_string()),
+        AttributeValue::Float(n) => Cow::Owned(n.to_string()),
+        AttributeValue::FloatWithPrecision(n, precision) => {
+            Cow::Owned(format!("{:.*}", precision, n))
+        }
+        AttributeValue::Char(c) => Cow::Owned(c.to_string()),
+        AttributeValue::Translation(translation) => Cow::Borrowed(translation.key()),
+    })
+}
+
+#[cfg(test)]
+mod select {
+    use super::*;
+    use crate::parse::parse_html;
+
+    fn page() -> Node {
+        parse_html(concat!(
+            r#"<main id="content">"#,
+            r#"<ul class="menu"><li>a</li><li class="active item">b</li><li data-id="3">c</li></ul>"#,
+            r#"<section><p lang="en-GB">d</p><div><p>e</p></div></section>"#,
+            r#"<a href="https://example.com/page.html">f</a>"#,
+            r#"</main>"#,
+        ))
+        .unwrap()
+    }
+
+    fn texts(node: &Node, selectors: &str) -> Vec<String> {
+        node.select(selectors)
+            .iter()
+            .map(|node| node.text_content())
+            .collect()
+    }
+
+    #[test]
+    fn it_should_select_by_name_class_and_id() {
+        let page = page();
+
+        assert_eq!(vec!["a", "b", "c"], texts(&page, "li"));
+        assert_eq!(vec!["b"], texts(&page, "LI.active.item"));
+        assert_eq!(Vec::<String>::new(), texts(&page, ".active.missing"));
+        assert_eq!(1, page.select("#content").len());
+        assert_eq!(Some("main"), page.select_first("*").and_then(Node::name));
+    }
+
+    #[test]
+    fn it_should_select_by_attribute() {
+        let page = page();
+
+        assert_eq!(vec!["c"], texts(&page, "[data-id]"));
+        assert_eq!(vec!["c"], texts(&page, "li[data-id='3']"));
+        assert_eq!(vec!["b"], texts(&page, r#"[class~="item"]"#));
+        assert_eq!(vec!["d"], texts(&page, "[lang|=en]"));
+        assert_eq!(vec!["f"], texts(&page, "a[href^=https][href$='.html']"));
+        assert_eq!(vec!["f"], texts(&page, "a[href*=example]"));
+    }
+
+    #[test]
+    fn it_should_select_with_combinators() {
+        let page = page();
+
+        assert_eq!(vec!["d", "e"], texts(&page, "section p"));
+        assert_eq!(vec!["d"], texts(&page, "section > p"));
+        assert_eq!(vec!["e"], texts(&page, "main div > p"));
+        assert_eq!(vec!["b"], texts(&page, "main > ul > li.active"));
+        assert_eq!(Vec::<String>::new(), texts(&page, "main > li"));
+    }
+
+    #[test]
+    fn it_should_select_with_pseudo_classes() {
+        let page = page();
+
+        assert_eq!(vec!["a"], texts(&page, "li:first-child"));
+        assert_eq!(vec!["c"], texts(&page, "li:last-child"));
+        assert_eq!(vec!["b"], texts(&page, "li:nth-child(2)"));
+        assert_eq!(vec!["a", "c"], texts(&page, "li:nth-child(odd)"));
+        assert_eq!(vec!["a", "b"], texts(&page, "li:nth-child(-n+2)"));
+    }
+
+    #[test]
+    fn it_should_select_lists_in_document_order() {
+        assert_eq!(
+            vec!["a", "d", "f"],
+            texts(&page(), "a, li:first-child, [lang]")
+        );
+    }
+
+    #[test]
+    fn it_should_select_from_fragments_and_static_html() {
+        let node = Node::Fragment {
+            children: vec![
+                new_static!("<p>a</p><p><b>b</b></p>"),
+                Node::new_open("p", None, Some("c")),
+            ],
+        };
+
+        assert_eq!(vec!["a", "b", "c"], texts(&node, "p"));
+        assert_eq!(vec!["c"], texts(&node, "p:nth-child(3)"));
+        assert_eq!(vec!["b"], texts(&node, "p > b"));
+    }
+
+    #[test]
+    fn it_should_return_none_when_nothing_matches() {
+        assert!(page().select_first("table").is_none());
+        assert!(Node::new_text("a").select_first("*").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid selector 'ul >'")]
+    fn it_should_panic_on_invalid_selectors() {
+        page().select("ul >");
+    }
+}
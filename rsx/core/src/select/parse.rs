@@ -0,0 +1,440 @@
+use ::std::error::Error;
+use ::std::fmt;
+
+/// A selector which could not be parsed.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SelectorError {
+    /// The selector ended when more was expected, i.e. `ul >`.
+    UnexpectedEnd,
+
+    /// A character was found which isn't allowed at this point.
+    UnexpectedCharacter { character: char, position: usize },
+
+    /// A pseudo class was used which isn't supported, i.e. `:hover`.
+    UnsupportedPseudoClass { name: String },
+
+    /// The argument to `:nth-child` was not valid, i.e. `:nth-child(x)`.
+    InvalidNth { argument: String },
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "selector ended unexpectedly"),
+            Self::UnexpectedCharacter {
+                character,
+                position,
+            } => write!(f, "unexpected '{}' at byte {}", character, position),
+            Self::UnsupportedPseudoClass { name } => {
+                write!(f, "pseudo class :{} is not supported", name)
+            }
+            Self::InvalidNth { argument } => write!(f, "invalid :nth-child({})", argument),
+        }
+    }
+}
+
+impl Error for SelectorError {}
+
+pub type Result<N> = ::std::result::Result<N, SelectorError>;
+
+/// One selector, such as `ul > li.active`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ComplexSelector {
+    /// The compound selectors from left to right.
+    pub compounds: Vec<CompoundSelector>,
+
+    /// The combinator before each compound selector, after the first.
+    pub combinators: Vec<Combinator>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+}
+
+/// The parts which all apply to one element, such as `li.active:first-child`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CompoundSelector {
+    /// `None` matches any element.
+    pub name: Option<String>,
+    pub ids: Vec<String>,
+    pub classes: Vec<String>,
+    pub attributes: Vec<AttributeSelector>,
+    pub pseudo_classes: Vec<PseudoClass>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AttributeSelector {
+    pub key: String,
+    /// `None` is for `[key]`, which only checks the attribute is present.
+    pub matcher: Option<(AttributeOperator, String)>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AttributeOperator {
+    /// `[key=value]`
+    Equals,
+    /// `[key~=value]`, the value is one of a whitespace separated list.
+    Includes,
+    /// `[key|=value]`, the value, or the value followed by a `-`.
+    DashMatch,
+    /// `[key^=value]`
+    Prefix,
+    /// `[key$=value]`
+    Suffix,
+    /// `[key*=value]`
+    Contains,
+}
+
+/// Where the element is among its siblings.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PseudoClass {
+    /// `:first-child`
+    First,
+    /// `:last-child`
+    Last,
+    /// `:nth-child(an+b)`
+    Nth { a: i64, b: i64 },
+}
+
+/// Parses a comma separated list of selectors.
+pub fn parse_selector_list(selectors: &str) -> Result<Vec<ComplexSelector>> {
+    let mut parser = Parser {
+        input: selectors,
+        position: 0,
+    };
+    let mut list = Vec::new();
+
+    loop {
+        parser.skip_whitespace();
+        list.push(parser.parse_complex()?);
+        parser.skip_whitespace();
+
+        match parser.peek() {
+            None => return Ok(list),
+            Some(',') => parser.position += ",".len(),
+            Some(character) => return Err(parser.unexpected(character)),
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn unexpected(&self, character: char) -> SelectorError {
+        SelectorError::UnexpectedCharacter {
+            character,
+            position: self.position,
+        }
+    }
+
+    /// Returns true if any whitespace was skipped.
+    fn skip_whitespace(&mut self) -> bool {
+        let rest = &self.input[self.position..];
+        let skipped = rest.len() - rest.trim_start().len();
+        self.position += skipped;
+        skipped > 0
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(self.unexpected(c)),
+            None => Err(SelectorError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_complex(&mut self) -> Result<ComplexSelector> {
+        let mut compounds = vec![self.parse_compound()?];
+        let mut combinators = Vec::new();
+
+        loop {
+            let has_whitespace = self.skip_whitespace();
+
+            let combinator = match self.peek() {
+                None | Some(',') => break,
+                Some('>') => {
+                    self.position += ">".len();
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(_) if has_whitespace => Combinator::Descendant,
+                Some(character) => return Err(self.unexpected(character)),
+            };
+
+            combinators.push(combinator);
+            compounds.push(self.parse_compound()?);
+        }
+
+        Ok(ComplexSelector {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn parse_compound(&mut self) -> Result<CompoundSelector> {
+        let start = self.position;
+        let mut compound = CompoundSelector::default();
+
+        match self.peek() {
+            Some('*') => self.position += "*".len(),
+            Some(c) if is_identifier_char(c) => {
+                compound.name = Some(self.parse_identifier()?.to_ascii_lowercase());
+            }
+            _ => {}
+        }
+
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.position += ".".len();
+                    compound.classes.push(self.parse_identifier()?.to_string());
+                }
+                Some('#') => {
+                    self.position += "#".len();
+                    compound.ids.push(self.parse_identifier()?.to_string());
+                }
+                Some('[') => compound.attributes.push(self.parse_attribute()?),
+                Some(':') => compound.pseudo_classes.push(self.parse_pseudo_class()?),
+                _ => break,
+            }
+        }
+
+        if self.position == start {
+            return match self.peek() {
+                Some(character) => Err(self.unexpected(character)),
+                None => Err(SelectorError::UnexpectedEnd),
+            };
+        }
+
+        Ok(compound)
+    }
+
+    fn parse_identifier(&mut self) -> Result<&'a str> {
+        let rest = &self.input[self.position..];
+        let length = rest.find(|c| !is_identifier_char(c)).unwrap_or(rest.len());
+
+        if length == 0 {
+            return match self.peek() {
+                Some(character) => Err(self.unexpected(character)),
+                None => Err(SelectorError::UnexpectedEnd),
+            };
+        }
+
+        self.position += length;
+        Ok(&rest[..length])
+    }
+
+    fn parse_attribute(&mut self) -> Result<AttributeSelector> {
+        self.expect('[')?;
+        self.skip_whitespace();
+        let key = self.parse_identifier()?.to_ascii_lowercase();
+        self.skip_whitespace();
+
+        let operator = match self.peek() {
+            Some(']') => {
+                self.position += "]".len();
+                return Ok(AttributeSelector { key, matcher: None });
+            }
+            Some('=') => AttributeOperator::Equals,
+            Some('~') => AttributeOperator::Includes,
+            Some('|') => AttributeOperator::DashMatch,
+            Some('^') => AttributeOperator::Prefix,
+            Some('$') => AttributeOperator::Suffix,
+            Some('*') => AttributeOperator::Contains,
+            Some(character) => return Err(self.unexpected(character)),
+            None => return Err(SelectorError::UnexpectedEnd),
+        };
+        if operator != AttributeOperator::Equals {
+            self.position += 1;
+        }
+        self.expect('=')?;
+        self.skip_whitespace();
+
+        let value = match self.peek() {
+            Some(quote @ '"') | Some(quote @ '\'') => {
+                self.position += quote.len_utf8();
+                let rest = &self.input[self.position..];
+                let length = rest.find(quote).ok_or(SelectorError::UnexpectedEnd)?;
+                self.position += length + quote.len_utf8();
+                rest[..length].to_string()
+            }
+            _ => self.parse_identifier()?.to_string(),
+        };
+
+        self.skip_whitespace();
+        self.expect(']')?;
+
+        Ok(AttributeSelector {
+            key,
+            matcher: Some((operator, value)),
+        })
+    }
+
+    fn parse_pseudo_class(&mut self) -> Result<PseudoClass> {
+        self.expect(':')?;
+        let name = self.parse_identifier()?.to_ascii_lowercase();
+
+        match name.as_str() {
+            "first-child" => Ok(PseudoClass::First),
+            "last-child" => Ok(PseudoClass::Last),
+            "nth-child" => {
+                self.expect('(')?;
+                let rest = &self.input[self.position..];
+                let length = rest.find(')').ok_or(SelectorError::UnexpectedEnd)?;
+                self.position += length + ")".len();
+
+                let (a, b) = parse_nth(&rest[..length])?;
+                Ok(PseudoClass::Nth { a, b })
+            }
+            _ => Err(SelectorError::UnsupportedPseudoClass { name }),
+        }
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// Parses the `an+b` argument of `:nth-child`, i.e. `2n+1`, `odd`, or `3`.
+fn parse_nth(argument: &str) -> Result<(i64, i64)> {
+    let invalid = || SelectorError::InvalidNth {
+        argument: argument.to_string(),
+    };
+    let nth: String = argument
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    match nth.as_str() {
+        "odd" => return Ok((2, 1)),
+        "even" => return Ok((2, 0)),
+        _ => {}
+    }
+
+    match nth.find('n') {
+        None => {
+            let b = nth.parse().map_err(|_| invalid())?;
+            Ok((0, b))
+        }
+        Some(index) => {
+            let a = match &nth[..index] {
+                "" | "+" => 1,
+                "-" => -1,
+                a => a.parse().map_err(|_| invalid())?,
+            };
+            let b = match &nth[index + "n".len()..] {
+                "" => 0,
+                b if b.starts_with('+') || b.starts_with('-') => {
+                    b.parse().map_err(|_| invalid())?
+                }
+                _ => return Err(invalid()),
+            };
+
+            Ok((a, b))
+        }
+    }
+}
+
+#[cfg(test)]
+mod parse_selector_list {
+    use super::*;
+
+    fn parse_one(selector: &str) -> ComplexSelector {
+        let mut list = parse_selector_list(selector).unwrap();
+        assert_eq!(1, list.len());
+        list.remove(0)
+    }
+
+    #[test]
+    fn it_should_parse_compound_selectors() {
+        let selector = parse_one("LI.active.big#first[data-id][href^='https:']:first-child");
+        let compound = &selector.compounds[0];
+
+        assert_eq!(Some("li".to_string()), compound.name);
+        assert_eq!(vec!["active", "big"], compound.classes);
+        assert_eq!(vec!["first"], compound.ids);
+        assert_eq!(
+            vec![
+                AttributeSelector {
+                    key: "data-id".to_string(),
+                    matcher: None,
+                },
+                AttributeSelector {
+                    key: "href".to_string(),
+                    matcher: Some((AttributeOperator::Prefix, "https:".to_string())),
+                },
+            ],
+            compound.attributes
+        );
+        assert_eq!(vec![PseudoClass::First], compound.pseudo_classes);
+    }
+
+    #[test]
+    fn it_should_parse_combinators() {
+        let selector = parse_one("main  ul>li a");
+
+        assert_eq!(4, selector.compounds.len());
+        assert_eq!(
+            vec![
+                Combinator::Descendant,
+                Combinator::Child,
+                Combinator::Descendant
+            ],
+            selector.combinators
+        );
+    }
+
+    #[test]
+    fn it_should_parse_lists() {
+        assert_eq!(3, parse_selector_list("h1, h2 ,h3").unwrap().len());
+    }
+
+    #[test]
+    fn it_should_parse_nth_arguments() {
+        assert_eq!(Ok((2, 1)), parse_nth("odd"));
+        assert_eq!(Ok((2, 0)), parse_nth("even"));
+        assert_eq!(Ok((0, 3)), parse_nth("3"));
+        assert_eq!(Ok((1, 0)), parse_nth("n"));
+        assert_eq!(Ok((-1, 3)), parse_nth("-n+3"));
+        assert_eq!(Ok((3, -2)), parse_nth(" 3n - 2 "));
+        assert!(parse_nth("x").is_err());
+        assert!(parse_nth("2n1").is_err());
+    }
+
+    #[test]
+    fn it_should_error_on_invalid_selectors() {
+        assert_eq!(
+            Err(SelectorError::UnexpectedEnd),
+            parse_selector_list("ul >")
+        );
+        assert_eq!(Err(SelectorError::UnexpectedEnd), parse_selector_list(""));
+        assert_eq!(
+            Err(SelectorError::UnexpectedCharacter {
+                character: '!',
+                position: 2,
+            }),
+            parse_selector_list("ul!")
+        );
+        assert_eq!(
+            Err(SelectorError::UnsupportedPseudoClass {
+                name: "hover".to_string(),
+            }),
+            parse_selector_list("a:hover")
+        );
+    }
+}
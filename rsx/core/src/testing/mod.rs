@@ -0,0 +1,212 @@
+//! Helpers for testing components.
+//!
+//! ```
+//! use ::core::dom::Node;
+//! use ::core::parse::parse_html;
+//! use ::core::testing::Rendered;
+//!
+//! let page = Rendered::new(parse_html(r#"<form><button type="submit">Save</button></form>"#).unwrap());
+//!
+//! let button = page.find_by_role("button").unwrap();
+//! assert_eq!(button.text_content(), "Save");
+//! assert_eq!(page.pretty(), "<form>\n  <button type=\"submit\">Save</button>\n</form>\n");
+//! ```
+
+use crate::dom::AttributeValue;
+use crate::dom::Node;
+use crate::dom::ToNode;
+use crate::render::render;
+use crate::Component;
+use ::std::iter;
+
+mod pretty;
+pub use self::pretty::*;
+
+mod role;
+pub use self::role::*;
+
+mod snapshot;
+pub use self::snapshot::*;
+
+/// The attribute used by `find_by_test_id`.
+pub const TEST_ID_ATTRIBUTE: &str = "data-testid";
+
+/// Calls a component with the props given.
+/// Components returning a `Result` can be tested too, with an `Err` held as a `Node::Error`.
+pub fn render_component<C, N>(component: C, props: C::Props) -> Rendered
+where
+    C: Component + FnOnce(C::Props) -> N,
+    N: ToNode,
+{
+    Rendered::new(component(props).to_node())
+}
+
+/// The output of a component, with ways to look into it.
+#[derive(Clone, Debug)]
+pub struct Rendered {
+    node: Node,
+}
+
+impl Rendered {
+    pub fn new(node: Node) -> Self {
+        Self { node }
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    pub fn into_node(self) -> Node {
+        self.node
+    }
+
+    /// The HTML, exactly as it would be rendered.
+    ///
+    /// This panics if a component returned an error which no `ErrorBoundary` caught.
+    pub fn html(&self) -> String {
+        render(&self.node).expect("rendering should not fail")
+    }
+
+    /// The HTML, normalised for comparing in tests. See `pretty`.
+    pub fn pretty(&self) -> String {
+        pretty(&self.node)
+    }
+
+    /// See `assert_snapshot`.
+    pub fn assert_snapshot(&self, name: &str) {
+        assert_snapshot(name, &self.node)
+    }
+
+    /// Returns the innermost elements whose text is the text given.
+    /// Whitespace is ignored at the ends, and between words.
+    pub fn find_all_by_text(&self, text: &str) -> Vec<&Node> {
+        let text = normalise_whitespace(text);
+        let has_text = |element: &Node| normalise_whitespace(&element.text_content()) == text;
+
+        self.elements()
+            .filter(|element| has_text(element))
+            .filter(|element| {
+                !element
+                    .children()
+                    .any(|child| child.is_element() && has_text(child))
+            })
+            .collect()
+    }
+
+    pub fn find_by_text(&self, text: &str) -> Option<&Node> {
+        self.find_all_by_text(text).into_iter().next()
+    }
+
+    /// Returns the elements with the ARIA role given. See `role`.
+    pub fn find_all_by_role(&self, role_name: &str) -> Vec<&Node> {
+        self.elements()
+            .filter(|element| role(element) == Some(role_name))
+            .collect()
+    }
+
+    pub fn find_by_role(&self, role_name: &str) -> Option<&Node> {
+        self.elements()
+            .find(|element| role(element) == Some(role_name))
+    }
+
+    /// Returns the element with a `data-testid` attribute of the id given.
+    pub fn find_by_test_id(&self, test_id: &str) -> Option<&Node> {
+        self.elements().find(|element| {
+            matches!(
+                element.attribute(TEST_ID_ATTRIBUTE),
+                Some(AttributeValue::Text(id)) if id == test_id
+            )
+        })
+    }
+
+    /// Every element, including the node itself.
+    fn elements(&self) -> impl Iterator<Item = &Node> {
+        iter::once(self.node.resolve())
+            .chain(self.node.descendants())
+            .filter(|node| node.is_element())
+    }
+}
+
+impl From<Node> for Rendered {
+    fn from(node: Node) -> Self {
+        Self::new(node)
+    }
+}
+
+impl AsRef<Node> for Rendered {
+    fn as_ref(&self) -> &Node {
+        &self.node
+    }
+}
+
+fn normalise_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod rendered {
+    use super::*;
+    use crate::parse::parse_html;
+
+    fn page() -> Rendered {
+        Rendered::new(
+            parse_html(concat!(
+                r#"<nav><a href="/">Home</a><a href="/about" data-testid="about">About  us</a></nav>"#,
+                r#"<main><h1>Welcome <b>home</b></h1><button>Save</button><div role="button">Cancel</div></main>"#,
+            ))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn it_should_find_by_text() {
+        let page = page();
+
+        assert_eq!(
+            Some("a"),
+            page.find_by_text(" About us ").and_then(Node::name)
+        );
+        assert_eq!(
+            Some("h1"),
+            page.find_by_text("Welcome home").and_then(Node::name)
+        );
+        assert_eq!(Some("b"), page.find_by_text("home").and_then(Node::name));
+        assert!(page.find_by_text("Missing").is_none());
+    }
+
+    #[test]
+    fn it_should_find_by_role() {
+        let page = page();
+        let buttons: Vec<String> = page
+            .find_all_by_role("button")
+            .into_iter()
+            .map(Node::text_content)
+            .collect();
+
+        assert_eq!(vec!["Save", "Cancel"], buttons);
+        assert_eq!(2, page.find_all_by_role("link").len());
+        assert_eq!(
+            Some("nav"),
+            page.find_by_role("navigation").and_then(Node::name)
+        );
+    }
+
+    #[test]
+    fn it_should_find_by_test_id() {
+        let page = page();
+
+        assert_eq!(
+            Some("a"),
+            page.find_by_test_id("about").and_then(Node::name)
+        );
+        assert!(page.find_by_test_id("home").is_none());
+    }
+
+    #[test]
+    fn it_should_find_the_root_element() {
+        let page = Rendered::new(new_static!("<button>a</button>"));
+
+        assert!(page.find_by_role("button").is_some());
+        assert!(page.find_by_text("a").is_some());
+    }
+}
@@ -0,0 +1,222 @@
+use crate::dom::Attribute;
+use crate::dom::AttributeValue;
+use crate::dom::Node;
+use crate::render::render;
+use crate::render::write_escaped_attribute;
+use crate::render::TextMode;
+use ::std::fmt::Write;
+
+const INDENT: &str = "  ";
+
+/// Renders a node with one element per line, and attributes sorted by key.
+///
+/// This is for comparing output in tests, where differences in whitespace,
+/// or in the order attributes were written, should not matter.
+pub fn pretty(node: &Node) -> String {
+    let mut buffer = String::new();
+    write_node(&mut buffer, node, 0, TextMode::Normal);
+    buffer
+}
+
+fn write_node(buffer: &mut String, node: &Node, depth: usize, text_mode: TextMode) {
+    match node.resolve() {
+        Node::Empty
+        | Node::Static { .. }
+        | Node::Slot { .. }
+        | Node::SlotContent { .. }
+        | Node::Error { .. } => {}
+        Node::Fragment { .. } | Node::ErrorBoundary { .. } => {
+            write_children(buffer, node, depth, text_mode)
+        }
+        Node::Translation { translation } => {
+            write_indent(buffer, depth);
+            text_mode.write_text(buffer, translation.key());
+            buffer.push('\n');
+        }
+        Node::Text { contents } => {
+            let text = normalise_text(contents, text_mode);
+            if !text.is_empty() {
+                write_indent(buffer, depth);
+                text_mode.write_text(buffer, &text);
+                buffer.push('\n');
+            }
+        }
+        node @ Node::Doctype { .. }
+        | node @ Node::ProcessingInstruction { .. }
+        | node @ Node::CData { .. }
+        | node @ Node::Comment { .. } => {
+            write_indent(buffer, depth);
+            buffer.push_str(&render(node).unwrap_or_default());
+            buffer.push('\n');
+        }
+        element => write_element(buffer, element, depth),
+    }
+}
+
+fn write_element(buffer: &mut String, element: &Node, depth: usize) {
+    let name = element.name().unwrap_or_default();
+    let text_mode = TextMode::for_element(name);
+
+    write_indent(buffer, depth);
+    write_open_tag(buffer, name, element.attributes());
+
+    if let Node::SelfClosing { .. } = element {
+        buffer.push_str("/>\n");
+        return;
+    }
+    buffer.push('>');
+
+    let children: Vec<&Node> = element
+        .children()
+        .filter(|child| match child.text() {
+            Some(text) => !normalise_text(text, text_mode).is_empty(),
+            None => true,
+        })
+        .collect();
+
+    match children.as_slice() {
+        [] => {}
+        [child] if child.text().is_some() => {
+            let text = normalise_text(child.text().unwrap_or_default(), text_mode);
+            text_mode.write_text(buffer, &text);
+        }
+        _ => {
+            buffer.push('\n');
+            for child in children {
+                write_node(buffer, child, depth + 1, text_mode);
+            }
+            write_indent(buffer, depth);
+        }
+    }
+
+    let _ = writeln!(buffer, "</{}>", name);
+}
+
+fn write_children(buffer: &mut String, node: &Node, depth: usize, text_mode: TextMode) {
+    for child in node.children() {
+        write_node(buffer, child, depth, text_mode);
+    }
+}
+
+fn write_open_tag(buffer: &mut String, name: &str, attributes: &[Attribute]) {
+    let mut attributes: Vec<&Attribute> = attributes
+        .iter()
+        .filter(|attribute| !matches!(attribute.value, AttributeValue::ImplicitFalse))
+        .collect();
+    attributes.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let _ = write!(buffer, "<{}", name);
+    for attribute in attributes {
+        let _ = match &attribute.value {
+            AttributeValue::ImplicitFalse => Ok(()),
+            AttributeValue::ImplicitTrue => write!(buffer, " {}", attribute.key),
+            AttributeValue::Text(text) => {
+                let _ = write!(buffer, " {}=\"", attribute.key);
+                write_escaped_attribute(buffer, text);
+                write!(buffer, "\"")
+            }
+            AttributeValue::Translation(translation) => {
+                let _ = write!(buffer, " {}=\"", attribute.key);
+                write_escaped_attribute(buffer, translation.key());
+                write!(buffer, "\"")
+            }
+            AttributeValue::UnsignedInteger(n) => write!(buffer, " {}=\"{}\"", attribute.key, n),
+            AttributeValue::SignedInteger(n) => write!(buffer, " {}=\"{}\"", attribute.key, n),
+            AttributeValue::UnsignedInteger128(n) => {
+                write!(buffer, " {}=\"{}\"", attribute.key, n)
+            }
+            AttributeValue::SignedInteger128(n) => write!(buffer, " {}=\"{}\"", attribute.key, n),
+            AttributeValue::Float(n) => write!(buffer, " {}=\"{}\"", attribute.key, n),
+            AttributeValue::FloatWithPrecision(n, precision) => {
+                write!(buffer, " {}=\"{:.*}\"", attribute.key, precision, n)
+            }
+            AttributeValue::Char(c) => {
+                let _ = write!(buffer, " {}=\"", attribute.key);
+                write_escaped_attribute(buffer, c.encode_utf8(&mut [0; 4]));
+                write!(buffer, "\"")
+            }
+        };
+    }
+}
+
+fn write_indent(buffer: &mut String, depth: usize) {
+    for _ in 0..depth {
+        buffer.push_str(INDENT);
+    }
+}
+
+/// Runs of whitespace are joined into a single space, as the browser would show them.
+/// Scripts and styles are only trimmed.
+fn normalise_text(text: &str, text_mode: TextMode) -> String {
+    match text_mode {
+        TextMode::Normal => text.split_whitespace().collect::<Vec<_>>().join(" "),
+        _ => text.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod pretty {
+    use super::*;
+    use crate::parse::parse_html;
+
+    fn pretty_html(html: &str) -> String {
+        pretty(&parse_html(html).unwrap())
+    }
+
+    #[test]
+    fn it_should_put_elements_on_their_own_lines() {
+        assert_eq!(
+            concat!(
+                "<ul>\n",
+                "  <li>a</li>\n",
+                "  <li>\n",
+                "    b\n",
+                "    <br/>\n",
+                "    c\n",
+                "  </li>\n",
+                "  <li></li>\n",
+                "</ul>\n",
+            ),
+            pretty_html("<ul>\n  <li> a </li><li>b<br>c</li><li>  </li>\n</ul>")
+        );
+    }
+
+    #[test]
+    fn it_should_sort_attributes() {
+        assert_eq!(
+            "<input class=\"a\" disabled id=\"b\" type=\"text\"/>\n",
+            pretty_html(r#"<input type="text" id="b" disabled class="a"/>"#)
+        );
+    }
+
+    #[test]
+    fn it_should_give_the_same_output_regardless_of_formatting() {
+        assert_eq!(
+            pretty_html(r#"<div id="a" class="b"><p>Hello   world</p></div>"#),
+            pretty_html("<div class=\"b\" id=\"a\">\n  <p>\n    Hello\n    world\n  </p>\n</div>")
+        );
+    }
+
+    #[test]
+    fn it_should_write_other_nodes_on_their_own_lines() {
+        assert_eq!(
+            concat!(
+                "<!doctype html>\n",
+                "<!-- note -->\n",
+                "<script>if (a < b) {}</script>\n",
+                "<p>a &amp; b</p>\n",
+            ),
+            pretty_html(
+                "<!doctype html><!-- note --><script>\n  if (a < b) {}\n</script><p>a &amp; b</p>"
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_look_inside_static_html() {
+        assert_eq!(
+            "<p>\n  <b>a</b>\n</p>\n",
+            pretty(&new_static!("<p><b>a</b></p>"))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::dom::AttributeValue;
+use crate::dom::Node;
+
+/// Returns the ARIA role of an element.
+///
+/// This is the `role` attribute if it is set,
+/// and otherwise the role implied by the element, i.e. `button` for `<button>`.
+///
+/// See https://www.w3.org/TR/html-aria/#docconformance
+pub fn role(element: &Node) -> Option<&str> {
+    if let Some(AttributeValue::Text(role)) = element.attribute("role") {
+        return role.split_whitespace().next();
+    }
+
+    let name = element.name()?.to_ascii_lowercase();
+    let role = match name.as_str() {
+        "a" | "area" if element.has_attribute("href") => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" => "img",
+        "input" => return input_role(element),
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" | "menu" => "list",
+        "option" => "option",
+        "progress" => "progressbar",
+        "section" => "region",
+        "select" => "combobox",
+        "table" => "table",
+        "tbody" | "thead" | "tfoot" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+
+    Some(role)
+}
+
+fn input_role(input: &Node) -> Option<&'static str> {
+    let input_type = match input.attribute("type") {
+        Some(AttributeValue::Text(input_type)) => input_type.to_ascii_lowercase(),
+        _ => "text".to_string(),
+    };
+
+    let role = match input_type.as_str() {
+        "button" | "image" | "reset" | "submit" => "button",
+        "checkbox" => "checkbox",
+        "number" => "spinbutton",
+        "radio" => "radio",
+        "range" => "slider",
+        "search" => "searchbox",
+        "email" | "tel" | "text" | "url" => "textbox",
+        _ => return None,
+    };
+
+    Some(role)
+}
+
+#[cfg(test)]
+mod role {
+    use super::*;
+    use crate::parse::parse_html;
+
+    fn role_of(html: &str) -> Option<String> {
+        role(&parse_html(html).unwrap()).map(str::to_string)
+    }
+
+    #[test]
+    fn it_should_use_the_role_attribute() {
+        assert_eq!(
+            Some("tab".to_string()),
+            role_of(r#"<div role="tab button">a</div>"#)
+        );
+    }
+
+    #[test]
+    fn it_should_imply_roles_from_elements() {
+        assert_eq!(Some("button".to_string()), role_of("<button>a</button>"));
+        assert_eq!(Some("link".to_string()), role_of(r#"<a href="/">a</a>"#));
+        assert_eq!(None, role_of("<a>a</a>"));
+        assert_eq!(Some("heading".to_string()), role_of("<H2>a</H2>"));
+        assert_eq!(None, role_of("<div>a</div>"));
+    }
+
+    #[test]
+    fn it_should_imply_roles_from_input_types() {
+        assert_eq!(Some("textbox".to_string()), role_of("<input>"));
+        assert_eq!(
+            Some("button".to_string()),
+            role_of(r#"<input type="submit">"#)
+        );
+        assert_eq!(
+            Some("checkbox".to_string()),
+            role_of(r#"<input type="Checkbox">"#)
+        );
+        assert_eq!(None, role_of(r#"<input type="hidden">"#));
+    }
+}
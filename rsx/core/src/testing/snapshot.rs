@@ -0,0 +1,197 @@
+use crate::dom::Node;
+use crate::testing::pretty;
+use ::std::env;
+use ::std::fs;
+use ::std::io::ErrorKind;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+
+/// Set this environment variable to write new snapshots over the old ones.
+pub const UPDATE_SNAPSHOTS: &str = "RENDERX_UPDATE_SNAPSHOTS";
+
+const SNAPSHOTS_DIR: &str = "tests/snapshots";
+
+/// Compares the node, pretty printed, against `tests/snapshots/<name>.html`.
+///
+/// If the snapshot doesn't exist yet, it is written and the assertion passes,
+/// unless the `CI` environment variable is set, so a missing snapshot fails on CI.
+/// Run with `RENDERX_UPDATE_SNAPSHOTS=1` to write missing snapshots,
+/// and replace those which no longer match.
+///
+/// # Panics
+///
+/// If the output is different to the snapshot, if it's missing on CI,
+/// or if it cannot be read or written.
+pub fn assert_snapshot<N>(name: &str, node: N)
+where
+    N: AsRef<Node>,
+{
+    let dir = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => PathBuf::from(manifest_dir).join(SNAPSHOTS_DIR),
+        None => PathBuf::from(SNAPSHOTS_DIR),
+    };
+
+    let update = is_env_set(UPDATE_SNAPSHOTS);
+    let is_ci = is_env_set("CI");
+
+    assert_snapshot_in(&dir, name, &pretty(node.as_ref()), update, is_ci);
+}
+
+fn is_env_set(key: &str) -> bool {
+    match env::var(key) {
+        Ok(value) => !matches!(value.as_str(), "" | "0" | "false"),
+        Err(_) => false,
+    }
+}
+
+fn assert_snapshot_in(dir: &Path, name: &str, actual: &str, update: bool, is_ci: bool) {
+    let path = dir.join(format!("{}.html", name));
+
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {}
+        Ok(_) if update => write_snapshot(&path, actual),
+        Ok(expected) => panic!(
+            "snapshot {} does not match, run with {}=1 to update it\n\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS,
+            diff_lines(&expected, actual)
+        ),
+        Err(err) if err.kind() == ErrorKind::NotFound && (update || !is_ci) => {
+            write_snapshot(&path, actual)
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => panic!(
+            "snapshot {} is missing, run with {}=1 to write it",
+            path.display(),
+            UPDATE_SNAPSHOTS
+        ),
+        Err(err) => panic!("cannot read snapshot {}, {}", path.display(), err),
+    }
+}
+
+fn write_snapshot(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|err| panic!("cannot create {}, {}", parent.display(), err));
+    }
+
+    fs::write(path, contents)
+        .unwrap_or_else(|err| panic!("cannot write snapshot {}, {}", path.display(), err));
+}
+
+/// Lists every line, marking those only in the snapshot with `-`, and only in the output with `+`.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // The length of the longest common subsequence, from each pair of positions to the end.
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            diff.push_str(&format!("  {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < actual.len() && (i == expected.len() || common[i][j + 1] > common[i + 1][j]) {
+            diff.push_str(&format!("+ {}\n", actual[j]));
+            j += 1;
+        } else {
+            diff.push_str(&format!("- {}\n", expected[i]));
+            i += 1;
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod assert_snapshot {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            env::temp_dir().join(format!("renderx-snapshots-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn it_should_write_missing_snapshots() {
+        let dir = temp_dir("missing");
+        assert_snapshot_in(&dir, "nested/page", "<p>a</p>\n", false, false);
+
+        assert_eq!(
+            "<p>a</p>\n",
+            fs::read_to_string(dir.join("nested/page.html")).unwrap()
+        );
+        assert_snapshot_in(&dir, "nested/page", "<p>a</p>\n", false, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing")]
+    fn it_should_panic_when_the_snapshot_is_missing_on_ci() {
+        let dir = temp_dir("missing-ci");
+        assert_snapshot_in(&dir, "page", "<p>a</p>\n", false, true);
+    }
+
+    #[test]
+    fn it_should_write_missing_snapshots_on_ci_when_asked() {
+        let dir = temp_dir("missing-ci-update");
+        assert_snapshot_in(&dir, "page", "<p>a</p>\n", true, true);
+
+        assert_eq!(
+            "<p>a</p>\n",
+            fs::read_to_string(dir.join("page.html")).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot read snapshot")]
+    fn it_should_panic_when_the_snapshot_cannot_be_read() {
+        let dir = temp_dir("unreadable");
+        fs::create_dir_all(dir.join("page.html")).unwrap();
+
+        assert_snapshot_in(&dir, "page", "<p>a</p>\n", true, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn it_should_panic_when_the_snapshot_is_different() {
+        let dir = temp_dir("different");
+        assert_snapshot_in(&dir, "page", "<p>a</p>\n", false, false);
+        assert_snapshot_in(&dir, "page", "<p>b</p>\n", false, false);
+    }
+
+    #[test]
+    fn it_should_update_snapshots_when_asked() {
+        let dir = temp_dir("update");
+        assert_snapshot_in(&dir, "page", "<p>a</p>\n", false, false);
+        assert_snapshot_in(&dir, "page", "<p>b</p>\n", true, false);
+
+        assert_eq!(
+            "<p>b</p>\n",
+            fs::read_to_string(dir.join("page.html")).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_diff_lines() {
+        assert_eq!(
+            "  <ul>\n-   <li>a</li>\n+   <li>b</li>\n    <li>c</li>\n+   <li>d</li>\n  </ul>\n",
+            diff_lines(
+                "<ul>\n  <li>a</li>\n  <li>c</li>\n</ul>\n",
+                "<ul>\n  <li>b</li>\n  <li>c</li>\n  <li>d</li>\n</ul>\n"
+            )
+        );
+    }
+}
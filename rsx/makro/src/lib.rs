@@ -1,6 +1,8 @@
+use ::parser::attribute_value;
 use ::parser::component;
 use ::parser::rsx;
 use ::proc_macro::TokenStream;
+use ::quote::quote_spanned;
 
 #[proc_macro]
 pub fn rsx(stream: TokenStream) -> TokenStream {
@@ -18,6 +20,16 @@ pub fn component(attr: TokenStream, stream: TokenStream) -> TokenStream {
     }
 }
 
+/// Writes each variant of an enum as its name in kebab-case, i.e. `DatetimeLocal` as `datetime-local`.
+/// Use `#[attribute_value(rename = "...")]` on a variant to write something else.
+#[proc_macro_derive(ToAttributeValue, attributes(attribute_value))]
+pub fn to_attribute_value(stream: TokenStream) -> TokenStream {
+    match attribute_value::parse(stream.into()) {
+        Err(err) => display_attribute_value_error(err),
+        Ok(code) => code.into(),
+    }
+}
+
 fn display_rsx_error(err: rsx::Error) -> TokenStream {
     match err {
         rsx::Error::MismatchedClosingTagCode => {
@@ -43,6 +55,39 @@ fn display_rsx_error(err: rsx::Error) -> TokenStream {
         rsx::Error::ChompOnEmptyNode => {
             panic!("Internal error; chomped on an empty node (this should never be visible)")
         }
+        rsx::Error::ExpectedCratePath => panic!("Expected a path after `crate =`"),
+        rsx::Error::ExpectedCratePathSemicolon => {
+            panic!(
+                "Expected a `;` after the crate path; i.e. `rsx!(crate = ::my_fw::html; <div/>)`"
+            )
+        }
+        rsx::Error::UnknownEntity(err) => {
+            let message = format!(
+                "Unknown HTML entity `{}`, use `&amp;` for a literal `&`",
+                err.entity
+            );
+            quote_spanned!(err.span=> compile_error!(#message)).into()
+        }
+        rsx::Error::UnknownAttributeNamespace(key) => panic!(
+            "Unknown namespace on the attribute `{}`, only `xlink:`, `xml:`, and `xmlns:` can be used within <svg> and <math>",
+            key
+        ),
+        rsx::Error::SlotAttributesFound => {
+            panic!("Slots don't take attributes; i.e. `<slot:head/>` or `<slot:head>...</slot:head>`")
+        }
+        rsx::Error::ErrorBoundaryFallbackMissing => panic!(
+            "{}",
+            "ErrorBoundary needs a fallback; i.e. `<ErrorBoundary fallback={|err| rsx! { <p>Sorry</p> }}>`"
+        ),
+        rsx::Error::ErrorBoundaryAttributeFound(key) => panic!(
+            "Unknown attribute `{}` on ErrorBoundary, only `fallback={{...}}` and a lone `catch_panics` can be used",
+            key
+        ),
+        rsx::Error::TranslationKeysError(err) => panic!(
+            "Failed writing to the `{}` file; {}",
+            rsx::TRANSLATION_KEYS_ENV,
+            err
+        ),
         rsx::Error::FmtError(fmt) => panic!(
             "Internal error; failed writing to string (this should never be visible), {}",
             fmt
@@ -53,7 +98,7 @@ fn display_rsx_error(err: rsx::Error) -> TokenStream {
 fn display_component_error(err: component::Error) -> TokenStream {
     match err {
         component::Error::AttributeFound => panic!(
-            "Component macro does not support any attributes; use `#[component]` on it's own."
+            "Component macro only supports the crate path attribute; i.e. `#[component(crate = \"::my_fw::html\")]`."
         ),
         component::Error::EmptyMacroStreamGiven => panic!("Empty rsx given"),
         component::Error::NoReturnType => panic!("Component is missing return type (i.e. `Node`)"),
@@ -64,3 +109,19 @@ fn display_component_error(err: component::Error) -> TokenStream {
         component::Error::SynError(err) => panic!("Error parsing component, {}", err),
     }
 }
+
+fn display_attribute_value_error(err: attribute_value::Error) -> TokenStream {
+    match err {
+        attribute_value::Error::NotAnEnum => {
+            panic!("ToAttributeValue can only be derived for enums")
+        }
+        attribute_value::Error::VariantFieldsFound(variant) => panic!(
+            "ToAttributeValue can only be derived for variants without fields, found fields on `{}`",
+            variant
+        ),
+        attribute_value::Error::UnknownAttributeFound => panic!(
+            "Unknown attribute; use `#[attribute_value(rename = \"...\")]` on a variant, or `#[attribute_value(crate = \"...\")]` on the enum"
+        ),
+        attribute_value::Error::SynError(err) => panic!("Error parsing enum, {}", err),
+    }
+}
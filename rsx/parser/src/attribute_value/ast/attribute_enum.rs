@@ -0,0 +1,18 @@
+use ::proc_macro2::TokenStream;
+use ::syn::Generics;
+use ::syn::Ident;
+
+#[derive(Clone, Debug)]
+pub struct AttributeEnum {
+    pub name: Ident,
+    pub generics: Generics,
+    pub crate_path: Option<TokenStream>,
+    pub variants: Vec<AttributeVariant>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AttributeVariant {
+    pub name: Ident,
+    /// What is written in the attribute, i.e. `datetime-local` for `DatetimeLocal`.
+    pub value: String,
+}
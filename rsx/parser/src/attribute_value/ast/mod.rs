@@ -0,0 +1,2 @@
+mod attribute_enum;
+pub use attribute_enum::*;
@@ -0,0 +1,20 @@
+use ::std::convert::From;
+
+pub type Result<N> = ::std::result::Result<N, Error>;
+
+#[derive(Clone, Debug)]
+pub enum Error {
+    /// Only enums can be derived.
+    NotAnEnum,
+    /// Only variants without fields can be derived, the name of the variant is given.
+    VariantFieldsFound(String),
+    /// An `#[attribute_value(...)]` which isn't `crate = "..."` or `rename = "..."`.
+    UnknownAttributeFound,
+    SynError(syn::parse::Error),
+}
+
+impl From<syn::parse::Error> for Error {
+    fn from(err: syn::parse::Error) -> Self {
+        Error::SynError(err)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::attribute_value::ast::AttributeEnum;
+use crate::attribute_value::ast::AttributeVariant;
+use crate::attribute_value::error::Error;
+use crate::attribute_value::error::Result;
+
+use ::proc_macro2::TokenStream;
+use ::quote::ToTokens;
+
+use ::syn::parse2;
+use ::syn::Attribute;
+use ::syn::Data;
+use ::syn::DeriveInput;
+use ::syn::Fields;
+use ::syn::Ident;
+use ::syn::LitStr;
+use ::syn::Path;
+use ::syn::Token;
+
+use ::syn::parse::ParseStream;
+
+/// The name of the attribute used to configure the derive.
+const ATTRIBUTE_NAME: &str = "attribute_value";
+
+/// A setting given with `#[attribute_value(...)]`.
+enum Setting {
+    /// i.e. `#[attribute_value(crate = "::my_fw::html")]` on the enum.
+    CratePath(LitStr),
+    /// i.e. `#[attribute_value(rename = "email")]` on a variant.
+    Rename(LitStr),
+}
+
+pub fn parse(stream: TokenStream) -> Result<AttributeEnum> {
+    let input = parse2::<DeriveInput>(stream)?;
+
+    let data = match input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::NotAnEnum),
+    };
+
+    let crate_path = match parse_settings(&input.attrs)?.as_slice() {
+        [] => None,
+        [Setting::CratePath(path)] => Some(path.parse::<Path>()?.into_token_stream()),
+        _ => return Err(Error::UnknownAttributeFound),
+    };
+
+    let variants = data
+        .variants
+        .into_iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::VariantFieldsFound(variant.ident.to_string()));
+            }
+
+            let value = match parse_settings(&variant.attrs)?.as_slice() {
+                [] => to_kebab_case(&variant.ident),
+                [Setting::Rename(value)] => value.value(),
+                _ => return Err(Error::UnknownAttributeFound),
+            };
+
+            Ok(AttributeVariant {
+                name: variant.ident,
+                value,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(AttributeEnum {
+        name: input.ident,
+        generics: input.generics,
+        crate_path,
+        variants,
+    })
+}
+
+fn parse_settings(attributes: &[Attribute]) -> Result<Vec<Setting>> {
+    attributes
+        .iter()
+        .filter(|attribute| attribute.path.is_ident(ATTRIBUTE_NAME))
+        .map(|attribute| {
+            attribute
+                .parse_args_with(parse_setting)
+                .map_err(|_| Error::UnknownAttributeFound)
+        })
+        .collect()
+}
+
+fn parse_setting(input: ParseStream) -> syn::Result<Setting> {
+    if input.peek(Token![crate]) {
+        input.parse::<Token![crate]>()?;
+        input.parse::<Token![=]>()?;
+        return Ok(Setting::CratePath(input.parse()?));
+    }
+
+    let name = input.parse::<Ident>()?;
+    if name != "rename" {
+        return Err(input.error("expected `crate` or `rename`"));
+    }
+    input.parse::<Token![=]>()?;
+    Ok(Setting::Rename(input.parse()?))
+}
+
+/// i.e. `DatetimeLocal` becomes `datetime-local`, and `HTMLInput` becomes `html-input`.
+fn to_kebab_case(name: &Ident) -> String {
+    let name = name.to_string();
+    let chars: Vec<char> = name.trim_start_matches("r#").chars().collect();
+    let mut kebab = String::with_capacity(name.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '_' {
+            kebab.push('-');
+            continue;
+        }
+
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let is_word_start = previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase()
+                    && chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+
+            if is_word_start {
+                kebab.push('-');
+            }
+        }
+
+        kebab.extend(c.to_lowercase());
+    }
+
+    kebab
+}
+
+#[cfg(test)]
+mod to_kebab_case {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+    use ::quote::format_ident;
+
+    fn kebab(name: &str) -> String {
+        to_kebab_case(&format_ident!("{}", name))
+    }
+
+    #[test]
+    fn it_should_split_words() {
+        assert_eq!("email", kebab("Email"));
+        assert_eq!("datetime-local", kebab("DatetimeLocal"));
+        assert_eq!(
+            "no-referrer-when-downgrade",
+            kebab("NoReferrerWhenDowngrade")
+        );
+    }
+
+    #[test]
+    fn it_should_keep_acronyms_together() {
+        assert_eq!("html-input", kebab("HTMLInput"));
+        assert_eq!("url", kebab("URL"));
+        assert_eq!("h2-title", kebab("H2Title"));
+    }
+}
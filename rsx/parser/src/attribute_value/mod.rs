@@ -0,0 +1,105 @@
+mod ast;
+mod error;
+mod grammar;
+mod output;
+
+pub use self::error::*;
+
+use crate::util::default_crate_path;
+use ::proc_macro2::TokenStream;
+
+pub fn parse(stream: TokenStream) -> Result<TokenStream> {
+    let mut ast = grammar::parse(stream)?;
+    let crate_path = ast.crate_path.take().unwrap_or_else(default_crate_path);
+
+    Ok(output::build(ast, &crate_path))
+}
+
+#[cfg(test)]
+mod parse {
+    use super::*;
+    use ::quote::quote;
+
+    #[test]
+    fn it_should_output_kebab_case_values() -> Result<()> {
+        let output = parse(quote! {
+            #[derive(Copy, Clone)]
+            pub enum InputType {
+                Email,
+                DatetimeLocal,
+                #[attribute_value(rename = "tel")]
+                Phone,
+            }
+        })?;
+
+        let expected = quote! {
+            impl ::renderx::dom::ToAttributeValue for InputType {
+                fn to_attribute_value(self) -> ::renderx::dom::AttributeValue {
+                    ::renderx::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed(match self {
+                        Self::Email => "email",
+                        Self::DatetimeLocal => "datetime-local",
+                        Self::Phone => "tel",
+                    }))
+                }
+            }
+        };
+
+        assert_tokens_eq(expected, output)
+    }
+
+    #[test]
+    fn it_should_output_with_crate_path() -> Result<()> {
+        let output = parse(quote! {
+            #[attribute_value(crate = "::my_fw::html")]
+            enum Loading {
+                Lazy,
+            }
+        })?;
+
+        let expected = quote! {
+            impl ::my_fw::html::dom::ToAttributeValue for Loading {
+                fn to_attribute_value(self) -> ::my_fw::html::dom::AttributeValue {
+                    ::my_fw::html::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed(match self {
+                        Self::Lazy => "lazy",
+                    }))
+                }
+            }
+        };
+
+        assert_tokens_eq(expected, output)
+    }
+
+    #[test]
+    fn it_should_error_on_variants_with_fields() {
+        let output = parse(quote! {
+            enum Size {
+                Small,
+                Custom(u32),
+            }
+        });
+
+        assert!(matches!(output, Err(Error::VariantFieldsFound(name)) if name == "Custom"));
+    }
+
+    #[test]
+    fn it_should_error_on_structs_and_unknown_attributes() {
+        let output = parse(quote! {
+            struct Size;
+        });
+        assert!(matches!(output, Err(Error::NotAnEnum)));
+
+        let output = parse(quote! {
+            enum Size {
+                #[attribute_value(name = "s")]
+                Small,
+            }
+        });
+        assert!(matches!(output, Err(Error::UnknownAttributeFound)));
+    }
+
+    fn assert_tokens_eq(expected: TokenStream, output: TokenStream) -> Result<()> {
+        ::pretty_assertions::assert_eq!(expected.to_string(), output.to_string());
+
+        Ok(())
+    }
+}
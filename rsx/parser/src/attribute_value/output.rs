@@ -0,0 +1,21 @@
+use crate::attribute_value::ast::AttributeEnum;
+
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+
+pub fn build(ast: AttributeEnum, crate_path: &TokenStream) -> TokenStream {
+    let name = ast.name;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    let variant_names = ast.variants.iter().map(|variant| &variant.name);
+    let variant_values = ast.variants.iter().map(|variant| &variant.value);
+
+    quote! {
+        impl #impl_generics #crate_path::dom::ToAttributeValue for #name #type_generics #where_clause {
+            fn to_attribute_value(self) -> #crate_path::dom::AttributeValue {
+                #crate_path::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed(match self {
+                    #(Self::#variant_names => #variant_values,)*
+                }))
+            }
+        }
+    }
+}
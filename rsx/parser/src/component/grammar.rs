@@ -4,11 +4,18 @@ use crate::component::error::Error;
 use crate::component::error::Result;
 
 use ::proc_macro2::TokenStream;
+use ::quote::ToTokens;
 
 use ::syn::parse2;
 use ::syn::FnArg;
 use ::syn::ItemFn;
+use ::syn::LitStr;
+use ::syn::Path;
 use ::syn::ReturnType;
+use ::syn::Token;
+
+use ::syn::parse::ParseStream;
+use ::syn::parse::Parser;
 
 use ::syn::punctuated::Pair;
 use ::syn::punctuated::Punctuated;
@@ -41,6 +48,25 @@ pub fn parse(stream: TokenStream) -> Result<Function> {
     })
 }
 
+/// Parses the attribute given to the component macro.
+/// The only one supported is the crate path, i.e. `#[component(crate = "my_fw::html")]`.
+pub fn parse_crate_attribute(stream: TokenStream) -> Result<Option<TokenStream>> {
+    if stream.is_empty() {
+        return Ok(None);
+    }
+
+    let parser = |input: ParseStream| -> syn::Result<LitStr> {
+        input.parse::<Token![crate]>()?;
+        input.parse::<Token![=]>()?;
+        input.parse::<LitStr>()
+    };
+
+    let path_literal = parser.parse2(stream).map_err(|_| Error::AttributeFound)?;
+    let path = path_literal.parse::<Path>()?;
+
+    Ok(Some(path.into_token_stream()))
+}
+
 fn parse_props(mut input: Punctuated<FnArg, Comma>) -> Result<Option<Props>> {
     if input.is_empty() {
         return Ok(None);
@@ -5,16 +5,16 @@ mod output;
 
 pub use self::error::*;
 
+use crate::util::default_crate_path;
 use ::proc_macro2::TokenStream;
 
 pub fn parse(old_attrs: TokenStream, old_stream: TokenStream) -> Result<TokenStream> {
-    if !old_attrs.is_empty() {
-        return Err(Error::AttributeFound);
-    }
+    let maybe_crate_path = grammar::parse_crate_attribute(old_attrs)?;
+    let crate_path = maybe_crate_path.unwrap_or_else(default_crate_path);
 
     let stream = TokenStream::from(old_stream);
     let ast = grammar::parse(stream)?;
-    Ok(output::build(ast))
+    Ok(output::build(ast, &crate_path))
 }
 
 #[cfg(test)]
@@ -94,6 +94,56 @@ mod parse {
         assert_tokens_eq(expected, output)
     }
 
+    #[test]
+    fn it_should_output_component_markup_with_crate_path() -> Result<()> {
+        let output = parse(
+            quote! { crate = "::my_fw::html" },
+            quote! {
+                pub fn HorizontalRule() -> Node {
+                    rsx! {
+                        <hr class="horizontal-rule" />
+                    }
+                }
+            },
+        )?;
+
+        let expected = quote! {
+            #[allow(non_snake_case)]
+            pub struct HorizontalRule;
+
+            impl FnOnce<()> for HorizontalRule {
+                type Output = Node;
+                extern "rust-call" fn call_once(self, _: ()) -> Node {
+                    rsx! {
+                        <hr class="horizontal-rule" />
+                    }
+                }
+            }
+
+            impl ::my_fw::html::Component for HorizontalRule {
+                type Props = ();
+            }
+        };
+
+        assert_tokens_eq(expected, output)
+    }
+
+    #[test]
+    fn it_should_error_on_unknown_attributes() {
+        let output = parse(
+            quote! { something = "else" },
+            quote! {
+                pub fn HorizontalRule() -> Node {
+                    rsx! {
+                        <hr class="horizontal-rule" />
+                    }
+                }
+            },
+        );
+
+        assert!(matches!(output, Err(Error::AttributeFound)));
+    }
+
     fn assert_tokens_eq(expected: TokenStream, output: TokenStream) -> Result<()> {
         ::pretty_assertions::assert_eq!(expected.to_string(), output.to_string());
 
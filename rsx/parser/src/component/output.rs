@@ -4,11 +4,11 @@ use crate::component::ast::Props;
 use ::proc_macro2::TokenStream;
 use ::quote::quote;
 
-pub fn build(ast: Function) -> TokenStream {
-    visit_function(ast)
+pub fn build(ast: Function, crate_path: &TokenStream) -> TokenStream {
+    visit_function(ast, crate_path)
 }
 
-fn visit_function(f: Function) -> TokenStream {
+fn visit_function(f: Function, crate_path: &TokenStream) -> TokenStream {
     let visibility = f.visibility;
     let constness = f.constness;
     let asyncness = f.asyncness;
@@ -31,7 +31,7 @@ fn visit_function(f: Function) -> TokenStream {
                 #code
         }
 
-        impl ::renderx::Component for #name {
+        impl #crate_path::Component for #name {
             type Props = #props_type;
         }
     }
@@ -1,3 +1,8 @@
+#![feature(proc_macro_tracked_env)]
+
+extern crate proc_macro;
+
+pub mod attribute_value;
 pub mod component;
 pub mod rsx;
 mod util;
@@ -13,6 +13,15 @@ pub enum Node {
     Comment {
         children: Option<Vec<Node>>,
     },
+    /// i.e. <![CDATA[ ... ]]>
+    CData {
+        children: Option<Vec<Node>>,
+    },
+    /// i.e. <?xml version="1.0"?>
+    ProcessingInstruction {
+        target: Value,
+        attributes: Option<Vec<Attribute>>,
+    },
     Fragment {
         children: Vec<Node>,
     },
@@ -36,6 +45,22 @@ pub enum Node {
         attributes: Option<Vec<Attribute>>,
         children: Option<Vec<Node>>,
     },
+    /// Where a layout writes a slot. i.e. <slot:head />
+    Slot {
+        name: String,
+    },
+    /// Content for a slot. i.e. <slot:head><title>Home</title></slot:head>
+    SlotContent {
+        name: String,
+        children: Option<Vec<Node>>,
+    },
+    /// Catches errors from the components within it.
+    /// i.e. <ErrorBoundary fallback={|err| rsx! { ... }} catch_panics>...</ErrorBoundary>
+    ErrorBoundary {
+        fallback: TokenStream,
+        catch_panics: bool,
+        children: Option<Vec<Node>>,
+    },
     Text(String),
     Code(TokenStream),
 }
@@ -59,6 +84,24 @@ impl PartialEq for Node {
                     attributes: right_attributes,
                 },
             ) => left_name == right_name && left_attributes == right_attributes,
+            (
+                Node::CData {
+                    children: left_children,
+                },
+                Node::CData {
+                    children: right_children,
+                },
+            ) => left_children == right_children,
+            (
+                Node::ProcessingInstruction {
+                    target: left_target,
+                    attributes: left_attributes,
+                },
+                Node::ProcessingInstruction {
+                    target: right_target,
+                    attributes: right_attributes,
+                },
+            ) => left_target == right_target && left_attributes == right_attributes,
             (
                 Node::Comment {
                     children: left_children,
@@ -127,6 +170,35 @@ impl PartialEq for Node {
                     && left_attributes == right_attributes
                     && left_children == right_children
             }
+            (Node::Slot { name: left_name }, Node::Slot { name: right_name }) => {
+                left_name == right_name
+            }
+            (
+                Node::SlotContent {
+                    name: left_name,
+                    children: left_children,
+                },
+                Node::SlotContent {
+                    name: right_name,
+                    children: right_children,
+                },
+            ) => left_name == right_name && left_children == right_children,
+            (
+                Node::ErrorBoundary {
+                    fallback: left_fallback,
+                    catch_panics: left_catch_panics,
+                    children: left_children,
+                },
+                Node::ErrorBoundary {
+                    fallback: right_fallback,
+                    catch_panics: right_catch_panics,
+                    children: right_children,
+                },
+            ) => {
+                token_stream_eq(&left_fallback, &right_fallback)
+                    && left_catch_panics == right_catch_panics
+                    && left_children == right_children
+            }
             (Node::Text(left), Node::Text(right)) => left == right,
             (Node::Code(left), Node::Code(right)) => token_stream_eq(&left, &right),
             _ => false,
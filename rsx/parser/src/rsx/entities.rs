@@ -0,0 +1,175 @@
+//! Decodes HTML entities written in `rsx!` text, i.e. `&copy;` and `&#x2014;`.
+//!
+//! This uses the same table as `core::parse`, which decodes entities at runtime.
+//! Unlike at runtime, an entity which cannot be decoded is an error,
+//! as it is most likely a typo.
+
+include!("../../../core/src/parse/entity_table.rs");
+
+/// An entity which could not be decoded, such as `&nbps;`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownEntity {
+    /// The entity as written.
+    pub entity: String,
+    /// Where the entity starts, in bytes.
+    pub offset: usize,
+}
+
+/// Replaces entities with the characters they represent.
+///
+/// An `&` which isn't followed by a name and a `;` is left as it is, i.e. `Fish & Chips`.
+pub fn decode_entities(text: &str) -> Result<String, UnknownEntity> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut offset = 0;
+
+    while let Some(index) = text[offset..].find('&') {
+        decoded.push_str(&text[offset..offset + index]);
+        offset += index;
+
+        let rest = &text[offset + "&".len()..];
+        let (entity, length) = match rest.strip_prefix('#') {
+            Some(number) => decode_numeric_entity(number)
+                .map(|(c, length)| (Some(c.to_string()), "#".len() + length))
+                .ok_or_else(|| unknown_entity(text, offset, "#".len() + entity_length(number)))?,
+            None => match named_entity_length(rest) {
+                Some(length) => {
+                    let entity = named_entity(&rest[..length - ";".len()])
+                        .ok_or_else(|| unknown_entity(text, offset, length))?;
+                    (Some(entity.to_string()), length)
+                }
+                None => (None, 0),
+            },
+        };
+
+        match entity {
+            Some(entity) => decoded.push_str(&entity),
+            None => decoded.push('&'),
+        }
+        offset += "&".len() + length;
+    }
+
+    decoded.push_str(&text[offset..]);
+    Ok(decoded)
+}
+
+fn unknown_entity(text: &str, offset: usize, length: usize) -> UnknownEntity {
+    UnknownEntity {
+        entity: text[offset..offset + "&".len() + length].to_string(),
+        offset,
+    }
+}
+
+/// The length of `name;`, if that is at the start of the text.
+fn named_entity_length(text: &str) -> Option<usize> {
+    let name_length = text
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(text.len());
+
+    if name_length > 0 && text[name_length..].starts_with(';') {
+        Some(name_length + ";".len())
+    } else {
+        None
+    }
+}
+
+/// How much of the text looks like part of an entity, for reporting errors.
+fn entity_length(text: &str) -> usize {
+    match text.find(|c: char| !c.is_ascii_alphanumeric()) {
+        Some(index) if text[index..].starts_with(';') => index + ";".len(),
+        Some(index) => index,
+        None => text.len(),
+    }
+}
+
+fn named_entity(name: &str) -> Option<&'static str> {
+    NAMED_ENTITIES
+        .binary_search_by(|(entity, _)| entity.cmp(&name))
+        .ok()
+        .map(|index| NAMED_ENTITIES[index].1)
+}
+
+/// Decodes the digits of a numeric entity, i.e. the `169;` of `&#169;`.
+/// The closing `;` is optional, the same as in the browser.
+fn decode_numeric_entity(text: &str) -> Option<(char, usize)> {
+    let (prefix_length, radix) = if text.starts_with('x') || text.starts_with('X') {
+        ("x".len(), 16)
+    } else {
+        (0, 10)
+    };
+
+    let digits = &text[prefix_length..];
+    let digits_length = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+
+    let c = u32::from_str_radix(&digits[..digits_length], radix)
+        .ok()
+        .filter(|code| *code != 0)
+        .and_then(::std::char::from_u32)?;
+
+    let mut length = prefix_length + digits_length;
+    if digits[digits_length..].starts_with(';') {
+        length += ";".len();
+    }
+
+    Some((c, length))
+}
+
+#[cfg(test)]
+mod decode_entities {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_decode_named_entities() {
+        assert_eq!(
+            Ok("Fish & Chips\u{a0}© — ♥".to_string()),
+            decode_entities("Fish &amp; Chips&nbsp;&copy; &mdash; &hearts;")
+        );
+    }
+
+    #[test]
+    fn it_should_decode_numeric_entities() {
+        assert_eq!(
+            Ok("— — ©".to_string()),
+            decode_entities("&#x2014; &#8212; &#169")
+        );
+    }
+
+    #[test]
+    fn it_should_keep_ampersands_which_are_not_entities() {
+        assert_eq!(
+            Ok("Fish & Chips, AT&T, a&&b, &;".to_string()),
+            decode_entities("Fish & Chips, AT&T, a&&b, &;")
+        );
+    }
+
+    #[test]
+    fn it_should_error_on_unknown_entities() {
+        assert_eq!(
+            Err(UnknownEntity {
+                entity: "&nbps;".to_string(),
+                offset: 2,
+            }),
+            decode_entities("a &nbps; b")
+        );
+    }
+
+    #[test]
+    fn it_should_error_on_invalid_numeric_entities() {
+        assert_eq!(
+            Err(UnknownEntity {
+                entity: "&#xZZ;".to_string(),
+                offset: 0,
+            }),
+            decode_entities("&#xZZ;")
+        );
+        assert_eq!(
+            Err(UnknownEntity {
+                entity: "&#xD800;".to_string(),
+                offset: 0,
+            }),
+            decode_entities("&#xD800;")
+        );
+    }
+}
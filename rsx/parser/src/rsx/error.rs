@@ -2,10 +2,11 @@ use ::std::convert::From;
 use ::std::fmt;
 
 use crate::util::TokenIteratorError;
+use ::proc_macro2::Span;
 
 pub type Result<N> = ::std::result::Result<N, Error>;
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum Error {
     MismatchedClosingTagCode,
     MismatchedClosingTagName,
@@ -17,9 +18,34 @@ pub enum Error {
     PeekOnEmptyNode,
     MoreTokensExpected,
     ChompOnEmptyNode,
+    ExpectedCratePath,
+    ExpectedCratePathSemicolon,
+    UnknownEntity(UnknownEntityError),
+    UnknownAttributeNamespace(String),
+    SlotAttributesFound,
+    /// An `<ErrorBoundary>` was written without a `fallback={...}`.
+    ErrorBoundaryFallbackMissing,
+    /// An attribute on an `<ErrorBoundary>`, other than `fallback` and `catch_panics`.
+    ErrorBoundaryAttributeFound(String),
+    /// Writing to the `RENDERX_TRANSLATION_KEYS` file failed.
+    TranslationKeysError(String),
     FmtError(fmt::Error),
 }
 
+/// An HTML entity in the text which isn't known, such as `&nbps;`.
+#[derive(Clone, Debug)]
+pub struct UnknownEntityError {
+    pub entity: String,
+    pub span: Span,
+}
+
+/// Spans cannot be compared, so only the entity is.
+impl PartialEq for UnknownEntityError {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+    }
+}
+
 impl From<fmt::Error> for Error {
     fn from(err: fmt::Error) -> Self {
         Error::FmtError(err)
@@ -0,0 +1,9 @@
+//! Escapes pre-rendered text and attribute values.
+//!
+//! This uses the same code as `core::render`, so that pre-rendered HTML
+//! is the same as the HTML which would be rendered at runtime.
+
+// Only the HTML parts are used, as `rsx!` is not pre-rendered as XML.
+#![allow(dead_code)]
+
+include!("../../../core/src/render/escaping.rs");
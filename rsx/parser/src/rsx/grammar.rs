@@ -1,43 +1,112 @@
 use crate::rsx::ast::Attribute;
 use crate::rsx::ast::Node;
 use crate::rsx::ast::Value;
+use crate::rsx::entities::decode_entities;
 use crate::rsx::error::Error;
 use crate::rsx::error::Result;
-use crate::util::flatten_non_braces;
+use crate::rsx::error::UnknownEntityError;
+use crate::rsx::namespace::is_void_element_name;
+use crate::rsx::namespace::Namespace;
+use crate::rsx::whitespace::delimiter_chars;
+use crate::rsx::whitespace::edge_spacing;
+use crate::rsx::whitespace::gap_between;
+use crate::rsx::whitespace::Gap;
+use crate::rsx::whitespace::TextRun;
 use crate::util::token_stream_eq;
 use crate::util::MicroVec;
 use crate::util::TokenIterator;
 
+use ::proc_macro2::Delimiter;
+use ::proc_macro2::Group;
+use ::proc_macro2::Span;
 use ::proc_macro2::TokenStream;
 use ::proc_macro2::TokenTree;
 use ::std::fmt::Write;
 use ::std::vec::IntoIter;
+use ::syn::Lit;
 
 const COLON: char = ':';
 const EXCLAMATION_MARK: char = '!';
+const QUESTION_MARK: char = '?';
 const HYPHEN: char = '-';
+const CDATA: &str = "CDATA";
 const LEFT_ANGLE: char = '<';
 const RIGHT_ANGLE: char = '>';
 const FORWARD_SLASH: char = '/';
 const EQUALS: char = '=';
+const SEMICOLON: char = ';';
+const SLOT_PREFIX: &str = "slot:";
+const ERROR_BOUNDARY: &str = "ErrorBoundary";
+const ERROR_BOUNDARY_FALLBACK: &str = "fallback";
+const ERROR_BOUNDARY_CATCH_PANICS: &str = "catch_panics";
 
 static COMMENT_CLOSING_LOOKAHEAD: &'static [char] = &[HYPHEN, HYPHEN, RIGHT_ANGLE];
 static TAG_OPENING_LOOKAHEAD: &'static [char] = &[LEFT_ANGLE];
 static TAG_CLOSING_LOOKAHEAD: &'static [char] = &[LEFT_ANGLE, FORWARD_SLASH];
 
+static RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
 type TokenIteratorVec = TokenIterator<IntoIter<TokenTree>>;
 
+/// Where text is being parsed, which changes what can be found within it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum TextContext {
+    /// Text within elements can contain tags, and `{code}`.
+    Element,
+    /// Text within comments can only contain `{code}`.
+    Comment,
+}
+
 pub fn parse(stream: TokenStream) -> Result<Node> {
     parse_root(stream)
 }
 
+/// Splits off the optional `crate = path;` argument at the start of the macro.
+///
+/// Returns the path (if one was given), and the rest of the stream to be parsed as rsx.
+pub fn parse_crate_argument(stream: TokenStream) -> Result<(Option<TokenStream>, TokenStream)> {
+    let mut input = TokenIterator::new(stream.into_iter().collect::<Vec<TokenTree>>());
+
+    let is_crate_argument = input.is_lookahead_punct(EQUALS, 1)
+        && match input.peek() {
+            Some(TokenTree::Ident(ident)) => ident.to_string() == "crate",
+            _ => false,
+        };
+
+    if !is_crate_argument {
+        return Ok((None, input.to_token_stream()));
+    }
+
+    input.chomp_ident_of("crate")?;
+    input.chomp_punct(EQUALS)?;
+
+    let mut crate_path = TokenStream::new();
+    loop {
+        if input.is_empty() {
+            return Err(Error::ExpectedCratePathSemicolon);
+        }
+
+        if input.is_next_punct(SEMICOLON) {
+            input.chomp_punct(SEMICOLON)?;
+            break;
+        }
+
+        crate_path.extend(Some(input.chomp()?));
+    }
+
+    if crate_path.is_empty() {
+        return Err(Error::ExpectedCratePath);
+    }
+
+    Ok((Some(crate_path), input.to_token_stream()))
+}
+
 fn parse_root(stream: TokenStream) -> Result<Node> {
     if stream.is_empty() {
         return Err(Error::EmptyMacroStreamGiven);
     }
 
-    let flat_stream = flatten_non_braces(stream);
-    let mut input = TokenIterator::new(flat_stream);
+    let mut input = TokenIterator::new(stream.into_iter().collect::<Vec<TokenTree>>());
     let node = parse_root_node(&mut input)?;
 
     if !input.is_empty() {
@@ -48,11 +117,18 @@ fn parse_root(stream: TokenStream) -> Result<Node> {
 }
 
 fn parse_root_node(input: &mut TokenIteratorVec) -> Result<Node> {
-    let mut nodes = MicroVec::new();
+    let mut children = vec![];
 
     while !input.is_empty() {
-        let node = parse_node(input)?;
-        nodes.push(node);
+        push_edge_spacing(&mut children, input.last_span(), input.peek_span());
+
+        let node = parse_node(input, Namespace::Html)?;
+        push_child(&mut children, node);
+    }
+
+    let mut nodes = MicroVec::new();
+    for child in children {
+        nodes.push(child);
     }
 
     match nodes {
@@ -62,24 +138,127 @@ fn parse_root_node(input: &mut TokenIteratorVec) -> Result<Node> {
     }
 }
 
-fn parse_node(input: &mut TokenIteratorVec) -> Result<Node> {
+fn parse_node(input: &mut TokenIteratorVec, namespace: Namespace) -> Result<Node> {
     if input.is_next_punct(LEFT_ANGLE) {
         if input.is_lookahead_punct(EXCLAMATION_MARK, 1) {
             if input.is_lookahead_punct(HYPHEN, 2) {
                 parse_node_comment(input)
+            } else if is_lookahead_cdata(input, 2) {
+                parse_node_cdata(input)
             } else {
                 parse_node_doctype(input)
             }
+        } else if input.is_lookahead_punct(QUESTION_MARK, 1) {
+            parse_node_processing_instruction(input)
         } else {
-            parse_node_tag(input)
+            parse_node_tag(input, namespace)
         }
     } else if input.is_brace_group() {
         Ok(Node::Code(input.chomp_brace_group()?))
+    } else if is_next_group_with_nodes(input, TextContext::Element) {
+        parse_node_group(input, TextContext::Element, namespace)
     } else {
         parse_node_text(input)
     }
 }
 
+/// Parses a `()` or `[]` group which contains tags or `{code}`,
+/// into a fragment of the brackets and the nodes within them.
+/// i.e. `({count} items)`.
+fn parse_node_group(
+    input: &mut TokenIteratorVec,
+    context: TextContext,
+    namespace: Namespace,
+) -> Result<Node> {
+    let group = match input.chomp()? {
+        TokenTree::Group(group) => group,
+        _ => return Err(Error::UnexpectedToken),
+    };
+
+    let (opening_char, closing_char) = delimiter_chars(group.delimiter());
+    let mut group_input =
+        TokenIterator::new(group.stream().into_iter().collect::<Vec<TokenTree>>());
+    group_input.set_last_span(group.span_open());
+
+    let mut children = vec![Node::Text(opening_char.to_string())];
+    while !group_input.is_empty() {
+        push_edge_spacing(
+            &mut children,
+            group_input.last_span(),
+            group_input.peek_span(),
+        );
+
+        let child = match context {
+            TextContext::Element => parse_node(&mut group_input, namespace)?,
+            TextContext::Comment => {
+                parse_comment_child(&mut group_input, &COMMENT_CLOSING_LOOKAHEAD)?
+            }
+        };
+        push_child(&mut children, child);
+    }
+
+    push_edge_spacing(
+        &mut children,
+        group_input.last_span(),
+        Some(group.span_close()),
+    );
+    push_child(&mut children, Node::Text(closing_char.to_string()));
+
+    Ok(Node::Fragment { children })
+}
+
+/// Returns true if the next item is a `()` or `[]` group,
+/// which contains more than just text.
+fn is_next_group_with_nodes(input: &mut TokenIteratorVec, context: TextContext) -> bool {
+    match input.peek() {
+        Some(TokenTree::Group(group)) if group.delimiter() != Delimiter::Brace => {
+            !is_text_group(group, context)
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if everything within the group is text.
+fn is_text_group(group: &Group, context: TextContext) -> bool {
+    group.stream().into_iter().all(|token| match token {
+        TokenTree::Group(group) => {
+            group.delimiter() != Delimiter::Brace && is_text_group(&group, context)
+        }
+        TokenTree::Punct(punct) => context == TextContext::Comment || punct.as_char() != LEFT_ANGLE,
+        _ => true,
+    })
+}
+
+/// Adds any same line spacing found between two nodes.
+/// See the `whitespace` module for the rules.
+fn push_edge_spacing(children: &mut Vec<Node>, maybe_last: Option<Span>, maybe_next: Option<Span>) {
+    let gap = match (maybe_last, maybe_next) {
+        (Some(last), Some(next)) => gap_between(last, next),
+        _ => Gap::Unknown,
+    };
+
+    if let Some(spacing) = edge_spacing(gap) {
+        push_child(children, Node::Text(spacing));
+    }
+}
+
+/// Adds the child to the end of the list.
+/// Text next to text is joined into one text node.
+fn push_child(children: &mut Vec<Node>, child: Node) {
+    if let Node::Text(text) = &child {
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(Node::Text(last_text)) = children.last_mut() {
+            last_text.push_str(text);
+            return;
+        }
+    }
+
+    children.push(child);
+}
+
 fn parse_node_comment(input: &mut TokenIteratorVec) -> Result<Node> {
     input.chomp_puncts(&[LEFT_ANGLE, EXCLAMATION_MARK, HYPHEN, HYPHEN])?;
 
@@ -102,11 +281,7 @@ fn parse_comment_children(input: &mut TokenIteratorVec) -> Result<Option<Vec<Nod
             return Ok(maybe_children);
         }
 
-        let child = if input.is_brace_group() {
-            Node::Code(input.chomp_brace_group()?)
-        } else {
-            Node::Text(parse_text(input, &COMMENT_CLOSING_LOOKAHEAD)?)
-        };
+        let child = parse_comment_child(input, &COMMENT_CLOSING_LOOKAHEAD)?;
 
         match maybe_children.as_mut() {
             Some(children) => children.push(child),
@@ -115,6 +290,94 @@ fn parse_comment_children(input: &mut TokenIteratorVec) -> Result<Option<Vec<Nod
     }
 }
 
+fn parse_comment_child(input: &mut TokenIteratorVec, stopping_lookaheads: &[char]) -> Result<Node> {
+    if input.is_brace_group() {
+        Ok(Node::Code(input.chomp_brace_group()?))
+    } else if is_next_group_with_nodes(input, TextContext::Comment) {
+        parse_node_group(input, TextContext::Comment, Namespace::Html)
+    } else {
+        Ok(Node::Text(parse_text(
+            input,
+            stopping_lookaheads,
+            TextContext::Comment,
+        )?))
+    }
+}
+
+/// Returns true if there is a `[CDATA[ ... ]]` ahead.
+fn is_lookahead_cdata(input: &mut TokenIteratorVec, index: usize) -> bool {
+    match input.lookahead(index) {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+            match group.stream().into_iter().next() {
+                Some(TokenTree::Ident(ident)) => ident == CDATA,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Parses `<![CDATA[ ... ]]>`.
+///
+/// The contents are kept as written, and can contain `{code}`.
+/// A lone string literal is kept exactly, i.e. `<![CDATA[r#"<p>Hello</p>"#]]>`.
+fn parse_node_cdata(input: &mut TokenIteratorVec) -> Result<Node> {
+    input.chomp_puncts(&[LEFT_ANGLE, EXCLAMATION_MARK])?;
+    let outer_group = input.chomp_group(Delimiter::Bracket)?;
+    input.chomp_punct(RIGHT_ANGLE)?;
+
+    let mut outer_input =
+        TokenIterator::new(outer_group.stream().into_iter().collect::<Vec<TokenTree>>());
+    outer_input.chomp_ident_of(CDATA)?;
+    let group = outer_input.chomp_group(Delimiter::Bracket)?;
+    if !outer_input.is_empty() {
+        return Err(Error::UnexpectedToken);
+    }
+
+    let mut group_input =
+        TokenIterator::new(group.stream().into_iter().collect::<Vec<TokenTree>>());
+    let children = if is_next_lone_string_literal(&mut group_input) {
+        vec![Node::Text(parse_raw_text_body(&mut group_input)?)]
+    } else {
+        parse_cdata_children(&mut group_input)?
+    };
+
+    if children.is_empty() {
+        Ok(Node::CData { children: None })
+    } else {
+        Ok(Node::CData {
+            children: Some(children),
+        })
+    }
+}
+
+fn parse_cdata_children(input: &mut TokenIteratorVec) -> Result<Vec<Node>> {
+    let mut children = vec![];
+
+    while !input.is_empty() {
+        if input.is_brace_group() {
+            children.push(Node::Code(input.chomp_brace_group()?));
+        } else {
+            let mut text = TextRun::new();
+            while !input.is_empty() && !input.is_brace_group() {
+                text.push_token(input.chomp()?);
+            }
+            push_child(&mut children, Node::Text(text.into_string()));
+        }
+    }
+
+    Ok(children)
+}
+
+fn is_next_lone_string_literal(input: &mut TokenIteratorVec) -> bool {
+    let is_string_literal = match input.peek() {
+        Some(TokenTree::Literal(literal)) => matches!(Lit::new(literal.clone()), Lit::Str(_)),
+        _ => false,
+    };
+
+    is_string_literal && input.lookahead(1).is_none()
+}
+
 fn parse_node_doctype(input: &mut TokenIteratorVec) -> Result<Node> {
     input.chomp_puncts(&[LEFT_ANGLE, EXCLAMATION_MARK])?;
     let name = parse_name(input)?;
@@ -124,7 +387,16 @@ fn parse_node_doctype(input: &mut TokenIteratorVec) -> Result<Node> {
     Ok(Node::Doctype { name, attributes })
 }
 
-fn parse_node_tag(input: &mut TokenIteratorVec) -> Result<Node> {
+fn parse_node_processing_instruction(input: &mut TokenIteratorVec) -> Result<Node> {
+    input.chomp_puncts(&[LEFT_ANGLE, QUESTION_MARK])?;
+    let target = parse_name(input)?;
+    let attributes = parse_attributes(input)?;
+    input.chomp_puncts(&[QUESTION_MARK, RIGHT_ANGLE])?;
+
+    Ok(Node::ProcessingInstruction { target, attributes })
+}
+
+fn parse_node_tag(input: &mut TokenIteratorVec, parent_namespace: Namespace) -> Result<Node> {
     input.chomp_punct(LEFT_ANGLE)?;
 
     // parses </>
@@ -136,7 +408,7 @@ fn parse_node_tag(input: &mut TokenIteratorVec) -> Result<Node> {
     // parses <>(... contents)</>
     if input.is_next_punct(RIGHT_ANGLE) {
         input.chomp_punct(RIGHT_ANGLE)?;
-        let maybe_children = parse_children(input)?;
+        let maybe_children = parse_children(input, parent_namespace)?;
         input.chomp_puncts(&[LEFT_ANGLE, FORWARD_SLASH, RIGHT_ANGLE])?;
 
         return match maybe_children {
@@ -147,12 +419,23 @@ fn parse_node_tag(input: &mut TokenIteratorVec) -> Result<Node> {
 
     // Real tags from here on. i.e. <div></div> and <hr />
     let opening_tag_name = parse_name(input)?;
+    let namespace = parent_namespace.of_element(&opening_tag_name);
     let attributes = parse_attributes(input)?;
-    let is_component = is_component_name(&opening_tag_name);
+
+    if let Some(slot_name) = slot_name(&opening_tag_name) {
+        return parse_node_slot(input, slot_name, attributes, parent_namespace);
+    }
+
+    let is_component = is_component_name(&opening_tag_name)
+        && namespace.known_element_name(&opening_tag_name).is_none();
 
     if input.is_next_punct(FORWARD_SLASH) {
         input.chomp_puncts(&[FORWARD_SLASH, RIGHT_ANGLE])?;
 
+        if is_component && is_error_boundary_name(&opening_tag_name) {
+            return build_error_boundary(attributes, None);
+        }
+
         if is_component {
             if let Value::Text(opening_tag_name_string) = opening_tag_name {
                 return Ok(Node::SelfClosingComponent {
@@ -163,16 +446,24 @@ fn parse_node_tag(input: &mut TokenIteratorVec) -> Result<Node> {
                 unreachable!("Component name was not parsed as `Value::Text` (this is a bug)");
             }
         } else {
-            return Ok(Node::SelfClosing {
-                name: opening_tag_name,
+            return build_element(
+                parent_namespace,
+                namespace,
+                opening_tag_name,
                 attributes,
-            });
+                None,
+                true,
+            );
         }
     }
 
     input.chomp_punct(RIGHT_ANGLE)?;
 
-    let children = parse_children(input)?;
+    let children = if is_raw_text_name(&opening_tag_name) && is_next_raw_text_body(input) {
+        Some(vec![Node::Text(parse_raw_text_body(input)?)])
+    } else {
+        parse_children(input, namespace.of_children(&opening_tag_name))?
+    };
 
     // Closing Tag.
     input.chomp_puncts(&[LEFT_ANGLE, FORWARD_SLASH])?;
@@ -197,6 +488,10 @@ fn parse_node_tag(input: &mut TokenIteratorVec) -> Result<Node> {
         }
     }
 
+    if is_component && is_error_boundary_name(&opening_tag_name) {
+        return build_error_boundary(attributes, children);
+    }
+
     if is_component {
         if let Value::Text(opening_tag_name_string) = opening_tag_name {
             Ok(Node::OpenComponent {
@@ -207,17 +502,162 @@ fn parse_node_tag(input: &mut TokenIteratorVec) -> Result<Node> {
         } else {
             unreachable!("Component name was not parsed as `Value::Text` (this is a bug)");
         }
+    } else {
+        build_element(
+            parent_namespace,
+            namespace,
+            opening_tag_name,
+            attributes,
+            children,
+            false,
+        )
+    }
+}
+
+/// Parses the rest of `<slot:name/>`, where a layout writes the slot,
+/// and `<slot:name>...</slot:name>`, which gives content for the slot.
+fn parse_node_slot(
+    input: &mut TokenIteratorVec,
+    name: String,
+    attributes: Option<Vec<Attribute>>,
+    namespace: Namespace,
+) -> Result<Node> {
+    if attributes.is_some() {
+        return Err(Error::SlotAttributesFound);
+    }
+
+    if input.is_next_punct(FORWARD_SLASH) {
+        input.chomp_puncts(&[FORWARD_SLASH, RIGHT_ANGLE])?;
+        return Ok(Node::Slot { name });
+    }
+
+    input.chomp_punct(RIGHT_ANGLE)?;
+    let children = parse_children(input, namespace)?;
+
+    input.chomp_puncts(&[LEFT_ANGLE, FORWARD_SLASH])?;
+    let closing_tag_name = parse_name(input)?;
+    input.chomp_punct(RIGHT_ANGLE)?;
+
+    if slot_name(&closing_tag_name).as_ref() != Some(&name) {
+        return Err(Error::MismatchedClosingTagName);
+    }
+
+    Ok(Node::SlotContent { name, children })
+}
+
+fn is_error_boundary_name(tag_name: &Value) -> bool {
+    matches!(tag_name, Value::Text(text) if text == ERROR_BOUNDARY)
+}
+
+/// `<ErrorBoundary>` is built in, like slots, rather than being a component.
+/// It takes a `fallback={...}`, and an optional lone `catch_panics`.
+fn build_error_boundary(
+    attributes: Option<Vec<Attribute>>,
+    children: Option<Vec<Node>>,
+) -> Result<Node> {
+    let mut maybe_fallback = None;
+    let mut catch_panics = false;
+
+    for attribute in attributes.into_iter().flatten() {
+        match (&attribute.key, attribute.value) {
+            (Value::Text(key), Some(Value::Code(code))) if key == ERROR_BOUNDARY_FALLBACK => {
+                maybe_fallback = Some(code);
+            }
+            (Value::Text(key), None) if key == ERROR_BOUNDARY_CATCH_PANICS => {
+                catch_panics = true;
+            }
+            (Value::Text(key), _) => return Err(Error::ErrorBoundaryAttributeFound(key.clone())),
+            (Value::Code(code), _) => {
+                return Err(Error::ErrorBoundaryAttributeFound(code.to_string()))
+            }
+        }
+    }
+
+    Ok(Node::ErrorBoundary {
+        fallback: maybe_fallback.ok_or(Error::ErrorBoundaryFallbackMissing)?,
+        catch_panics,
+        children,
+    })
+}
+
+/// The name of the slot for `<slot:name>` tags.
+fn slot_name(tag_name: &Value) -> Option<String> {
+    match tag_name {
+        Value::Code(_) => None,
+        Value::Text(text) => text.strip_prefix(SLOT_PREFIX).map(str::to_string),
+    }
+}
+
+/// Builds an element, following the rules of the namespace it is in.
+///
+/// Empty SVG and MathML elements, and HTML void elements, are self-closing.
+/// Other HTML elements are always closed, as the browser reads `<div/>` as `<div>`.
+fn build_element(
+    parent_namespace: Namespace,
+    namespace: Namespace,
+    name: Value,
+    mut attributes: Option<Vec<Attribute>>,
+    children: Option<Vec<Node>>,
+    is_written_self_closing: bool,
+) -> Result<Node> {
+    namespace.adjust_attributes(&mut attributes)?;
+    if !parent_namespace.is_foreign() {
+        namespace.add_xmlns_attributes(&mut attributes, &children);
+    }
+
+    let name = namespace.adjust_element_name(name);
+    let is_self_closing = match name {
+        // The tag is only known at runtime, so it is kept as it was written.
+        Value::Code(_) => is_written_self_closing,
+        Value::Text(_) => {
+            children.is_none() && (namespace.is_foreign() || is_void_element_name(&name))
+        }
+    };
+
+    if is_self_closing {
+        Ok(Node::SelfClosing { name, attributes })
     } else {
         Ok(Node::Open {
-            name: opening_tag_name,
+            name,
             attributes,
             children,
         })
     }
 }
 
+/// Returns true if the body is a lone string literal, i.e. `<style>r#"a > b {}"#</style>`.
+fn is_next_raw_text_body(input: &mut TokenIteratorVec) -> bool {
+    let is_string_literal = match input.peek() {
+        Some(TokenTree::Literal(literal)) => match Lit::new(literal.clone()) {
+            Lit::Str(_) => true,
+            _ => false,
+        },
+        _ => false,
+    };
+
+    is_string_literal
+        && input.is_lookahead_punct(LEFT_ANGLE, 1)
+        && input.is_lookahead_punct(FORWARD_SLASH, 2)
+}
+
+/// Parses the string literal body of a raw text element,
+/// keeping the contents exactly as written.
+fn parse_raw_text_body(input: &mut TokenIteratorVec) -> Result<String> {
+    match input.chomp()? {
+        TokenTree::Literal(literal) => match Lit::new(literal) {
+            Lit::Str(text) => Ok(text.value()),
+            _ => Err(Error::UnexpectedToken),
+        },
+        _ => Err(Error::UnexpectedToken),
+    }
+}
+
 fn parse_node_text(input: &mut TokenIteratorVec) -> Result<Node> {
-    Ok(Node::Text(parse_text(input, &TAG_OPENING_LOOKAHEAD)?))
+    Ok(Node::Text(parse_text(
+        input,
+        &TAG_OPENING_LOOKAHEAD,
+        TextContext::Element,
+    )?))
 }
 
 fn parse_attributes(input: &mut TokenIteratorVec) -> Result<Option<Vec<Attribute>>> {
@@ -253,10 +693,22 @@ fn parse_attribute_value(input: &mut TokenIteratorVec) -> Result<Value> {
     if input.is_brace_group() {
         Ok(Value::Code(input.chomp_brace_group()?))
     } else {
-        Ok(Value::Text(input.chomp_literal()?))
+        let span = input.peek_span().unwrap_or_else(Span::call_site);
+        let value = input.chomp_literal()?;
+        Ok(Value::Text(decode_literal_entities(&value, span)?))
     }
 }
 
+/// Decodes entities in a literal, i.e. the `&amp;` in `title="Fish &amp; Chips"`.
+fn decode_literal_entities(literal: &str, span: Span) -> Result<String> {
+    decode_entities(literal).map_err(|err| {
+        Error::UnknownEntity(UnknownEntityError {
+            entity: err.entity,
+            span,
+        })
+    })
+}
+
 fn parse_name(input: &mut TokenIteratorVec) -> Result<Value> {
     match parse_maybe_name(input)? {
         Some(name) => Ok(name),
@@ -312,7 +764,7 @@ fn parse_maybe_name(input: &mut TokenIteratorVec) -> Result<Option<Value>> {
 /// Finds and grabs all child nodes, and then returns them in a Vec.
 /// `stopping_lookaheads` is for telling it what puncts to look for,
 /// to know to stop parsing. For a HTML tag this is `</`, and for a comment this is `-->`.
-fn parse_children(input: &mut TokenIteratorVec) -> Result<Option<Vec<Node>>> {
+fn parse_children(input: &mut TokenIteratorVec, namespace: Namespace) -> Result<Option<Vec<Node>>> {
     let mut maybe_children = None;
 
     loop {
@@ -320,89 +772,52 @@ fn parse_children(input: &mut TokenIteratorVec) -> Result<Option<Vec<Node>>> {
             return Err(Error::MoreTokensExpected);
         }
 
+        let children = maybe_children.get_or_insert_with(Vec::new);
+        push_edge_spacing(children, input.last_span(), input.peek_span());
+
         if input.is_lookahead_puncts(&TAG_CLOSING_LOOKAHEAD) {
+            if children.is_empty() {
+                return Ok(None);
+            }
+
             return Ok(maybe_children);
         }
 
-        let child = parse_node(input)?;
-
-        match maybe_children.as_mut() {
-            Some(children) => children.push(child),
-            None => maybe_children = Some(vec![child]),
-        }
+        let child = parse_node(input, namespace)?;
+        push_child(children, child);
     }
 }
 
-fn parse_text(input: &mut TokenIteratorVec, stopping_lookaheads: &[char]) -> Result<String> {
-    let mut text = String::new();
-    let mut last_spacing_rules = (false, false);
+fn parse_text(
+    input: &mut TokenIteratorVec,
+    stopping_lookaheads: &[char],
+    context: TextContext,
+) -> Result<String> {
+    let mut text = TextRun::new();
 
     while !input.is_brace_group()
         && !input.is_empty()
         && !input.is_lookahead_puncts(stopping_lookaheads)
+        && !is_next_group_with_nodes(input, context)
     {
-        let next = input.chomp()?;
-
-        let next_spacing_rules = spacing_rules(&next);
-        match (last_spacing_rules, next_spacing_rules) {
-            ((_, true), (true, _)) => {
-                write!(text, " ")?;
-            }
-            _ => {}
-        }
-        last_spacing_rules = next_spacing_rules;
-
-        match next {
-            TokenTree::Ident(ident) => {
-                write!(text, "{}", ident)?;
-            }
-            TokenTree::Punct(punct) => {
-                write!(text, "{}", punct)?;
-            }
-            TokenTree::Literal(literal) => {
-                let literal_string = literal.to_string();
-                if literal_string.starts_with('"') {
-                    let literal_substring = &literal_string.as_str()[1..literal_string.len() - 1];
-                    write!(text, "{}", literal_substring)?;
-                } else {
-                    write!(text, "{}", literal_string)?;
-                }
-            }
-            TokenTree::Group(_) => unreachable!(),
-        }
+        text.push_token(input.chomp()?);
     }
 
-    Ok(text)
-}
-
-fn spacing_rules(tree: &TokenTree) -> (bool, bool) {
-    match tree {
-        TokenTree::Ident(_) => (true, true),
-        TokenTree::Literal(_) => (true, true),
-        TokenTree::Group(_) => (true, true),
-        TokenTree::Punct(punct) => char_spacing_rules(punct.as_char()),
+    match context {
+        TextContext::Element => text.into_decoded_string(),
+        // Comments are written out as they are.
+        TextContext::Comment => Ok(text.into_string()),
     }
 }
 
-fn char_spacing_rules(c: char) -> (bool, bool) {
-    match c {
-        '.' => (false, true),
-        ',' => (false, true),
-        ';' => (false, true),
-        ':' => (false, true),
-        '?' => (false, true),
-        '!' => (false, true),
-        '%' => (false, true),
-        ')' => (false, true),
-        ']' => (false, true),
-        '>' => (false, true),
-        '}' => (false, true),
-        '(' => (true, false),
-        '[' => (true, false),
-        '{' => (true, false),
-        '<' => (true, false),
-        '-' => (false, false),
-        _ => (true, true),
+/// Elements whose contents are text, and never HTML.
+/// i.e. `<script>`, `<style>`, `<textarea>`, and `<title>`.
+fn is_raw_text_name(name: &Value) -> bool {
+    match name {
+        Value::Code(_) => false,
+        Value::Text(name) => RAW_TEXT_ELEMENTS
+            .iter()
+            .any(|element| name.eq_ignore_ascii_case(element)),
     }
 }
 
@@ -483,6 +898,133 @@ mod parse {
         }
     }
 
+    #[cfg(test)]
+    mod cdata {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+        use ::std::str::FromStr;
+
+        #[test]
+        fn it_should_keep_text_as_written() -> Result<()> {
+            let expected = Node::CData {
+                children: Some(vec![Node::Text("a < b && c".to_string())]),
+            };
+
+            let nodes = parse(TokenStream::from_str("<![CDATA[ a < b && c ]]>").unwrap())?;
+            assert_eq!(nodes, expected);
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_parse_code() -> Result<()> {
+            let code = quote! {
+                <description><![CDATA[Read {summary}]]></description>
+            };
+
+            let expected = Node::Open {
+                name: Value::Text("description".to_string()),
+                attributes: None,
+                children: Some(vec![Node::CData {
+                    children: Some(vec![
+                        Node::Text("Read".to_string()),
+                        Node::Code(quote! { summary }),
+                    ]),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_keep_string_literals_verbatim() -> Result<()> {
+            let code = quote! {
+                <![CDATA[r#"<p>Fish &amp; Chips</p>"#]]>
+            };
+
+            let expected = Node::CData {
+                children: Some(vec![Node::Text("<p>Fish &amp; Chips</p>".to_string())]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_support_empty_cdata() -> Result<()> {
+            let code = quote! {
+                <![CDATA[]]>
+            };
+
+            assert_eq_nodes(code, Node::CData { children: None })
+        }
+
+        #[test]
+        fn it_should_still_parse_doctypes() -> Result<()> {
+            let code = quote! {
+                <![if IE]>
+            };
+
+            assert_eq!(parse(code).err().unwrap(), Error::ExpectedName);
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod processing_instructions {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+
+        #[test]
+        fn it_should_parse_xml_declarations() -> Result<()> {
+            let code = quote! {
+                <?xml version="1.0" encoding="UTF-8"?>
+            };
+
+            let expected = Node::ProcessingInstruction {
+                target: Value::Text("xml".to_string()),
+                attributes: Some(vec![
+                    Attribute {
+                        key: Value::Text("version".to_string()),
+                        value: Some(Value::Text("1.0".to_string())),
+                    },
+                    Attribute {
+                        key: Value::Text("encoding".to_string()),
+                        value: Some(Value::Text("UTF-8".to_string())),
+                    },
+                ]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_parse_hyphenated_targets_and_code() -> Result<()> {
+            let code = quote! {
+                <?xml-stylesheet href={url}?>
+            };
+
+            let expected = Node::ProcessingInstruction {
+                target: Value::Text("xml-stylesheet".to_string()),
+                attributes: Some(vec![Attribute {
+                    key: Value::Text("href".to_string()),
+                    value: Some(Value::Code(quote! { url })),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_error_without_a_closing_question_mark() {
+            let code = quote! {
+                <?xml version="1.0">
+            };
+
+            assert_eq!(parse(code).err().unwrap(), Error::UnexpectedToken);
+        }
+    }
+
     #[cfg(test)]
     mod comments {
         use super::*;
@@ -616,11 +1158,11 @@ mod parse {
     #[test]
     fn it_should_return_node_for_self_closing_tag() -> Result<()> {
         let code = quote! {
-          <div/>
+          <br/>
         };
 
         let expected = Node::SelfClosing {
-            name: Value::Text("div".to_string()),
+            name: Value::Text("br".to_string()),
             attributes: None,
         };
 
@@ -628,13 +1170,13 @@ mod parse {
     }
 
     #[test]
-    fn it_should_return_node_for_an_empty_tag() -> Result<()> {
+    fn it_should_close_self_closing_html_elements_which_are_not_void() -> Result<()> {
         let code = quote! {
-          <h1></h1>
+          <div/>
         };
 
         let expected = Node::Open {
-            name: Value::Text("h1".to_string()),
+            name: Value::Text("div".to_string()),
             attributes: None,
             children: None,
         };
@@ -643,15 +1185,44 @@ mod parse {
     }
 
     #[test]
-    fn it_should_return_an_error_on_mismatched_closing_node() {
+    fn it_should_self_close_empty_void_elements() -> Result<()> {
         let code = quote! {
-          <div></p>
+          <br></br>
         };
 
-        let error = parse(code.into()).err().unwrap();
-        assert_eq!(error, Error::MismatchedClosingTagName);
-    }
-
+        let expected = Node::SelfClosing {
+            name: Value::Text("br".to_string()),
+            attributes: None,
+        };
+
+        assert_eq_nodes(code, expected)
+    }
+
+    #[test]
+    fn it_should_return_node_for_an_empty_tag() -> Result<()> {
+        let code = quote! {
+          <h1></h1>
+        };
+
+        let expected = Node::Open {
+            name: Value::Text("h1".to_string()),
+            attributes: None,
+            children: None,
+        };
+
+        assert_eq_nodes(code, expected)
+    }
+
+    #[test]
+    fn it_should_return_an_error_on_mismatched_closing_node() {
+        let code = quote! {
+          <div></p>
+        };
+
+        let error = parse(code.into()).err().unwrap();
+        assert_eq!(error, Error::MismatchedClosingTagName);
+    }
+
     #[test]
     fn it_should_parse_lone_attributes() -> Result<()> {
         let code = quote! {
@@ -683,11 +1254,11 @@ mod parse {
     #[test]
     fn it_should_parse_lone_attributes_on_self_closing_tags() -> Result<()> {
         let code = quote! {
-          <button is_disabled />
+          <input is_disabled />
         };
 
         let expected = Node::SelfClosing {
-            name: Value::Text("button".to_string()),
+            name: Value::Text("input".to_string()),
             attributes: Some(vec![Attribute {
                 key: Value::Text("is_disabled".to_string()),
                 value: None,
@@ -718,14 +1289,14 @@ mod parse {
     #[test]
     fn it_should_parse_key_value_attributes_on_self_closing_tags() -> Result<()> {
         let code = quote! {
-            <button type="input" />
+            <input type="text" />
         };
 
         let expected = Node::SelfClosing {
-            name: Value::Text("button".to_string()),
+            name: Value::Text("input".to_string()),
             attributes: Some(vec![Attribute {
                 key: Value::Text("type".to_string()),
-                value: Some(Value::Text("input".to_string())),
+                value: Some(Value::Text("text".to_string())),
             }]),
         };
 
@@ -735,11 +1306,11 @@ mod parse {
     #[test]
     fn it_should_parse_key_value_code_attributes() -> Result<()> {
         let code = quote! {
-            <button type={base_class.child("el")} />
+            <input type={base_class.child("el")} />
         };
 
         let expected = Node::SelfClosing {
-            name: Value::Text("button".to_string()),
+            name: Value::Text("input".to_string()),
             attributes: Some(vec![Attribute {
                 key: Value::Text("type".to_string()),
                 value: Some(Value::Code(quote! {
@@ -755,7 +1326,7 @@ mod parse {
     fn it_should_parse_child_nodes() -> Result<()> {
         let code = quote! {
             <div>
-                <h1/>
+                <hr/>
             </div>
         };
 
@@ -763,7 +1334,7 @@ mod parse {
             name: Value::Text("div".to_string()),
             attributes: None,
             children: Some(vec![Node::SelfClosing {
-                name: Value::Text("h1".to_string()),
+                name: Value::Text("hr".to_string()),
                 attributes: None,
             }]),
         };
@@ -801,9 +1372,10 @@ mod parse {
                         children: None,
                     }]),
                 },
-                Node::SelfClosing {
+                Node::Open {
                     name: Value::Text("article".to_string()),
                     attributes: None,
+                    children: None,
                 },
             ]),
         };
@@ -963,6 +1535,587 @@ mod parse {
         }
     }
 
+    #[cfg(test)]
+    mod whitespace {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+        use ::std::str::FromStr;
+
+        fn assert_eq_source_nodes(source: &str, expected_nodes: Node) -> Result<()> {
+            let nodes = parse(TokenStream::from_str(source).unwrap())?;
+            assert_eq!(nodes, expected_nodes);
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_keep_spacing_next_to_tags_on_the_same_line() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::Text(" Hello , ".to_string()),
+                    Node::Open {
+                        name: Value::Text("b".to_string()),
+                        attributes: None,
+                        children: Some(vec![Node::Text("world".to_string())]),
+                    },
+                    Node::Text(" !".to_string()),
+                ]),
+            };
+
+            assert_eq_source_nodes("<p> Hello , <b>world</b> !</p>", expected)
+        }
+
+        #[test]
+        fn it_should_remove_spacing_next_to_tags_across_lines() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::Text("Hello a - b and c".to_string()),
+                    Node::Open {
+                        name: Value::Text("b".to_string()),
+                        attributes: None,
+                        children: Some(vec![Node::Text("world".to_string())]),
+                    },
+                ]),
+            };
+
+            assert_eq_source_nodes(
+                "<p>
+                    Hello a - b
+                    and c
+                    <b>world</b>
+                </p>",
+                expected,
+            )
+        }
+
+        #[test]
+        fn it_should_keep_spacing_between_tags_on_the_same_line() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::SelfClosing {
+                        name: Value::Text("br".to_string()),
+                        attributes: None,
+                    },
+                    Node::Text("  ".to_string()),
+                    Node::SelfClosing {
+                        name: Value::Text("hr".to_string()),
+                        attributes: None,
+                    },
+                ]),
+            };
+
+            assert_eq_source_nodes("<p><br/>  <hr/></p>", expected)
+        }
+
+        #[test]
+        fn it_should_keep_spacing_next_to_code_on_the_same_line() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::Code(quote! { a }),
+                    Node::Text(" and ".to_string()),
+                    Node::Code(quote! { b }),
+                ]),
+            };
+
+            assert_eq_source_nodes("<p>{a} and {b}</p>", expected)
+        }
+
+        #[test]
+        fn it_should_parse_code_within_brackets() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::Text("Basket ".to_string()),
+                    Node::Fragment {
+                        children: vec![
+                            Node::Text("(".to_string()),
+                            Node::Code(quote! { count }),
+                            Node::Text(" items)".to_string()),
+                        ],
+                    },
+                ]),
+            };
+
+            assert_eq_source_nodes("<p>Basket ({count} items)</p>", expected)
+        }
+
+        #[test]
+        fn it_should_parse_tags_within_brackets() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Fragment {
+                    children: vec![
+                        Node::Text("[ ".to_string()),
+                        Node::Open {
+                            name: Value::Text("b".to_string()),
+                            attributes: None,
+                            children: Some(vec![Node::Text("new".to_string())]),
+                        },
+                        Node::Text(" ]".to_string()),
+                    ],
+                }]),
+            };
+
+            assert_eq_source_nodes("<p>[ <b>new</b> ]</p>", expected)
+        }
+    }
+
+    #[cfg(test)]
+    mod raw_text {
+        use super::*;
+
+        #[test]
+        fn it_should_keep_raw_string_bodies_verbatim() -> Result<()> {
+            let code = quote! {
+                <style>r#"a > b::after { content: "-"; }"#</style>
+            };
+
+            let expected = Node::Open {
+                name: Value::Text("style".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Text(
+                    r#"a > b::after { content: "-"; }"#.to_string(),
+                )]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_unescape_string_bodies() -> Result<()> {
+            let code = quote! {
+                <script>"let a = \"</b>\";\nlet b = 1 - 2;"</script>
+            };
+
+            let expected = Node::Open {
+                name: Value::Text("script".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Text(
+                    "let a = \"</b>\";\nlet b = 1 - 2;".to_string(),
+                )]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+    }
+
+    mod entities {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+        use ::std::str::FromStr;
+
+        fn parse_source(source: &str) -> Result<Node> {
+            parse(TokenStream::from_str(source).unwrap())
+        }
+
+        #[test]
+        fn it_should_decode_entities_in_text() -> Result<()> {
+            let expected = Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Text(
+                    "Fish & Chips\u{a0}\u{a9} \u{2014} <b>".to_string(),
+                )]),
+            };
+
+            assert_eq!(
+                parse_source("<p>Fish &amp; Chips&nbsp;&copy; &#x2014; &lt;b&gt;</p>")?,
+                expected
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_decode_entities_in_attribute_values() -> Result<()> {
+            let expected = Node::SelfClosing {
+                name: Value::Text("img".to_string()),
+                attributes: Some(vec![Attribute {
+                    key: Value::Text("alt".to_string()),
+                    value: Some(Value::Text("Fish & Chips \u{a9}".to_string())),
+                }]),
+            };
+
+            assert_eq!(
+                parse_source(r#"<img alt="Fish &amp; Chips &copy;" />"#)?,
+                expected
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_keep_comments_as_written() -> Result<()> {
+            let expected = Node::Comment {
+                children: Some(vec![Node::Text("a &amp; b".to_string())]),
+            };
+
+            assert_eq!(parse_source("<!-- a &amp; b -->")?, expected);
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_error_on_unknown_entities_in_text() {
+            let error = parse_source("<p>Fish &nbps; Chips</p>").err().unwrap();
+
+            match error {
+                Error::UnknownEntity(err) => {
+                    assert_eq!(err.entity, "&nbps;");
+                    assert_eq!(err.span.start().column, 9);
+                }
+                err => panic!("expected an unknown entity error, found {:?}", err),
+            }
+        }
+
+        #[test]
+        fn it_should_error_on_unknown_entities_in_attribute_values() {
+            let error = parse_source(r#"<img alt="&nbps;" />"#).err().unwrap();
+
+            assert_eq!(
+                error,
+                Error::UnknownEntity(UnknownEntityError {
+                    entity: "&nbps;".to_string(),
+                    span: Span::call_site(),
+                })
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod namespaces {
+        use super::*;
+        use crate::rsx::namespace::SVG_URI;
+        use crate::rsx::namespace::XLINK_URI;
+        use ::pretty_assertions::assert_eq;
+
+        fn text(text: &str) -> Value {
+            Value::Text(text.to_string())
+        }
+
+        fn attribute(key: &str, value: &str) -> Attribute {
+            Attribute {
+                key: text(key),
+                value: Some(text(value)),
+            }
+        }
+
+        #[test]
+        fn it_should_add_xmlns_to_svg() -> Result<()> {
+            let code = quote! {
+                <svg viewBox="0 0 10 10"><circle r="5" /></svg>
+            };
+
+            let expected = Node::Open {
+                name: text("svg"),
+                attributes: Some(vec![
+                    attribute("xmlns", SVG_URI),
+                    attribute("viewBox", "0 0 10 10"),
+                ]),
+                children: Some(vec![Node::SelfClosing {
+                    name: text("circle"),
+                    attributes: Some(vec![attribute("r", "5")]),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_keep_xmlns_when_given() -> Result<()> {
+            let code = quote! {
+                <svg xmlns="http://www.w3.org/2000/svg"></svg>
+            };
+
+            let expected = Node::SelfClosing {
+                name: text("svg"),
+                attributes: Some(vec![attribute("xmlns", SVG_URI)]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_add_xmlns_xlink_when_xlink_is_used() -> Result<()> {
+            let code = quote! {
+                <svg><g><use xlink:href="#a"></use></g></svg>
+            };
+
+            let expected = Node::Open {
+                name: text("svg"),
+                attributes: Some(vec![
+                    attribute("xmlns", SVG_URI),
+                    attribute("xmlns:xlink", XLINK_URI),
+                ]),
+                children: Some(vec![Node::Open {
+                    name: text("g"),
+                    attributes: None,
+                    children: Some(vec![Node::SelfClosing {
+                        name: text("use"),
+                        attributes: Some(vec![attribute("xlink:href", "#a")]),
+                    }]),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_not_treat_camel_case_svg_elements_as_components() -> Result<()> {
+            let code = quote! {
+                <svg xmlns="http://www.w3.org/2000/svg">
+                    <LinearGradient gradientunits="userSpaceOnUse"></LinearGradient>
+                    <Icon />
+                </svg>
+            };
+
+            let expected = Node::Open {
+                name: text("svg"),
+                attributes: Some(vec![attribute("xmlns", SVG_URI)]),
+                children: Some(vec![
+                    Node::SelfClosing {
+                        name: text("linearGradient"),
+                        attributes: Some(vec![attribute("gradientUnits", "userSpaceOnUse")]),
+                    },
+                    Node::SelfClosingComponent {
+                        name: "Icon".to_string(),
+                        attributes: None,
+                    },
+                ]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_use_html_rules_within_foreign_object() -> Result<()> {
+            let code = quote! {
+                <svg xmlns="http://www.w3.org/2000/svg">
+                    <foreignObject><div /></foreignObject>
+                </svg>
+            };
+
+            let expected = Node::Open {
+                name: text("svg"),
+                attributes: Some(vec![attribute("xmlns", SVG_URI)]),
+                children: Some(vec![Node::Open {
+                    name: text("foreignObject"),
+                    attributes: None,
+                    children: Some(vec![Node::Open {
+                        name: text("div"),
+                        attributes: None,
+                        children: None,
+                    }]),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_error_on_unknown_attribute_namespaces() {
+            let code = quote! {
+                <svg><a foo:href="#a"></a></svg>
+            };
+
+            let error = parse(code).err().unwrap();
+            assert_eq!(
+                error,
+                Error::UnknownAttributeNamespace("foo:href".to_string())
+            );
+        }
+
+        #[test]
+        fn it_should_not_check_attribute_namespaces_in_html() -> Result<()> {
+            let code = quote! {
+                <p x-on:click="open = true"></p>
+            };
+
+            let expected = Node::Open {
+                name: text("p"),
+                attributes: Some(vec![attribute("x-on:click", "open = true")]),
+                children: None,
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+    }
+
+    #[cfg(test)]
+    mod slots {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+
+        #[test]
+        fn it_should_parse_where_slots_are_written() -> Result<()> {
+            let code = quote! {
+                <head><slot:head /></head>
+            };
+
+            let expected = Node::Open {
+                name: Value::Text("head".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Slot {
+                    name: "head".to_string(),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_parse_slot_content() -> Result<()> {
+            let code = quote! {
+                <slot:head><title>Home</title></slot:head>
+            };
+
+            let expected = Node::SlotContent {
+                name: "head".to_string(),
+                children: Some(vec![Node::Open {
+                    name: Value::Text("title".to_string()),
+                    attributes: None,
+                    children: Some(vec![Node::Text("Home".to_string())]),
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_error_on_mismatched_slot_names() {
+            let code = quote! {
+                <slot:head></slot:body>
+            };
+
+            assert_eq!(parse(code.into()), Err(Error::MismatchedClosingTagName));
+        }
+
+        #[test]
+        fn it_should_error_on_slot_attributes() {
+            let code = quote! {
+                <slot:head class="a" />
+            };
+
+            assert_eq!(parse(code.into()), Err(Error::SlotAttributesFound));
+        }
+    }
+
+    #[cfg(test)]
+    mod error_boundary {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+
+        #[test]
+        fn it_should_parse_error_boundaries() -> Result<()> {
+            let code = quote! {
+                <ErrorBoundary fallback={|err| rsx! { <p>Sorry</p> }} catch_panics>
+                    <UserCard />
+                </ErrorBoundary>
+            };
+
+            let expected = Node::ErrorBoundary {
+                fallback: quote! { |err| rsx! { <p>Sorry</p> } },
+                catch_panics: true,
+                children: Some(vec![Node::SelfClosingComponent {
+                    name: "UserCard".to_string(),
+                    attributes: None,
+                }]),
+            };
+
+            assert_eq_nodes(code, expected)
+        }
+
+        #[test]
+        fn it_should_error_without_a_fallback() {
+            let code = quote! {
+                <ErrorBoundary catch_panics />
+            };
+
+            assert_eq!(parse(code.into()), Err(Error::ErrorBoundaryFallbackMissing));
+        }
+
+        #[test]
+        fn it_should_error_on_other_attributes() {
+            let code = quote! {
+                <ErrorBoundary fallback={fallback} class="a" />
+            };
+
+            assert_eq!(
+                parse(code.into()),
+                Err(Error::ErrorBoundaryAttributeFound("class".to_string()))
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod crate_argument {
+        use super::*;
+        use ::pretty_assertions::assert_eq;
+
+        #[test]
+        fn it_should_return_no_path_when_not_given() -> Result<()> {
+            let code = quote! {
+                <div></div>
+            };
+
+            let (crate_path, rest) = parse_crate_argument(code.clone())?;
+
+            assert!(crate_path.is_none());
+            assert!(token_stream_eq(&code, &rest));
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_split_off_the_crate_path() -> Result<()> {
+            let code = quote! {
+                crate = ::my_fw::html; <div></div>
+            };
+
+            let (crate_path, rest) = parse_crate_argument(code)?;
+
+            assert!(token_stream_eq(
+                &quote! { ::my_fw::html },
+                &crate_path.unwrap()
+            ));
+            assert!(token_stream_eq(&quote! { <div></div> }, &rest));
+
+            Ok(())
+        }
+
+        #[test]
+        fn it_should_error_when_semicolon_is_missing() {
+            let code = quote! {
+                crate = ::my_fw::html
+            };
+
+            let result = parse_crate_argument(code);
+
+            assert_eq!(result.err(), Some(Error::ExpectedCratePathSemicolon));
+        }
+
+        #[test]
+        fn it_should_error_when_path_is_empty() {
+            let code = quote! {
+                crate = ; <div></div>
+            };
+
+            let result = parse_crate_argument(code);
+
+            assert_eq!(result.err(), Some(Error::ExpectedCratePath));
+        }
+    }
+
     fn assert_eq_nodes(tokens: TokenStream, expected_nodes: Node) -> Result<()> {
         let nodes = parse(tokens.into())?;
         assert_eq!(nodes, expected_nodes);
@@ -1,14 +1,25 @@
 mod ast;
+mod entities;
 mod error;
+mod escaping;
 mod grammar;
+mod namespace;
 mod output;
+mod prerender;
+mod translation_keys;
+mod whitespace;
 
 pub use self::error::*;
+pub use self::translation_keys::TRANSLATION_KEYS_ENV;
 
+use crate::util::default_crate_path;
 use ::proc_macro2::TokenStream;
 
 pub fn parse(old_stream: TokenStream) -> Result<TokenStream> {
     let stream = TokenStream::from(old_stream);
+    let (maybe_crate_path, stream) = grammar::parse_crate_argument(stream)?;
+    let crate_path = maybe_crate_path.unwrap_or_else(default_crate_path);
     let ast = grammar::parse(stream)?;
-    Ok(output::build(ast))
+    translation_keys::write_translation_keys(&ast)?;
+    Ok(output::build(ast, &crate_path))
 }
@@ -0,0 +1,368 @@
+//! SVG and MathML, which follow different rules to the HTML around them.
+//!
+//! Within `<svg>` and `<math>` names are case sensitive, any empty element can be self-closing,
+//! and attributes can be namespaced, i.e. `xlink:href`.
+
+use crate::rsx::ast::Attribute;
+use crate::rsx::ast::Node;
+use crate::rsx::ast::Value;
+use crate::rsx::error::Error;
+use crate::rsx::error::Result;
+
+pub const SVG_URI: &str = "http://www.w3.org/2000/svg";
+pub const MATHML_URI: &str = "http://www.w3.org/1998/Math/MathML";
+pub const XLINK_URI: &str = "http://www.w3.org/1999/xlink";
+
+/// Prefixes which can be used on attributes within SVG and MathML.
+static ATTRIBUTE_PREFIXES: &[&str] = &["xlink", "xml", "xmlns"];
+
+// Elements which can be written as `<br>` or `<br/>`. The same ones as `core::parse`.
+include!("../../../core/src/parse/void_elements.rs");
+
+/// SVG elements with camelCase names.
+/// See https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inforeign
+static SVG_ELEMENTS: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "clipPath",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "foreignObject",
+    "glyphRef",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+];
+
+/// SVG attributes with camelCase names.
+static SVG_ATTRIBUTES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
+
+/// MathML attributes with camelCase names.
+static MATHML_ATTRIBUTES: &[&str] = &["definitionURL"];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Namespace {
+    Html,
+    Svg,
+    MathMl,
+}
+
+impl Namespace {
+    /// The namespace of an element, when it is found within this namespace.
+    /// i.e. `<svg>` within HTML is SVG.
+    pub fn of_element(self, name: &Value) -> Self {
+        match (self, name) {
+            (Self::Html, Value::Text(name)) if name.eq_ignore_ascii_case("svg") => Self::Svg,
+            (Self::Html, Value::Text(name)) if name.eq_ignore_ascii_case("math") => Self::MathMl,
+            _ => self,
+        }
+    }
+
+    /// The namespace of the children of an element in this namespace.
+    /// This is HTML within `<foreignObject>`.
+    pub fn of_children(self, name: &Value) -> Self {
+        match (self, name) {
+            (Self::Svg, Value::Text(name)) if name.eq_ignore_ascii_case("foreignObject") => {
+                Self::Html
+            }
+            _ => self,
+        }
+    }
+
+    pub fn is_foreign(self) -> bool {
+        self != Self::Html
+    }
+
+    pub fn uri(self) -> Option<&'static str> {
+        match self {
+            Self::Html => None,
+            Self::Svg => Some(SVG_URI),
+            Self::MathMl => Some(MATHML_URI),
+        }
+    }
+
+    /// Returns the name with the case the namespace expects,
+    /// if it is one of the known camelCase elements. i.e. `lineargradient` as `linearGradient`.
+    pub fn known_element_name(self, name: &Value) -> Option<&'static str> {
+        match self {
+            Self::Svg => find_ignoring_case(SVG_ELEMENTS, name),
+            Self::Html | Self::MathMl => None,
+        }
+    }
+
+    /// Fixes the case of camelCase element names, i.e. `<clippath>` as `<clipPath>`.
+    pub fn adjust_element_name(self, name: Value) -> Value {
+        match self.known_element_name(&name) {
+            Some(known_name) => Value::Text(known_name.to_string()),
+            None => name,
+        }
+    }
+
+    /// Fixes the case of camelCase attribute keys, i.e. `viewbox` as `viewBox`,
+    /// and checks the prefix of namespaced keys, i.e. `xlink:href`.
+    ///
+    /// HTML attributes are left as they are.
+    pub fn adjust_attributes(self, maybe_attributes: &mut Option<Vec<Attribute>>) -> Result<()> {
+        let known_attributes = match self {
+            Self::Html => return Ok(()),
+            Self::Svg => SVG_ATTRIBUTES,
+            Self::MathMl => MATHML_ATTRIBUTES,
+        };
+
+        for attribute in maybe_attributes.iter_mut().flatten() {
+            if let Value::Text(key) = &attribute.key {
+                if let Some((prefix, _)) = key.split_once(':') {
+                    if !ATTRIBUTE_PREFIXES.contains(&prefix) {
+                        return Err(Error::UnknownAttributeNamespace(key.to_string()));
+                    }
+                }
+            }
+
+            if let Some(known_key) = find_ignoring_case(known_attributes, &attribute.key) {
+                attribute.key = Value::Text(known_key.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the `xmlns` attributes needed on the outermost `<svg>` or `<math>` element,
+    /// unless they have been given.
+    pub fn add_xmlns_attributes(
+        self,
+        maybe_attributes: &mut Option<Vec<Attribute>>,
+        maybe_children: &Option<Vec<Node>>,
+    ) {
+        let uri = match self.uri() {
+            Some(uri) => uri,
+            None => return,
+        };
+
+        let attributes = maybe_attributes.get_or_insert_with(Vec::new);
+        let mut xmlns_attributes = vec![];
+
+        if !has_attribute(attributes, "xmlns") {
+            xmlns_attributes.push(xmlns_attribute("xmlns", uri));
+        }
+
+        let uses_xlink = attributes
+            .iter()
+            .any(|attribute| is_xlink_attribute(&attribute.key))
+            || maybe_children.iter().flatten().any(uses_xlink_attributes);
+        if uses_xlink && !has_attribute(attributes, "xmlns:xlink") {
+            xmlns_attributes.push(xmlns_attribute("xmlns:xlink", XLINK_URI));
+        }
+
+        attributes.splice(0..0, xmlns_attributes);
+    }
+}
+
+/// Returns true for elements such as `<br>`, which never have children.
+pub fn is_void_element_name(name: &Value) -> bool {
+    match name {
+        Value::Code(_) => false,
+        Value::Text(name) => VOID_ELEMENTS
+            .iter()
+            .any(|element| name.eq_ignore_ascii_case(element)),
+    }
+}
+
+fn find_ignoring_case(names: &[&'static str], name: &Value) -> Option<&'static str> {
+    match name {
+        Value::Code(_) => None,
+        Value::Text(name) => names
+            .iter()
+            .find(|known_name| name.eq_ignore_ascii_case(known_name))
+            .copied(),
+    }
+}
+
+fn has_attribute(attributes: &[Attribute], key: &str) -> bool {
+    attributes
+        .iter()
+        .any(|attribute| matches!(&attribute.key, Value::Text(text) if text == key))
+}
+
+fn xmlns_attribute(key: &str, uri: &str) -> Attribute {
+    Attribute {
+        key: Value::Text(key.to_string()),
+        value: Some(Value::Text(uri.to_string())),
+    }
+}
+
+fn is_xlink_attribute(key: &Value) -> bool {
+    matches!(key, Value::Text(key) if key.starts_with("xlink:"))
+}
+
+fn uses_xlink_attributes(node: &Node) -> bool {
+    match node {
+        Node::SelfClosing { attributes, .. } => attributes
+            .iter()
+            .flatten()
+            .any(|attribute| is_xlink_attribute(&attribute.key)),
+        Node::Open {
+            attributes,
+            children,
+            ..
+        } => {
+            attributes
+                .iter()
+                .flatten()
+                .any(|attribute| is_xlink_attribute(&attribute.key))
+                || children.iter().flatten().any(uses_xlink_attributes)
+        }
+        Node::Fragment { children } => children.iter().any(uses_xlink_attributes),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod namespace {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    fn text(text: &str) -> Value {
+        Value::Text(text.to_string())
+    }
+
+    #[test]
+    fn it_should_enter_svg_and_mathml() {
+        assert_eq!(Namespace::Svg, Namespace::Html.of_element(&text("svg")));
+        assert_eq!(Namespace::MathMl, Namespace::Html.of_element(&text("math")));
+        assert_eq!(Namespace::Html, Namespace::Html.of_element(&text("div")));
+        assert_eq!(Namespace::Svg, Namespace::Svg.of_element(&text("g")));
+    }
+
+    #[test]
+    fn it_should_return_to_html_within_foreign_object() {
+        assert_eq!(
+            Namespace::Html,
+            Namespace::Svg.of_children(&text("foreignObject"))
+        );
+        assert_eq!(Namespace::Svg, Namespace::Svg.of_children(&text("g")));
+    }
+
+    #[test]
+    fn it_should_fix_the_case_of_svg_names() -> Result<()> {
+        assert_eq!(
+            text("linearGradient"),
+            Namespace::Svg.adjust_element_name(text("LinearGradient"))
+        );
+        assert_eq!(
+            text("div"),
+            Namespace::Html.adjust_element_name(text("div"))
+        );
+
+        let mut attributes = Some(vec![Attribute {
+            key: text("viewbox"),
+            value: Some(text("0 0 10 10")),
+        }]);
+        Namespace::Svg.adjust_attributes(&mut attributes)?;
+        assert_eq!(
+            Some(text("viewBox")),
+            attributes.map(|mut a| a.remove(0).key)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_error_on_unknown_attribute_namespaces() {
+        let mut attributes = Some(vec![Attribute {
+            key: text("foo:href"),
+            value: None,
+        }]);
+
+        assert_eq!(
+            Err(Error::UnknownAttributeNamespace("foo:href".to_string())),
+            Namespace::Svg.adjust_attributes(&mut attributes)
+        );
+        assert_eq!(Ok(()), Namespace::Html.adjust_attributes(&mut attributes));
+    }
+}
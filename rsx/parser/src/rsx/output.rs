@@ -1,58 +1,117 @@
 use crate::rsx::ast::Attribute;
 use crate::rsx::ast::Node;
 use crate::rsx::ast::Value;
+use crate::rsx::prerender::is_prerenderable;
+use crate::rsx::prerender::prerender;
 
 use ::proc_macro2::Ident;
 use ::proc_macro2::TokenStream;
 use ::quote::format_ident;
 use ::quote::quote;
 
-pub fn build(ast: Node) -> TokenStream {
-    visit_node(ast)
+pub fn build(ast: Node, crate_path: &TokenStream) -> TokenStream {
+    if !has_slot(&ast) {
+        return visit_node(ast, crate_path);
+    }
+
+    let tokens = visit_node(ast, crate_path);
+    quote! {
+        #crate_path::dom::Node::fill_slots(#tokens)
+    }
+}
+
+/// Returns true if a `<slot:name/>` is written anywhere within the node.
+fn has_slot(node: &Node) -> bool {
+    match node {
+        Node::Slot { .. } => true,
+        Node::Fragment { children } => children.iter().any(has_slot),
+        Node::Open { children, .. }
+        | Node::OpenComponent { children, .. }
+        | Node::SlotContent { children, .. }
+        | Node::ErrorBoundary { children, .. } => children.iter().flatten().any(has_slot),
+        _ => false,
+    }
+}
+
+fn visit_node(node: Node, crate_path: &TokenStream) -> TokenStream {
+    if is_prerenderable(&node) {
+        let html = prerender(&node);
+        let tree_tokens = visit_node_tree(node, crate_path, false);
+
+        return quote! {
+            #crate_path::dom::Node::new_static(#html, || #tree_tokens)
+        };
+    }
+
+    visit_node_tree(node, crate_path, true)
 }
 
-fn visit_node(node: Node) -> TokenStream {
+/// Outputs the node as a tree.
+///
+/// Within pre-rendered HTML, this builds the tree it was rendered from,
+/// so its children are not pre-rendered again.
+fn visit_node_tree(node: Node, crate_path: &TokenStream, prerender_children: bool) -> TokenStream {
     match node {
         Node::Empty => {
             quote! {
-              ::renderx::dom::Node::Empty
+              #crate_path::dom::Node::Empty
             }
         }
         Node::Doctype { name, attributes } => {
             let name_tokens = visit_node_name(name);
-            let attribute_tokens = visit_optional_attributes(attributes);
+            let attribute_tokens = visit_optional_attributes(attributes, crate_path);
 
             quote! {
-                ::renderx::dom::Node::Doctype {
-                  name: #name_tokens,
+                #crate_path::dom::Node::Doctype {
+                  name: ::std::convert::Into::into(#name_tokens),
+                  attributes: #attribute_tokens,
+                }
+            }
+        }
+        Node::CData { children } => {
+            let children_tokens = visit_optional_children(children, crate_path, prerender_children);
+
+            quote! {
+                #crate_path::dom::Node::CData {
+                    children: #children_tokens
+                }
+            }
+        }
+        Node::ProcessingInstruction { target, attributes } => {
+            let target_tokens = visit_node_name(target);
+            let attribute_tokens = visit_optional_attributes(attributes, crate_path);
+
+            quote! {
+                #crate_path::dom::Node::ProcessingInstruction {
+                  target: ::std::convert::Into::into(#target_tokens),
                   attributes: #attribute_tokens,
                 }
             }
         }
         Node::Fragment { children } => {
-            let children_tokens = visit_children(children);
+            let children_tokens = visit_children(children, crate_path, prerender_children);
 
             quote! {
-                ::renderx::dom::Node::Fragment {
+                #crate_path::dom::Node::Fragment {
                     children: #children_tokens
                 }
             }
         }
         Node::Comment { children } => {
-            let children_tokens = visit_optional_children(children);
+            let children_tokens = visit_optional_children(children, crate_path, prerender_children);
 
             quote! {
-                ::renderx::dom::Node::Comment {
+                #crate_path::dom::Node::Comment {
                     children: #children_tokens
                 }
             }
         }
         Node::SelfClosing { name, attributes } => {
             let name_tokens = visit_node_name(name);
-            let attribute_tokens = visit_optional_attributes(attributes);
+            let attribute_tokens = visit_optional_attributes(attributes, crate_path);
 
             quote! {
-                ::renderx::dom::Node::new_self_closing(#name_tokens, #attribute_tokens)
+                #crate_path::dom::Node::new_self_closing(#name_tokens, #attribute_tokens)
             }
         }
         Node::Open {
@@ -61,21 +120,30 @@ fn visit_node(node: Node) -> TokenStream {
             children,
         } => {
             let name_tokens = visit_node_name(name);
-            let attribute_tokens = visit_optional_attributes(attributes);
-            let children_tokens = visit_optional_children(children);
+            let attribute_tokens = visit_optional_attributes(attributes, crate_path);
 
-            quote! {
-                ::renderx::dom::Node::new_open(#name_tokens, #attribute_tokens, #children_tokens)
+            match children {
+                Some(children) => {
+                    let children_tokens = visit_children(children, crate_path, prerender_children);
+
+                    quote! {
+                        #crate_path::dom::Node::new_open_with_children(#name_tokens, #attribute_tokens, #children_tokens)
+                    }
+                }
+                None => quote! {
+                    #crate_path::dom::Node::new_open_empty(#name_tokens, #attribute_tokens)
+                },
             }
         }
         Node::SelfClosingComponent { name, attributes } => {
             let ident = format_ident!("{}", name);
-            let props_tokens = visit_optional_props(&ident, attributes);
+            let props_tokens = visit_optional_props(&ident, attributes, None, crate_path);
 
+            // Components can return a `Result`, which is turned into a node.
             quote! {
-                #ident(
+                #crate_path::dom::ToNode::to_node(#ident(
                     #props_tokens
-                )
+                ))
             }
         }
         Node::OpenComponent {
@@ -84,24 +152,58 @@ fn visit_node(node: Node) -> TokenStream {
             children,
         } => {
             let ident = format_ident!("{}", name);
-            let props_tokens = visit_optional_props(&ident, attributes);
+            let props_tokens = visit_optional_props(&ident, attributes, children, crate_path);
 
             quote! {
-                #ident(
+                #crate_path::dom::ToNode::to_node(#ident(
                     #props_tokens
-                )
+                ))
             }
         }
-        Node::Text(text) => {
+        Node::Slot { name } => {
+            quote! {
+                #crate_path::dom::Node::new_slot(#name)
+            }
+        }
+        Node::SlotContent { name, children } => {
+            let children_tokens = visit_children(children.unwrap_or_default(), crate_path, true);
+
             quote! {
-                ::renderx::dom::Node::Text {
-                    contents: #text,
+                #crate_path::dom::Node::new_slot_content(#name, #children_tokens)
+            }
+        }
+        Node::ErrorBoundary {
+            fallback,
+            catch_panics,
+            children,
+        } => {
+            let children_tokens = visit_children(children.unwrap_or_default(), crate_path, true);
+            let children_node_tokens = quote! {
+                #crate_path::dom::Node::new_fragment(#children_tokens)
+            };
+
+            // Panics can only be caught if the children are built within the boundary.
+            if catch_panics {
+                quote! {
+                    #crate_path::dom::Node::new_error_boundary_catching_panics(
+                        #fallback,
+                        || #children_node_tokens,
+                    )
+                }
+            } else {
+                quote! {
+                    #crate_path::dom::Node::new_error_boundary(#fallback, #children_node_tokens)
                 }
             }
         }
+        Node::Text(text) => {
+            quote! {
+                #crate_path::dom::Node::new_text(#text)
+            }
+        }
         Node::Code(code) => {
             quote! {
-                ::renderx::dom::ToNode::to_node(#code)
+                #crate_path::dom::ToNode::to_node(#code)
             }
         }
     }
@@ -118,11 +220,14 @@ fn visit_node_name(name: Value) -> TokenStream {
     }
 }
 
-fn visit_optional_attributes(maybe_attributes: Option<Vec<Attribute>>) -> TokenStream {
+fn visit_optional_attributes(
+    maybe_attributes: Option<Vec<Attribute>>,
+    crate_path: &TokenStream,
+) -> TokenStream {
     match maybe_attributes {
         None => quote! { None },
         Some(attributes) => {
-            let tokens = visit_attributes(attributes);
+            let tokens = visit_attributes(attributes, crate_path);
 
             quote! {
                 Some(#tokens)
@@ -131,22 +236,72 @@ fn visit_optional_attributes(maybe_attributes: Option<Vec<Attribute>>) -> TokenS
     }
 }
 
-fn visit_attributes(attributes: Vec<Attribute>) -> TokenStream {
-    let attribute_tokens = attributes.into_iter().map(|a| visit_attribute(a));
+fn visit_attributes(attributes: Vec<Attribute>, crate_path: &TokenStream) -> TokenStream {
+    if attributes.iter().all(is_static_attribute) {
+        return visit_static_attributes(attributes, crate_path);
+    }
+
+    let attribute_tokens = attributes
+        .into_iter()
+        .map(|a| visit_attribute(a, crate_path));
 
     quote! {
-        vec![
+        #crate_path::dom::Attributes::Owned(vec![
             #(#attribute_tokens),*
-        ]
+        ])
     }
 }
 
-fn visit_attribute(attribute: Attribute) -> TokenStream {
+fn is_static_attribute(attribute: &Attribute) -> bool {
+    match (&attribute.key, &attribute.value) {
+        (Value::Text(_), None) => true,
+        (Value::Text(_), Some(Value::Text(_))) => true,
+        _ => false,
+    }
+}
+
+/// Outputs the attributes as a constant list, so they don't need allocating.
+/// This must only be called when all keys and values are text.
+fn visit_static_attributes(attributes: Vec<Attribute>, crate_path: &TokenStream) -> TokenStream {
+    let attribute_tokens = attributes
+        .into_iter()
+        .map(|a| visit_static_attribute(a, crate_path));
+
+    quote! {
+        #crate_path::dom::Attributes::Static({
+            const ATTRIBUTES: &[#crate_path::dom::Attribute] = &[
+                #(#attribute_tokens),*
+            ];
+            ATTRIBUTES
+        })
+    }
+}
+
+fn visit_static_attribute(attribute: Attribute, crate_path: &TokenStream) -> TokenStream {
+    let key = visit_attribute_key(attribute.key);
+    let value = match attribute.value {
+        None => quote! {
+            #crate_path::dom::AttributeValue::ImplicitTrue
+        },
+        Some(Value::Text(text)) => quote! {
+            #crate_path::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed(#text))
+        },
+        Some(Value::Code(_)) => {
+            unreachable!("Static attribute found with a code value (this is a bug)")
+        }
+    };
+
+    quote! {
+        #crate_path::dom::Attribute::new(#key, #value)
+    }
+}
+
+fn visit_attribute(attribute: Attribute, crate_path: &TokenStream) -> TokenStream {
     let key = visit_attribute_key(attribute.key);
-    let value = visit_attribute_value(attribute.value);
+    let value = visit_attribute_value(attribute.value, crate_path);
 
     quote! {
-        ::renderx::dom::Attribute::new(#key, #value)
+        #crate_path::dom::Attribute::new(#key, #value)
     }
 }
 
@@ -161,37 +316,56 @@ fn visit_attribute_key(key: Value) -> TokenStream {
     }
 }
 
-fn visit_attribute_value(value: Option<Value>) -> TokenStream {
+fn visit_attribute_value(value: Option<Value>, crate_path: &TokenStream) -> TokenStream {
     match value {
         None => quote! {
-            ::renderx::dom::AttributeValue::ImplicitTrue
+            #crate_path::dom::AttributeValue::ImplicitTrue
         },
         Some(Value::Text(text)) => quote! {
-            ::renderx::dom::ToAttributeValue::to_attribute_value(#text)
+            #crate_path::dom::ToAttributeValue::to_attribute_value(#text)
         },
         Some(Value::Code(code)) => quote! {
-            ::renderx::dom::ToAttributeValue::to_attribute_value(#code)
+            #crate_path::dom::ToAttributeValue::to_attribute_value(#code)
         },
     }
 }
 
+/// The children of a component are given as the `children` prop.
 fn visit_optional_props(
     component_ident: &Ident,
     maybe_props: Option<Vec<Attribute>>,
+    maybe_children: Option<Vec<Node>>,
+    crate_path: &TokenStream,
 ) -> TokenStream {
-    match maybe_props {
-        None => quote! {},
-        Some(props) => visit_props(component_ident, props),
+    match (maybe_props, maybe_children) {
+        (None, None) => quote! {},
+        (maybe_props, maybe_children) => visit_props(
+            component_ident,
+            maybe_props.unwrap_or_default(),
+            maybe_children,
+            crate_path,
+        ),
     }
 }
 
-fn visit_props(component_ident: &Ident, props: Vec<Attribute>) -> TokenStream {
+fn visit_props(
+    component_ident: &Ident,
+    props: Vec<Attribute>,
+    maybe_children: Option<Vec<Node>>,
+    crate_path: &TokenStream,
+) -> TokenStream {
     let props_type_name = format_ident!("__RSX__{}__Props__", component_ident);
-    let props_tokens = props.into_iter().map(|a| visit_prop(a));
+    let mut props_tokens: Vec<TokenStream> = props.into_iter().map(|a| visit_prop(a)).collect();
+    if let Some(children) = maybe_children {
+        let children_tokens = visit_node(Node::Fragment { children }, crate_path);
+        props_tokens.push(quote! {
+            children: #children_tokens
+        });
+    }
 
     quote! {
         {
-            type #props_type_name = <#component_ident as ::renderx::Component>::Props;
+            type #props_type_name = <#component_ident as #crate_path::Component>::Props;
             #props_type_name {
                 #(#props_tokens),*
             }
@@ -237,11 +411,15 @@ fn visit_prop_value(value: Option<Value>) -> TokenStream {
     }
 }
 
-fn visit_optional_children(maybe_children: Option<Vec<Node>>) -> TokenStream {
+fn visit_optional_children(
+    maybe_children: Option<Vec<Node>>,
+    crate_path: &TokenStream,
+    prerender_children: bool,
+) -> TokenStream {
     match maybe_children {
-        None => quote! { Option::<Vec<::renderx::dom::Node>>::None },
+        None => quote! { Option::<Vec<#crate_path::dom::Node>>::None },
         Some(children) => {
-            let tokens = visit_children(children);
+            let tokens = visit_children(children, crate_path, prerender_children);
 
             quote! {
                 Some(#tokens)
@@ -250,8 +428,21 @@ fn visit_optional_children(maybe_children: Option<Vec<Node>>) -> TokenStream {
     }
 }
 
-fn visit_children(children: Vec<Node>) -> TokenStream {
-    let children_tokens: Vec<TokenStream> = children.into_iter().map(|a| visit_node(a)).collect();
+fn visit_children(
+    children: Vec<Node>,
+    crate_path: &TokenStream,
+    prerender_children: bool,
+) -> TokenStream {
+    let children_tokens: Vec<TokenStream> = children
+        .into_iter()
+        .map(|child| {
+            if prerender_children {
+                visit_node(child, crate_path)
+            } else {
+                visit_node_tree(child, crate_path, false)
+            }
+        })
+        .collect();
 
     quote! {
         vec![
@@ -267,13 +458,16 @@ mod nodes {
 
     #[test]
     fn it_should_output_simple_self_closing_nodes() {
-        let code = build(Node::SelfClosing {
-            name: Value::Text("hr".to_string()),
-            attributes: None,
-        });
+        let code = build(
+            Node::SelfClosing {
+                name: Value::Text("hr".to_string()),
+                attributes: None,
+            },
+            &quote! { ::renderx },
+        );
 
         let expected = quote! {
-          ::renderx::dom::Node::new_self_closing("hr", None)
+          ::renderx::dom::Node::new_static("<hr/>", || ::renderx::dom::Node::new_self_closing("hr", None))
         };
 
         assert_eq!(expected.to_string(), code.to_string());
@@ -281,14 +475,17 @@ mod nodes {
 
     #[test]
     fn it_should_output_simple_open_nodes() {
-        let code = build(Node::Open {
-            name: Value::Text("div".to_string()),
-            attributes: None,
-            children: None,
-        });
+        let code = build(
+            Node::Open {
+                name: Value::Text("div".to_string()),
+                attributes: None,
+                children: None,
+            },
+            &quote! { ::renderx },
+        );
 
         let expected = quote! {
-          ::renderx::dom::Node::new_open("div", None, Option::<Vec<::renderx::dom::Node>>::None)
+          ::renderx::dom::Node::new_static("<div></div>", || ::renderx::dom::Node::new_open_empty("div", None))
         };
 
         assert_eq!(expected.to_string(), code.to_string());
@@ -302,18 +499,248 @@ mod literals {
 
     #[test]
     fn it_should_output_nodes_with_literals() {
-        let code = build(Node::Open {
-            name: Value::Text("h1".to_string()),
-            attributes: None,
-            children: Some(vec![Node::Text("hello world!".to_string())]),
-        });
+        let code = build(
+            Node::Open {
+                name: Value::Text("h1".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Text("hello world!".to_string())]),
+            },
+            &quote! { ::renderx },
+        );
 
         let expected = quote! {
-          ::renderx::dom::Node::new_open("h1", None, Some(vec![
-            ::renderx::dom::Node::Text {
-              contents: "hello world!",
+          ::renderx::dom::Node::new_static("<h1>hello world!</h1>", ||
+            ::renderx::dom::Node::new_open_with_children("h1", None, vec![
+              ::renderx::dom::Node::new_text("hello world!")
+            ])
+          )
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+}
+
+#[cfg(test)]
+mod code_names {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_output_self_closing_nodes_with_code_names() {
+        let code = build(
+            Node::SelfClosing {
+                name: Value::Code(quote! { el }),
+                attributes: None,
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_self_closing(el, None)
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+
+    #[test]
+    fn it_should_output_open_nodes_with_code_names() {
+        let code = build(
+            Node::Open {
+                name: Value::Code(quote! { el }),
+                attributes: None,
+                children: None,
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_open_empty(el, None)
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+}
+
+#[cfg(test)]
+mod static_subtrees {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_prerender_static_children_of_dynamic_nodes() {
+        let code = build(
+            Node::Open {
+                name: Value::Text("div".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::Open {
+                        name: Value::Text("p".to_string()),
+                        attributes: Some(vec![Attribute {
+                            key: Value::Text("class".to_string()),
+                            value: Some(Value::Text("intro".to_string())),
+                        }]),
+                        children: Some(vec![Node::Text("Hi".to_string())]),
+                    },
+                    Node::Code(quote! { text }),
+                ]),
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_open_with_children("div", None, vec![
+            ::renderx::dom::Node::new_static("<p class=\"intro\">Hi</p>", ||
+              ::renderx::dom::Node::new_open_with_children(
+                "p",
+                Some(::renderx::dom::Attributes::Static({
+                  const ATTRIBUTES: &[::renderx::dom::Attribute] = &[
+                    ::renderx::dom::Attribute::new(
+                      "class",
+                      ::renderx::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed("intro"))
+                    )
+                  ];
+                  ATTRIBUTES
+                })),
+                vec![::renderx::dom::Node::new_text("Hi")]
+              )
+            ),
+            ::renderx::dom::ToNode::to_node(text)
+          ])
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+}
+
+#[cfg(test)]
+mod error_boundary {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    fn boundary(catch_panics: bool) -> Node {
+        Node::ErrorBoundary {
+            fallback: quote! { fallback },
+            catch_panics,
+            children: Some(vec![Node::Code(quote! { card })]),
+        }
+    }
+
+    #[test]
+    fn it_should_build_the_children_before_the_boundary() {
+        let code = build(boundary(false), &quote! { ::renderx });
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_error_boundary(
+            fallback,
+            ::renderx::dom::Node::new_fragment(vec![::renderx::dom::ToNode::to_node(card)])
+          )
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+
+    #[test]
+    fn it_should_build_the_children_within_the_boundary_when_catching_panics() {
+        let code = build(boundary(true), &quote! { ::renderx });
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_error_boundary_catching_panics(
+            fallback,
+            || ::renderx::dom::Node::new_fragment(vec![::renderx::dom::ToNode::to_node(card)]),
+          )
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+}
+
+#[cfg(test)]
+mod slots {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_fill_slots_when_they_are_written() {
+        let code = build(
+            Node::Open {
+                name: Value::Text("head".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Slot {
+                    name: "head".to_string(),
+                }]),
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::fill_slots(
+            ::renderx::dom::Node::new_open_with_children("head", None, vec![
+              ::renderx::dom::Node::new_slot("head")
+            ])
+          )
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+
+    #[test]
+    fn it_should_output_slot_content() {
+        let code = build(
+            Node::SlotContent {
+                name: "head".to_string(),
+                children: Some(vec![Node::SelfClosing {
+                    name: Value::Text("meta".to_string()),
+                    attributes: Some(vec![Attribute {
+                        key: Value::Text("charset".to_string()),
+                        value: Some(Value::Text("utf-8".to_string())),
+                    }]),
+                }]),
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_slot_content("head", vec![
+            ::renderx::dom::Node::new_static("<meta charset=\"utf-8\"/>", ||
+              ::renderx::dom::Node::new_self_closing(
+                "meta",
+                Some(::renderx::dom::Attributes::Static({
+                  const ATTRIBUTES: &[::renderx::dom::Attribute] = &[
+                    ::renderx::dom::Attribute::new(
+                      "charset",
+                      ::renderx::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed("utf-8"))
+                    )
+                  ];
+                  ATTRIBUTES
+                }))
+              )
+            )
+          ])
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+
+    #[test]
+    fn it_should_pass_component_children_as_a_prop() {
+        let code = build(
+            Node::OpenComponent {
+                name: "Layout".to_string(),
+                attributes: None,
+                children: Some(vec![Node::Code(quote! { page })]),
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::ToNode::to_node(Layout({
+            type __RSX__Layout__Props__ = <Layout as ::renderx::Component>::Props;
+            __RSX__Layout__Props__ {
+              children: ::renderx::dom::Node::Fragment {
+                children: vec![::renderx::dom::ToNode::to_node(page)]
+              }
             }
-          ]))
+          }))
         };
 
         assert_eq!(expected.to_string(), code.to_string());
@@ -328,28 +755,127 @@ mod code {
 
     #[test]
     fn it_should_transform_code() {
-        let code = build(Node::Open {
-            name: Value::Text("h1".to_string()),
-            attributes: None,
-            children: Some(vec![
-                Node::Code(quote! {
-                  "Hello world!"
-                }),
-                Node::Text("hello world!".to_string()),
-                Node::Code(quote! {
-                  text
-                }),
-            ]),
-        });
+        let code = build(
+            Node::Open {
+                name: Value::Text("h1".to_string()),
+                attributes: None,
+                children: Some(vec![
+                    Node::Code(quote! {
+                      "Hello world!"
+                    }),
+                    Node::Text("hello world!".to_string()),
+                    Node::Code(quote! {
+                      text
+                    }),
+                ]),
+            },
+            &quote! { ::renderx },
+        );
 
         let expected = quote! {
-          ::renderx::dom::Node::new_open("h1", None, Some(vec![
+          ::renderx::dom::Node::new_open_with_children("h1", None, vec![
             ::renderx::dom::ToNode::to_node("Hello world!"),
-            ::renderx::dom::Node::Text {
-              contents: "hello world!",
+            ::renderx::dom::Node::new_text("hello world!"),
+            ::renderx::dom::ToNode::to_node(text)
+          ])
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+}
+
+#[cfg(test)]
+mod crate_path {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_output_the_given_crate_path() {
+        let code = build(
+            Node::SelfClosing {
+                name: Value::Text("input".to_string()),
+                attributes: Some(vec![Attribute {
+                    key: Value::Text("disabled".to_string()),
+                    value: Some(Value::Code(quote! { is_disabled })),
+                }]),
+            },
+            &quote! { ::my_fw::html },
+        );
+
+        let expected = quote! {
+          ::my_fw::html::dom::Node::new_self_closing("input", Some(::my_fw::html::dom::Attributes::Owned(vec![
+            ::my_fw::html::dom::Attribute::new("disabled", ::my_fw::html::dom::ToAttributeValue::to_attribute_value(is_disabled))
+          ])))
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+}
+
+#[cfg(test)]
+mod attributes {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_output_static_attributes_when_all_are_literals() {
+        let code = build(
+            Node::Open {
+                name: Value::Text("div".to_string()),
+                attributes: Some(vec![
+                    Attribute {
+                        key: Value::Text("class".to_string()),
+                        value: Some(Value::Text("a".to_string())),
+                    },
+                    Attribute {
+                        key: Value::Text("hidden".to_string()),
+                        value: None,
+                    },
+                ]),
+                children: Some(vec![Node::Code(quote! { text })]),
             },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_open_with_children("div", Some(::renderx::dom::Attributes::Static({
+            const ATTRIBUTES: &[::renderx::dom::Attribute] = &[
+              ::renderx::dom::Attribute::new("class", ::renderx::dom::AttributeValue::Text(::std::borrow::Cow::Borrowed("a"))),
+              ::renderx::dom::Attribute::new("hidden", ::renderx::dom::AttributeValue::ImplicitTrue)
+            ];
+            ATTRIBUTES
+          })), vec![
             ::renderx::dom::ToNode::to_node(text)
-          ]))
+          ])
+        };
+
+        assert_eq!(expected.to_string(), code.to_string());
+    }
+
+    #[test]
+    fn it_should_output_owned_attributes_when_any_use_code() {
+        let code = build(
+            Node::SelfClosing {
+                name: Value::Text("input".to_string()),
+                attributes: Some(vec![
+                    Attribute {
+                        key: Value::Text("type".to_string()),
+                        value: Some(Value::Text("text".to_string())),
+                    },
+                    Attribute {
+                        key: Value::Text("min".to_string()),
+                        value: Some(Value::Code(quote! { min })),
+                    },
+                ]),
+            },
+            &quote! { ::renderx },
+        );
+
+        let expected = quote! {
+          ::renderx::dom::Node::new_self_closing("input", Some(::renderx::dom::Attributes::Owned(vec![
+            ::renderx::dom::Attribute::new("type", ::renderx::dom::ToAttributeValue::to_attribute_value("text")),
+            ::renderx::dom::Attribute::new("min", ::renderx::dom::ToAttributeValue::to_attribute_value(min))
+          ])))
         };
 
         assert_eq!(expected.to_string(), code.to_string());
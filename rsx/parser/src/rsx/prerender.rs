@@ -0,0 +1,434 @@
+use crate::rsx::ast::Attribute;
+use crate::rsx::ast::Node;
+use crate::rsx::ast::Value;
+use crate::rsx::escaping::write_escaped_attribute;
+use crate::rsx::escaping::TextMode;
+use crate::rsx::namespace::is_void_element_name;
+
+/// Returns true if the node can be rendered to HTML at compile time.
+///
+/// This is when it contains no code, and no components,
+/// anywhere within it. Plain text and empty nodes are not worth pre-rendering,
+/// as they are already as cheap as a `Static` node.
+pub fn is_prerenderable(node: &Node) -> bool {
+    match node {
+        Node::Empty | Node::Text(_) => false,
+        node => is_static(node),
+    }
+}
+
+/// Renders the node to the HTML that `core::render` would output for it.
+///
+/// This must only be called on nodes where `is_prerenderable` is true.
+pub fn prerender(node: &Node) -> String {
+    let mut html = String::new();
+    prerender_node(&mut html, node, TextMode::Normal);
+    html
+}
+
+fn is_static(node: &Node) -> bool {
+    match node {
+        Node::Empty => true,
+        Node::Text(_) => true,
+        Node::Code(_) => false,
+        Node::SelfClosingComponent { .. } => false,
+        Node::OpenComponent { .. } => false,
+        // Errors are caught at runtime.
+        Node::ErrorBoundary { .. } => false,
+        // Slots are filled at runtime.
+        Node::Slot { .. } | Node::SlotContent { .. } => false,
+        Node::Doctype { name, attributes } => {
+            is_static_value(name) && is_static_maybe_attributes(attributes)
+        }
+        Node::ProcessingInstruction { target, attributes } => {
+            is_static_value(target) && is_static_maybe_attributes(attributes)
+        }
+        Node::Comment { children } => is_static_maybe_nodes(children),
+        Node::CData { children } => is_static_maybe_nodes(children),
+        Node::Fragment { children } => children.iter().all(is_static),
+        Node::SelfClosing { name, attributes } => {
+            is_static_value(name) && is_static_maybe_attributes(attributes)
+        }
+        // The renderer collects the children of `<head>`,
+        // so components elsewhere in the page can change them,
+        // and gives `<html>` the `lang` and `dir` of the translator.
+        Node::Open {
+            name: Value::Text(name),
+            ..
+        } if name.eq_ignore_ascii_case("head") || name.eq_ignore_ascii_case("html") => false,
+        // `parse_html` would read `<link>` as having no children,
+        // so when the `link` of an RSS feed is given text, it is built at runtime instead.
+        // This keeps the tree as written, for when it is traversed or rendered as XML.
+        Node::Open {
+            name,
+            children: Some(_),
+            ..
+        } if is_void_element_name(name) => false,
+        Node::Open {
+            name,
+            attributes,
+            children,
+        } => {
+            is_static_value(name)
+                && is_static_maybe_attributes(attributes)
+                && is_static_maybe_nodes(children)
+        }
+    }
+}
+
+fn is_static_value(value: &Value) -> bool {
+    match value {
+        Value::Text(_) => true,
+        Value::Code(_) => false,
+    }
+}
+
+fn is_static_maybe_attributes(maybe_attributes: &Option<Vec<Attribute>>) -> bool {
+    match maybe_attributes {
+        None => true,
+        Some(attributes) => attributes.iter().all(|attribute| {
+            is_static_value(&attribute.key)
+                && attribute
+                    .value
+                    .as_ref()
+                    .map(is_static_value)
+                    .unwrap_or(true)
+        }),
+    }
+}
+
+fn is_static_maybe_nodes(maybe_nodes: &Option<Vec<Node>>) -> bool {
+    match maybe_nodes {
+        None => true,
+        Some(nodes) => nodes.iter().all(is_static),
+    }
+}
+
+fn prerender_node(html: &mut String, node: &Node, text_mode: TextMode) {
+    match node {
+        Node::Empty => {}
+        Node::Doctype { name, attributes } => {
+            html.push_str("<!");
+            html.push_str(text_of(name));
+            if let Some(attributes) = attributes {
+                for attribute in attributes {
+                    html.push(' ');
+                    html.push_str(text_of(&attribute.key));
+                }
+            }
+            html.push('>');
+        }
+        Node::Comment { children } => match children {
+            Some(children) => {
+                html.push_str("<!-- ");
+                prerender_nodes(html, children, TextMode::Comment);
+                html.push_str(" -->");
+            }
+            None => html.push_str("<!-- -->"),
+        },
+        Node::CData { children } => {
+            html.push_str("<![CDATA[");
+            if let Some(children) = children {
+                prerender_nodes(html, children, TextMode::CData);
+            }
+            html.push_str("]]>");
+        }
+        Node::ProcessingInstruction { target, attributes } => {
+            html.push_str("<?");
+            html.push_str(text_of(target));
+            prerender_maybe_attributes(html, attributes);
+            html.push_str("?>");
+        }
+        Node::Fragment { children } => prerender_nodes(html, children, text_mode),
+        Node::SelfClosing { name, attributes } => {
+            html.push('<');
+            html.push_str(text_of(name));
+            prerender_maybe_attributes(html, attributes);
+            html.push_str("/>");
+        }
+        Node::Open {
+            name,
+            attributes,
+            children,
+        } => {
+            html.push('<');
+            html.push_str(text_of(name));
+            prerender_maybe_attributes(html, attributes);
+            html.push('>');
+            if let Some(children) = children {
+                prerender_nodes(html, children, TextMode::for_element(text_of(name)));
+            }
+            html.push_str("</");
+            html.push_str(text_of(name));
+            html.push('>');
+        }
+        Node::Text(text) => text_mode.write_text(html, text),
+        Node::Code(_)
+        | Node::SelfClosingComponent { .. }
+        | Node::OpenComponent { .. }
+        | Node::ErrorBoundary { .. }
+        | Node::Slot { .. }
+        | Node::SlotContent { .. } => {
+            unreachable!("Pre-rendering a node containing code (this is a bug)")
+        }
+    }
+}
+
+fn prerender_nodes(html: &mut String, nodes: &[Node], text_mode: TextMode) {
+    for node in nodes {
+        prerender_node(html, node, text_mode);
+    }
+}
+
+fn prerender_maybe_attributes(html: &mut String, maybe_attributes: &Option<Vec<Attribute>>) {
+    if let Some(attributes) = maybe_attributes {
+        for attribute in attributes {
+            html.push(' ');
+            html.push_str(text_of(&attribute.key));
+
+            if let Some(value) = &attribute.value {
+                html.push_str("=\"");
+                write_escaped_attribute(html, text_of(value));
+                html.push('"');
+            }
+        }
+    }
+}
+
+fn text_of(value: &Value) -> &str {
+    match value {
+        Value::Text(text) => text,
+        Value::Code(_) => unreachable!("Pre-rendering a code value (this is a bug)"),
+    }
+}
+
+#[cfg(test)]
+mod is_prerenderable {
+    use super::*;
+    use ::quote::quote;
+
+    #[test]
+    fn it_should_not_prerender_lone_text() {
+        assert!(!is_prerenderable(&Node::Text("hello".to_string())));
+    }
+
+    #[test]
+    fn it_should_prerender_nodes_without_code() {
+        let node = Node::Open {
+            name: Value::Text("div".to_string()),
+            attributes: Some(vec![Attribute {
+                key: Value::Text("class".to_string()),
+                value: Some(Value::Text("a".to_string())),
+            }]),
+            children: Some(vec![Node::Text("hello".to_string())]),
+        };
+
+        assert!(is_prerenderable(&node));
+    }
+
+    #[test]
+    fn it_should_not_prerender_nodes_with_code_attributes() {
+        let node = Node::SelfClosing {
+            name: Value::Text("input".to_string()),
+            attributes: Some(vec![Attribute {
+                key: Value::Text("min".to_string()),
+                value: Some(Value::Code(quote! { min })),
+            }]),
+        };
+
+        assert!(!is_prerenderable(&node));
+    }
+
+    #[test]
+    fn it_should_not_prerender_nodes_with_code_children() {
+        let node = Node::Open {
+            name: Value::Text("div".to_string()),
+            attributes: None,
+            children: Some(vec![Node::Open {
+                name: Value::Text("p".to_string()),
+                attributes: None,
+                children: Some(vec![Node::Code(quote! { text })]),
+            }]),
+        };
+
+        assert!(!is_prerenderable(&node));
+    }
+
+    #[test]
+    fn it_should_not_prerender_components() {
+        let node = Node::Open {
+            name: Value::Text("div".to_string()),
+            attributes: None,
+            children: Some(vec![Node::SelfClosingComponent {
+                name: "HorizontalRule".to_string(),
+                attributes: None,
+            }]),
+        };
+
+        assert!(!is_prerenderable(&node));
+    }
+
+    #[test]
+    fn it_should_not_prerender_void_elements_with_children() {
+        let node = Node::Open {
+            name: Value::Text("link".to_string()),
+            attributes: None,
+            children: Some(vec![Node::Text("https://example.com".to_string())]),
+        };
+
+        assert!(!is_prerenderable(&node));
+    }
+
+    #[test]
+    fn it_should_not_prerender_the_html_element() {
+        let node = Node::Open {
+            name: Value::Text("html".to_string()),
+            attributes: None,
+            children: Some(vec![Node::Open {
+                name: Value::Text("body".to_string()),
+                attributes: None,
+                children: None,
+            }]),
+        };
+
+        assert!(!is_prerenderable(&node));
+    }
+
+    #[test]
+    fn it_should_not_prerender_the_head() {
+        let node = Node::Open {
+            name: Value::Text("html".to_string()),
+            attributes: None,
+            children: Some(vec![Node::Open {
+                name: Value::Text("head".to_string()),
+                attributes: None,
+                children: None,
+            }]),
+        };
+
+        assert!(!is_prerenderable(&node));
+    }
+}
+
+#[cfg(test)]
+mod prerender {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_prerender_nested_nodes() {
+        let html = prerender(&Node::Open {
+            name: Value::Text("div".to_string()),
+            attributes: Some(vec![
+                Attribute {
+                    key: Value::Text("class".to_string()),
+                    value: Some(Value::Text("a".to_string())),
+                },
+                Attribute {
+                    key: Value::Text("hidden".to_string()),
+                    value: None,
+                },
+            ]),
+            children: Some(vec![
+                Node::Open {
+                    name: Value::Text("p".to_string()),
+                    attributes: None,
+                    children: Some(vec![Node::Text("Hi".to_string())]),
+                },
+                Node::SelfClosing {
+                    name: Value::Text("hr".to_string()),
+                    attributes: None,
+                },
+            ]),
+        });
+
+        assert_eq!("<div class=\"a\" hidden><p>Hi</p><hr/></div>", html);
+    }
+
+    #[test]
+    fn it_should_prerender_doctypes_and_comments() {
+        let html = prerender(&Node::Fragment {
+            children: vec![
+                Node::Doctype {
+                    name: Value::Text("doctype".to_string()),
+                    attributes: Some(vec![Attribute {
+                        key: Value::Text("html".to_string()),
+                        value: None,
+                    }]),
+                },
+                Node::Comment { children: None },
+                Node::Comment {
+                    children: Some(vec![Node::Text("hello".to_string())]),
+                },
+            ],
+        });
+
+        assert_eq!("<!doctype html><!-- --><!-- hello -->", html);
+    }
+
+    #[test]
+    fn it_should_prerender_cdata() {
+        let html = prerender(&Node::CData {
+            children: Some(vec![Node::Text("<p>a]]>b</p>".to_string())]),
+        });
+
+        assert_eq!("<![CDATA[<p>a]]]]><![CDATA[>b</p>]]>", html);
+    }
+
+    #[test]
+    fn it_should_prerender_processing_instructions() {
+        let html = prerender(&Node::ProcessingInstruction {
+            target: Value::Text("xml".to_string()),
+            attributes: Some(vec![Attribute {
+                key: Value::Text("version".to_string()),
+                value: Some(Value::Text("1.0".to_string())),
+            }]),
+        });
+
+        assert_eq!(r#"<?xml version="1.0"?>"#, html);
+    }
+
+    #[test]
+    fn it_should_guard_closing_tags_in_scripts() {
+        let html = prerender(&Node::Open {
+            name: Value::Text("script".to_string()),
+            attributes: None,
+            children: Some(vec![Node::Text(r#"{"html": "</script><b>"}"#.to_string())]),
+        });
+
+        assert_eq!(r#"<script>{"html": "<\/script><b>"}</script>"#, html);
+    }
+
+    #[test]
+    fn it_should_escape_titles() {
+        let html = prerender(&Node::Open {
+            name: Value::Text("title".to_string()),
+            attributes: None,
+            children: Some(vec![Node::Text("Fish & <Chips>".to_string())]),
+        });
+
+        assert_eq!("<title>Fish &amp; &lt;Chips></title>", html);
+    }
+
+    #[test]
+    fn it_should_escape_text_and_attributes() {
+        let html = prerender(&Node::Open {
+            name: Value::Text("p".to_string()),
+            attributes: Some(vec![Attribute {
+                key: Value::Text("title".to_string()),
+                value: Some(Value::Text(r#"Say "hi" & <wave>"#.to_string())),
+            }]),
+            children: Some(vec![
+                Node::Text("Fish & <Chips>".to_string()),
+                Node::Comment {
+                    children: Some(vec![Node::Text("a & b".to_string())]),
+                },
+            ]),
+        });
+
+        assert_eq!(
+            r#"<p title="Say &quot;hi&quot; &amp; &lt;wave&gt;">Fish &amp; &lt;Chips&gt;<!-- a & b --></p>"#,
+            html
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::rsx::ast::Attribute;
+use crate::rsx::ast::Node;
+use crate::rsx::ast::Value;
+use crate::rsx::Error;
+use crate::rsx::Result;
+use crate::util::tracked_env_var;
+
+use ::proc_macro2::TokenStream;
+use ::proc_macro2::TokenTree;
+use ::std::collections::BTreeSet;
+use ::std::env;
+use ::std::fs::OpenOptions;
+use ::std::io;
+use ::std::io::Read;
+use ::std::io::Seek;
+use ::std::io::SeekFrom;
+use ::std::io::Write;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+
+/// The environment variable naming a file, which the keys of every `t!` used within `rsx!` are added to.
+///
+/// i.e. building with `RENDERX_TRANSLATION_KEYS=translation-keys.txt` gives a sorted list
+/// of the keys, one per line, which can be checked against the translations.
+/// A relative path is from the crate being built.
+pub const TRANSLATION_KEYS_ENV: &str = "RENDERX_TRANSLATION_KEYS";
+
+/// The name of the macro used for translations.
+const TRANSLATION_MACRO: &str = "t";
+
+/// Adds the translation keys used within the node to the file set by `RENDERX_TRANSLATION_KEYS`.
+/// Nothing happens when it isn't set.
+pub fn write_translation_keys(node: &Node) -> Result<()> {
+    let path = match translation_keys_path() {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    let mut keys = BTreeSet::new();
+    collect_node_keys(node, &mut keys);
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    add_keys_to_file(&path, keys)
+        .map_err(|err| Error::TranslationKeysError(format!("{}, {}", path.display(), err)))
+}
+
+/// Merges the keys into those already in the file.
+///
+/// The file is locked until it has been written, as `rsx!` is expanded by many `rustc` processes at once
+/// when building several crates in parallel.
+fn add_keys_to_file(path: &Path, mut keys: BTreeSet<String>) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    file.lock()?;
+
+    let mut existing = String::new();
+    file.read_to_string(&mut existing)?;
+    keys.extend(existing.lines().map(str::to_string));
+
+    let mut contents = keys.into_iter().collect::<Vec<_>>().join("\n");
+    contents.push('\n');
+    file.seek(SeekFrom::Start(0))?;
+    file.set_len(0)?;
+    file.write_all(contents.as_bytes())
+}
+
+fn translation_keys_path() -> Option<PathBuf> {
+    let path = PathBuf::from(tracked_env_var(TRANSLATION_KEYS_ENV)?.trim());
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+
+    match env::var("CARGO_MANIFEST_DIR") {
+        Ok(manifest_dir) if path.is_relative() => Some(PathBuf::from(manifest_dir).join(path)),
+        _ => Some(path),
+    }
+}
+
+fn collect_node_keys(node: &Node, keys: &mut BTreeSet<String>) {
+    match node {
+        Node::Empty | Node::Text(_) | Node::Slot { .. } => {}
+        Node::Code(code) => collect_keys(code.clone(), keys),
+        Node::Doctype { name, attributes } | Node::SelfClosing { name, attributes } => {
+            collect_value_keys(name, keys);
+            collect_attributes_keys(attributes, keys);
+        }
+        Node::ProcessingInstruction { target, attributes } => {
+            collect_value_keys(target, keys);
+            collect_attributes_keys(attributes, keys);
+        }
+        Node::Open {
+            name,
+            attributes,
+            children,
+        } => {
+            collect_value_keys(name, keys);
+            collect_attributes_keys(attributes, keys);
+            collect_nodes_keys(children.iter().flatten(), keys);
+        }
+        Node::SelfClosingComponent { attributes, .. } => {
+            collect_attributes_keys(attributes, keys);
+        }
+        Node::OpenComponent {
+            attributes,
+            children,
+            ..
+        } => {
+            collect_attributes_keys(attributes, keys);
+            collect_nodes_keys(children.iter().flatten(), keys);
+        }
+        Node::Comment { children }
+        | Node::CData { children }
+        | Node::SlotContent { children, .. } => {
+            collect_nodes_keys(children.iter().flatten(), keys);
+        }
+        Node::ErrorBoundary {
+            fallback, children, ..
+        } => {
+            collect_keys(fallback.clone(), keys);
+            collect_nodes_keys(children.iter().flatten(), keys);
+        }
+        Node::Fragment { children } => collect_nodes_keys(children.iter(), keys),
+    }
+}
+
+fn collect_nodes_keys<'a, I>(nodes: I, keys: &mut BTreeSet<String>)
+where
+    I: Iterator<Item = &'a Node>,
+{
+    for node in nodes {
+        collect_node_keys(node, keys);
+    }
+}
+
+fn collect_attributes_keys(maybe_attributes: &Option<Vec<Attribute>>, keys: &mut BTreeSet<String>) {
+    for attribute in maybe_attributes.iter().flatten() {
+        collect_value_keys(&attribute.key, keys);
+        if let Some(value) = &attribute.value {
+            collect_value_keys(value, keys);
+        }
+    }
+}
+
+fn collect_value_keys(value: &Value, keys: &mut BTreeSet<String>) {
+    if let Value::Code(code) = value {
+        collect_keys(code.clone(), keys);
+    }
+}
+
+/// Finds `t!("key", ...)` within the code, however deeply it is nested.
+/// Only keys given as a string literal can be found.
+fn collect_keys(code: TokenStream, keys: &mut BTreeSet<String>) {
+    let tokens: Vec<TokenTree> = code.into_iter().collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Group(group) = token {
+            if is_translation_macro(&tokens[..i]) {
+                if let Some(key) = first_string_literal(group.stream()) {
+                    keys.insert(key);
+                }
+            }
+
+            collect_keys(group.stream(), keys);
+        }
+    }
+}
+
+/// Returns true if the tokens end with `t!`.
+fn is_translation_macro(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [.., TokenTree::Ident(ident), TokenTree::Punct(punct)] => {
+            ident == TRANSLATION_MACRO && punct.as_char() == '!'
+        }
+        _ => false,
+    }
+}
+
+fn first_string_literal(stream: TokenStream) -> Option<String> {
+    match stream.into_iter().next()? {
+        TokenTree::Literal(literal) => match ::syn::Lit::new(literal) {
+            ::syn::Lit::Str(text) => Some(text.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod translation_keys {
+    use super::*;
+    use crate::rsx::grammar::parse;
+    use ::pretty_assertions::assert_eq;
+    use ::quote::quote;
+    use ::std::fs;
+    use ::std::thread;
+
+    fn keys(code: TokenStream) -> Vec<String> {
+        let mut keys = BTreeSet::new();
+        collect_node_keys(&parse(code).unwrap(), &mut keys);
+        keys.into_iter().collect()
+    }
+
+    #[test]
+    fn it_should_collect_keys_from_children_and_attributes() {
+        let code = quote! {
+            <form title={t!("form.title")}>
+                <Field label={t!("form.email")}>
+                    {t!("form.help", count = 3)}
+                </Field>
+                <input placeholder={t!("form.email")} />
+            </form>
+        };
+
+        assert_eq!(vec!["form.email", "form.help", "form.title"], keys(code));
+    }
+
+    #[test]
+    fn it_should_collect_keys_nested_within_code() {
+        let code = quote! {
+            <ul>
+                {items.iter().map(|item| rsx! { <li>{t!("item", name = item)}</li> }).collect::<Vec<_>>()}
+                {if done { renderx::t!("done") } else { t!(r"pending") }}
+            </ul>
+        };
+
+        assert_eq!(vec!["done", "item", "pending"], keys(code));
+    }
+
+    #[test]
+    fn it_should_skip_keys_which_are_not_literals() {
+        let code = quote! {
+            <p>{t!(key)}{format!("{}", t)}</p>
+        };
+
+        assert_eq!(Vec::<String>::new(), keys(code));
+    }
+
+    #[test]
+    fn it_should_merge_keys_into_the_file() {
+        let path = env::temp_dir().join(format!(
+            "renderx-translation-keys-{}.txt",
+            ::std::process::id()
+        ));
+        fs::write(&path, "b\nd\n").unwrap();
+
+        let keys = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+        add_keys_to_file(&path, keys.into_iter().collect()).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!("a\nb\nc\nd\n", contents);
+    }
+
+    #[test]
+    fn it_should_keep_the_keys_of_every_writer() {
+        let path = env::temp_dir().join(format!(
+            "renderx-translation-keys-parallel-{}.txt",
+            ::std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let writers = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    let keys = (0..20).map(|j| format!("key.{}.{}", i, j)).collect();
+                    add_keys_to_file(&path, keys).unwrap();
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(8 * 20, contents.lines().count());
+    }
+}
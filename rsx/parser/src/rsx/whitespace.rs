@@ -0,0 +1,421 @@
+//! The whitespace model for text written within `rsx!`.
+//!
+//! Text is not a string in Rust; it's a series of tokens.
+//! To get back the text the author wrote, the spacing between tokens
+//! is worked out from where each token sits in the source code.
+//!
+//!  1. Within a run of text, tokens on the same line keep the exact spacing between them.
+//!     i.e. `Hello , world` stays as `Hello , world`, and `a - b` stays as `a - b`.
+//!  2. Within a run of text, a gap which crosses a newline becomes a single space.
+//!     Lines are trimmed, and then joined together, in the same way as JSX.
+//!  3. Between text, tags, and `{code}`, spacing on the same line is kept.
+//!     i.e. `<p>Hello <b>world</b></p>` keeps the space after `Hello`.
+//!  4. Between text, tags, and `{code}`, spacing which crosses a newline is removed.
+//!     i.e. the newlines and indentation around `<h1>\n  Hello\n</h1>` disappear.
+//!  5. To force a space where rule 4 would remove it, write `{" "}`.
+//!
+//! When the source positions are not available (i.e. tokens made by another macro),
+//! spacing within text falls back to a set of rules based on punctuation,
+//! and spacing between text, tags, and `{code}` is removed.
+
+use crate::rsx::entities::decode_entities;
+use crate::rsx::Error;
+use crate::rsx::Result;
+use crate::rsx::UnknownEntityError;
+use ::proc_macro2::LineColumn;
+use ::proc_macro2::Spacing;
+use ::proc_macro2::Span;
+use ::proc_macro2::TokenTree;
+use ::syn::Lit;
+
+/// The space found between two tokens in the source code.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gap {
+    /// The source positions are not known.
+    Unknown,
+    /// The tokens are on the same line, with this many characters between them.
+    Spaces(usize),
+    /// The tokens are on different lines.
+    NewLine,
+}
+
+/// Works out the gap between the end of `left`, and the start of `right`.
+pub fn gap_between(left: Span, right: Span) -> Gap {
+    let (left_start, left_end) = (left.start(), left.end());
+    let (right_start, right_end) = (right.start(), right.end());
+
+    // Empty spans carry no position, and out of order spans are from another macro.
+    if is_before_or_equal(left_end, left_start)
+        || is_before_or_equal(right_end, right_start)
+        || is_before(right_start, left_end)
+    {
+        return Gap::Unknown;
+    }
+
+    if left_end.line == right_start.line {
+        Gap::Spaces(right_start.column - left_end.column)
+    } else {
+        Gap::NewLine
+    }
+}
+
+/// Returns the spaces to place between text, tags, and `{code}`.
+/// This is only same line spacing (rules 3 and 4 above).
+pub fn edge_spacing(gap: Gap) -> Option<String> {
+    match gap {
+        Gap::Spaces(spaces) if spaces > 0 => Some(" ".repeat(spaces)),
+        _ => None,
+    }
+}
+
+fn is_before(a: LineColumn, b: LineColumn) -> bool {
+    (a.line, a.column) < (b.line, b.column)
+}
+
+fn is_before_or_equal(a: LineColumn, b: LineColumn) -> bool {
+    (a.line, a.column) <= (b.line, b.column)
+}
+
+/// Builds up a run of text from tokens, following rules 1 and 2 above.
+#[derive(Clone, Debug)]
+pub struct TextRun {
+    text: String,
+    last: Option<(Span, SpacingRules)>,
+    /// Where each token starts in the text, so errors can point to the token.
+    spans: Vec<(usize, Span)>,
+}
+
+impl TextRun {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            last: None,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Adds a token to the end of the text.
+    /// Groups are added with their delimiters, i.e. `(Upgrade today!)`.
+    pub fn push_token(&mut self, token: TokenTree) {
+        match token {
+            TokenTree::Ident(ident) => {
+                self.push_piece(&ident.to_string(), ident.span(), (true, true));
+            }
+            TokenTree::Punct(punct) => {
+                let c = punct.as_char();
+                let spacing_rules = match (c, punct.spacing()) {
+                    // The start of a lifetime, which in text is a contraction, i.e. `it's`.
+                    // rustc gives the `'` the span of the whole lifetime, overlapping the word.
+                    ('\'', Spacing::Joint) => (false, false),
+                    _ => char_spacing_rules(c),
+                };
+                self.push_piece(&c.to_string(), punct.span(), spacing_rules);
+            }
+            TokenTree::Literal(literal) => {
+                let span = literal.span();
+                let text = match Lit::new(literal.clone()) {
+                    Lit::Str(text) => text.value(),
+                    _ => literal.to_string(),
+                };
+
+                self.push_piece(&text, span, (true, true));
+            }
+            TokenTree::Group(group) => {
+                let (opening_char, closing_char) = delimiter_chars(group.delimiter());
+
+                self.push_piece(
+                    &opening_char.to_string(),
+                    group.span_open(),
+                    char_spacing_rules(opening_char),
+                );
+                for token in group.stream() {
+                    self.push_token(token);
+                }
+                self.push_piece(
+                    &closing_char.to_string(),
+                    group.span_close(),
+                    char_spacing_rules(closing_char),
+                );
+            }
+        }
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns the text with HTML entities decoded, i.e. `&copy;` becomes `©`.
+    pub fn into_decoded_string(self) -> Result<String> {
+        decode_entities(&self.text).map_err(|err| {
+            // Point to the name of the entity, which follows the `&`.
+            let span = self.span_at(err.offset + "&".len());
+            Error::UnknownEntity(UnknownEntityError {
+                entity: err.entity,
+                span,
+            })
+        })
+    }
+
+    /// The span of the token which the text at `offset` came from.
+    fn span_at(&self, offset: usize) -> Span {
+        self.spans
+            .iter()
+            .take_while(|(start, _)| *start <= offset)
+            .last()
+            .map(|(_, span)| *span)
+            .unwrap_or_else(Span::call_site)
+    }
+
+    fn push_piece(&mut self, piece: &str, span: Span, spacing_rules: SpacingRules) {
+        if let Some((last_span, last_spacing_rules)) = self.last {
+            match gap_between(last_span, span) {
+                Gap::Spaces(spaces) => self.text.push_str(&" ".repeat(spaces)),
+                Gap::NewLine => self.text.push(' '),
+                Gap::Unknown => {
+                    if last_spacing_rules.1 && spacing_rules.0 {
+                        self.text.push(' ');
+                    }
+                }
+            }
+        }
+
+        self.spans.push((self.text.len(), span));
+        self.text.push_str(piece);
+        self.last = Some((span, spacing_rules));
+    }
+}
+
+/// Whether a token wants a space (before, after) it.
+/// This is only used when source positions are not known.
+type SpacingRules = (bool, bool);
+
+fn char_spacing_rules(c: char) -> SpacingRules {
+    match c {
+        '.' => (false, true),
+        ',' => (false, true),
+        ';' => (false, true),
+        ':' => (false, true),
+        '?' => (false, true),
+        '!' => (false, true),
+        '%' => (false, true),
+        ')' => (false, true),
+        ']' => (false, true),
+        '>' => (false, true),
+        '}' => (false, true),
+        '(' => (true, false),
+        '[' => (true, false),
+        '{' => (true, false),
+        '<' => (true, false),
+        '-' => (false, false),
+        _ => (true, true),
+    }
+}
+
+pub fn delimiter_chars(delimiter: ::proc_macro2::Delimiter) -> (char, char) {
+    match delimiter {
+        ::proc_macro2::Delimiter::Bracket => ('[', ']'),
+        ::proc_macro2::Delimiter::Parenthesis => ('(', ')'),
+        ::proc_macro2::Delimiter::Brace => ('{', '}'),
+        ::proc_macro2::Delimiter::None => (' ', ' '),
+    }
+}
+
+#[cfg(test)]
+mod text_run {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+    use ::proc_macro2::TokenStream;
+    use ::quote::quote;
+    use ::std::str::FromStr;
+
+    fn text_from_source(source: &str) -> String {
+        let mut run = TextRun::new();
+        for token in TokenStream::from_str(source).unwrap() {
+            run.push_token(token);
+        }
+        run.into_string()
+    }
+
+    fn text_from_tokens(tokens: TokenStream) -> String {
+        let mut run = TextRun::new();
+        for token in tokens {
+            run.push_token(token);
+        }
+        run.into_string()
+    }
+
+    #[test]
+    fn it_should_keep_spacing_on_the_same_line() {
+        assert_eq!("Hello   world", text_from_source("Hello   world"));
+    }
+
+    #[test]
+    fn it_should_join_lines_with_a_single_space() {
+        assert_eq!(
+            "Hello world, how are you?",
+            text_from_source("Hello world,\n        how are you?")
+        );
+    }
+
+    #[test]
+    fn it_should_keep_spaces_before_punctuation() {
+        let cases = [
+            "Hello , world",
+            "Hello . world",
+            "Hello ; world",
+            "Hello : world",
+            "Hello ? world",
+            "Hello ! world",
+            "50 % off",
+            "a - b",
+            "a -- b",
+            "a + b = c",
+            "a * b / c",
+            "a < b > c",
+            "a & b | c",
+            "a @ b # c $ d",
+            "a ^ b ~ c",
+        ];
+
+        for case in cases.iter() {
+            assert_eq!(*case, text_from_source(case));
+        }
+    }
+
+    #[test]
+    fn it_should_keep_punctuation_without_spaces() {
+        let cases = [
+            "Hello, world.",
+            "Hello,world",
+            "a-b-c",
+            "sign-up",
+            "50%",
+            "e.g.",
+            "a->b",
+            "a=>b",
+            "a::b",
+            "Why?!",
+            "email@example.com",
+            "#hashtag",
+            "$5.00",
+            "1.5",
+            "it's",
+            "don't",
+            "can't stop",
+            "rock 'n' roll",
+            "'tis",
+        ];
+
+        for case in cases.iter() {
+            assert_eq!(*case, text_from_source(case));
+        }
+    }
+
+    #[test]
+    fn it_should_join_contractions_when_spans_overlap() {
+        let mut tokens = TokenStream::from_str("don't stop")
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // rustc gives both the `'` and the `t` the span of the lifetime `'t`.
+        let lifetime = tokens[1].span().join(tokens[2].span()).unwrap();
+        tokens[1].set_span(lifetime);
+        tokens[2].set_span(lifetime);
+
+        assert_eq!("don't stop", text_from_tokens(tokens.into_iter().collect()));
+    }
+
+    #[test]
+    fn it_should_join_contractions_without_positions() {
+        assert_eq!("it's, don't", text_from_tokens(quote! { it's, don't }));
+    }
+
+    #[test]
+    fn it_should_keep_spacing_within_brackets() {
+        let cases = [
+            "(Upgrade today!)",
+            "( Upgrade today! )",
+            "You should (Upgrade (to something new) today!) if you want to",
+            "[1, 2, 3]",
+            "[ 1 , 2 ]",
+            "f(x)",
+            "list[0]",
+        ];
+
+        for case in cases.iter() {
+            assert_eq!(*case, text_from_source(case));
+        }
+    }
+
+    #[test]
+    fn it_should_join_brackets_across_lines() {
+        assert_eq!(
+            "( Upgrade today! )",
+            text_from_source("(\n    Upgrade today!\n)")
+        );
+    }
+
+    #[test]
+    fn it_should_use_the_contents_of_string_literals() {
+        assert_eq!(
+            "He said \"hi\" , then left",
+            text_from_source(r#"He said "\"hi\"" , then left"#)
+        );
+    }
+
+    #[test]
+    fn it_should_fall_back_to_spacing_rules_without_positions() {
+        assert_eq!(
+            "Hello, world! (a-b) [c] 50%",
+            text_from_tokens(quote! { Hello , world ! (a - b) [c] 50 % })
+        );
+    }
+}
+
+#[cfg(test)]
+mod gap_between {
+    use super::*;
+    use ::proc_macro2::TokenStream;
+    use ::std::str::FromStr;
+
+    fn spans(source: &str) -> Vec<Span> {
+        TokenStream::from_str(source)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.span())
+            .collect()
+    }
+
+    #[test]
+    fn it_should_count_spaces_on_the_same_line() {
+        let spans = spans("a   b");
+        assert_eq!(Gap::Spaces(3), gap_between(spans[0], spans[1]));
+    }
+
+    #[test]
+    fn it_should_find_no_spaces_between_touching_tokens() {
+        let spans = spans("a,");
+        assert_eq!(Gap::Spaces(0), gap_between(spans[0], spans[1]));
+    }
+
+    #[test]
+    fn it_should_find_newlines() {
+        let spans = spans("a\n  b");
+        assert_eq!(Gap::NewLine, gap_between(spans[0], spans[1]));
+    }
+
+    #[test]
+    fn it_should_not_know_the_gap_without_positions() {
+        assert_eq!(
+            Gap::Unknown,
+            gap_between(Span::call_site(), Span::call_site())
+        );
+    }
+
+    #[test]
+    fn it_should_not_know_the_gap_for_out_of_order_spans() {
+        let spans = spans("a b");
+        assert_eq!(Gap::Unknown, gap_between(spans[1], spans[0]));
+    }
+}
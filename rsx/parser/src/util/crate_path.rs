@@ -0,0 +1,24 @@
+use crate::util::tracked_env_var;
+use ::proc_macro2::TokenStream;
+use ::quote::quote;
+use ::std::str::FromStr;
+
+/// The environment variable used to override the crate path
+/// that the generated code points to.
+///
+/// i.e. setting `RENDERX_CRATE=::my_fw::html` in the `[env]` section
+/// of `.cargo/config.toml` makes the macros output `::my_fw::html::dom::Node`.
+pub const CRATE_PATH_ENV: &str = "RENDERX_CRATE";
+
+/// Returns the path used by generated code when none is given to the macro.
+///
+/// This is `::renderx`, unless it is overridden by `RENDERX_CRATE`.
+pub fn default_crate_path() -> TokenStream {
+    match tracked_env_var(CRATE_PATH_ENV) {
+        Some(path) if !path.trim().is_empty() => match TokenStream::from_str(&path) {
+            Ok(stream) => stream,
+            Err(_) => panic!("`{}` is not a valid path, found `{}`", CRATE_PATH_ENV, path),
+        },
+        _ => quote! { ::renderx },
+    }
+}
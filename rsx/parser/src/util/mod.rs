@@ -1,9 +1,11 @@
-mod flatten_non_braces;
+mod crate_path;
 mod micro_vec;
 mod token_iterator;
 mod token_stream_eq;
+mod tracked_env;
 
-pub use self::flatten_non_braces::*;
+pub use self::crate_path::*;
 pub use self::micro_vec::*;
 pub use self::token_iterator::*;
 pub use self::token_stream_eq::*;
+pub use self::tracked_env::*;
@@ -3,6 +3,7 @@ use ::lookahead::Lookahead;
 use ::proc_macro2::Delimiter;
 use ::proc_macro2::Group;
 use ::proc_macro2::Ident;
+use ::proc_macro2::Span;
 use ::proc_macro2::TokenStream;
 use ::proc_macro2::TokenTree;
 use ::std::fmt::Debug;
@@ -19,6 +20,7 @@ pub type Result<N> = ::std::result::Result<N, TokenIteratorError>;
 #[derive(Clone, Debug)]
 pub struct TokenIterator<I: Iterator<Item = TokenTree> + Clone + Debug> {
     iter: Lookahead<I>,
+    last_span: Option<Span>,
 }
 
 impl<I: Iterator<Item = TokenTree> + Clone + Debug> TokenIterator<I> {
@@ -29,9 +31,26 @@ impl<I: Iterator<Item = TokenTree> + Clone + Debug> TokenIterator<I> {
         let iterator = stream.into_iter();
         Self {
             iter: lookahead(iterator),
+            last_span: None,
         }
     }
 
+    /// The span of the last item chomped.
+    pub fn last_span(&self) -> Option<Span> {
+        self.last_span
+    }
+
+    /// Sets what is treated as the last span chomped.
+    /// i.e. The opening delimiter, when iterating over the contents of a group.
+    pub fn set_last_span(&mut self, span: Span) {
+        self.last_span = Some(span);
+    }
+
+    /// The span of the next item.
+    pub fn peek_span(&mut self) -> Option<Span> {
+        self.peek().map(|next| next.span())
+    }
+
     pub fn peek(&mut self) -> Option<&TokenTree> {
         self.iter.lookahead(0)
     }
@@ -99,7 +118,10 @@ impl<I: Iterator<Item = TokenTree> + Clone + Debug> TokenIterator<I> {
             return Err(TokenIteratorError::ChompOnEmptyNode);
         }
 
-        Ok(self.iter.next().unwrap())
+        let next = self.iter.next().unwrap();
+        self.last_span = Some(next.span());
+
+        Ok(next)
     }
 
     pub fn chomp_ident(&mut self) -> Result<Ident> {
@@ -0,0 +1,14 @@
+use ::std::env;
+
+/// Reads an environment variable which changes what the macros output.
+///
+/// When expanding a macro, the variable is read through `proc_macro`,
+/// so Cargo knows to expand it again when the variable changes.
+/// Elsewhere, such as in tests, it is read directly.
+pub fn tracked_env_var(key: &str) -> Option<String> {
+    if ::proc_macro::is_available() {
+        ::proc_macro::tracked::env_var(key).ok()
+    } else {
+        env::var(key).ok()
+    }
+}
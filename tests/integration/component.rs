@@ -66,3 +66,124 @@ fn it_should_render_open_component_with_props() -> Result<(), std::fmt::Error> {
 
     Ok(())
 }
+
+#[test]
+fn it_should_pass_children_to_open_components() -> Result<(), std::fmt::Error> {
+    struct CardProps {
+        title: &'static str,
+        children: Node,
+    }
+
+    #[component]
+    fn Card(props: CardProps) -> Node {
+        rsx! {
+            <section class="card">
+                <h2>{props.title}</h2>
+                {props.children}
+            </section>
+        }
+    }
+
+    let html = render(rsx! {
+        <Card title="News"><p>Hello</p></Card>
+    })?;
+
+    assert_eq!(
+        html,
+        "<section class=\"card\"><h2>News</h2><p>Hello</p></section>"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_should_render_components_using_a_given_crate_path() -> Result<(), std::fmt::Error> {
+    #[component(crate = "::core")]
+    fn HorizontalRule() -> Node {
+        rsx! {
+            crate = ::core;
+            <hr class="horizontal-rule"/>
+        }
+    }
+
+    let html = render(rsx! {
+        crate = ::core;
+        <HorizontalRule />
+    })?;
+
+    assert_eq!(html, "<hr class=\"horizontal-rule\"/>");
+
+    Ok(())
+}
+
+#[test]
+fn it_should_select_elements_from_component_output() {
+    struct MenuProps {
+        active: usize,
+    }
+
+    #[component]
+    fn Menu(props: MenuProps) -> Node {
+        let items = ["Home", "About", "Contact"]
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let class = if i == props.active {
+                    "item active"
+                } else {
+                    "item"
+                };
+                rsx! {
+                    <li class={class}>{*item}</li>
+                }
+            });
+
+        rsx! {
+            <nav>
+              <ul>{items.collect::<Vec<Node>>()}</ul>
+              <button class="btn btn--primary" type="submit">Go</button>
+            </nav>
+        }
+    }
+
+    let node = rsx! {
+        <Menu active={1} />
+    };
+
+    let active = node.select_first("ul > li.active").unwrap();
+    assert_eq!(active.text_content(), "About");
+    assert_eq!(node.select("li.item").len(), 3);
+    assert_eq!(
+        node.select_first("li:nth-child(3)").unwrap().text_content(),
+        "Contact"
+    );
+    assert!(node
+        .select_first("nav button.btn--primary[type=submit]")
+        .is_some());
+    assert!(node.select_first("nav > li").is_none());
+}
+
+#[test]
+#[allow(deprecated)]
+fn it_should_render_types_which_implement_to_child() -> Result<(), std::fmt::Error> {
+    use ::renderx::dom::Child;
+    use ::renderx::dom::ToChild;
+
+    struct Price(u32);
+
+    impl ToChild for Price {
+        fn to_child(self) -> Child {
+            Child::Nodes {
+                nodes: vec![Node::new_text("£"), Node::new_text(self.0.to_string())],
+            }
+        }
+    }
+
+    let html = render(rsx! {
+        <p>"Total: "{Price(12)}</p>
+    })?;
+
+    assert_eq!(html, "<p>Total: £12</p>");
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use ::pretty_assertions::assert_eq;
+use ::renderx::component;
+use ::renderx::dom::Node;
+use ::renderx::render::render;
+use ::renderx::render::RenderError;
+use ::renderx::rsx;
+use ::std::error::Error;
+use ::std::fmt;
+
+#[derive(Debug)]
+struct UserNotFound(u32);
+
+impl fmt::Display for UserNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "user {} not found", self.0)
+    }
+}
+
+impl Error for UserNotFound {}
+
+fn find_user(id: u32) -> Result<&'static str, UserNotFound> {
+    match id {
+        1 => Ok("Anna"),
+        id => Err(UserNotFound(id)),
+    }
+}
+
+struct UserCardProps {
+    id: u32,
+}
+
+#[component]
+fn UserCard(props: UserCardProps) -> Result<Node, UserNotFound> {
+    Ok(rsx! {
+        <b>{find_user(props.id)?}</b>
+    })
+}
+
+#[test]
+fn it_should_render_fallible_components() -> Result<(), std::fmt::Error> {
+    let html = render(rsx! {
+        <p><UserCard id={1} /></p>
+    })?;
+
+    assert_eq!("<p><b>Anna</b></p>", html);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_render_the_fallback_for_errors() -> Result<(), std::fmt::Error> {
+    let html = render(rsx! {
+        <main>
+            <ErrorBoundary fallback={|err| rsx! { <p>{err.to_string()}</p> }}>
+                <UserCard id={1} />
+                <UserCard id={2} />
+            </ErrorBoundary>
+        </main>
+    })?;
+
+    assert_eq!("<main><p>user 2 not found</p></main>", html);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_fail_rendering_errors_outside_of_a_boundary() {
+    let result = render(rsx! {
+        <p><UserCard id={2} /></p>
+    });
+
+    match result {
+        Err(RenderError::Component(err)) => assert_eq!("user 2 not found", err.to_string()),
+        result => panic!("expected a component error, found {:?}", result),
+    }
+}
+
+#[test]
+fn it_should_catch_panics_when_asked() -> Result<(), std::fmt::Error> {
+    #[component]
+    fn Broken() -> Node {
+        panic!("out of cheese")
+    }
+
+    let html = render(rsx! {
+        <ErrorBoundary fallback={|_| rsx! { <p>Sorry</p> }} catch_panics>
+            <Broken />
+        </ErrorBoundary>
+    })?;
+
+    assert_eq!("<p>Sorry</p>", html);
+
+    Ok(())
+}
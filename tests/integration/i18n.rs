@@ -0,0 +1,76 @@
+use ::pretty_assertions::assert_eq;
+use ::renderx::i18n::Catalog;
+use ::renderx::render::render;
+use ::renderx::render::render_translated;
+use ::renderx::rsx;
+use ::renderx::t;
+use ::std::sync::Arc;
+
+fn basket(count: u32) -> ::renderx::dom::Node {
+    rsx! {
+      <html>
+        <body>
+          <input type="search" placeholder={t!("search")} />
+          <p>{t!("basket", count = count)}</p>
+        </body>
+      </html>
+    }
+}
+
+fn catalog() -> Arc<Catalog> {
+    Arc::new(
+        Catalog::new("en-GB")
+            .with_message("search", "Search the shop")
+            .with_message(
+                "basket",
+                "{ $count ->\n [0] Your basket is empty\n [one] One item in your basket\n *[other] { $count } items in your basket\n}",
+            ),
+    )
+}
+
+#[test]
+fn it_should_translate_text_and_attributes() -> Result<(), std::fmt::Error> {
+    assert_eq!(
+        concat!(
+            r#"<html lang="en-GB" dir="ltr"><body><input type="search" placeholder="Search the shop"/>"#,
+            r#"<p>7 items in your basket</p></body></html>"#,
+        ),
+        render_translated(basket(7), catalog())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_should_choose_the_plural_form() -> Result<(), std::fmt::Error> {
+    let basket =
+        |count: u32| render_translated(rsx! { <p>{t!("basket", count = count)}</p> }, catalog());
+
+    assert_eq!("<p>Your basket is empty</p>", basket(0)?);
+    assert_eq!("<p>One item in your basket</p>", basket(1)?);
+    assert_eq!("<p>2 items in your basket</p>", basket(2)?);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_render_keys_without_a_translator() -> Result<(), std::fmt::Error> {
+    assert_eq!(
+        r#"<html><body><input type="search" placeholder="search"/><p>basket</p></body></html>"#,
+        render(basket(2))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_should_set_the_direction_for_right_to_left_languages() -> Result<(), std::fmt::Error> {
+    let catalog = Arc::new(Catalog::new("he").with_message("search", "חיפוש"));
+
+    assert_eq!(
+        r#"<html lang="he" dir="rtl"><body><input type="search" placeholder="חיפוש"/><p>basket</p></body></html>"#,
+        render_translated(basket(2), catalog)?
+    );
+
+    Ok(())
+}
@@ -2,5 +2,9 @@
 #![feature(fn_traits)]
 
 mod component;
+mod error_boundary;
+mod i18n;
 mod page;
 mod render;
+mod sanitize;
+mod testing;
@@ -1,6 +1,8 @@
 use ::classnames::classname;
 use ::core::render::render;
 use ::pretty_assertions::assert_eq;
+use ::renderx::component;
+use ::renderx::dom::Node;
 use ::renderx::rsx;
 
 #[test]
@@ -42,3 +44,125 @@ fn it_should_render_example_front_page() -> Result<(), std::fmt::Error> {
 
     Ok(())
 }
+
+struct LayoutProps {
+    children: Node,
+}
+
+#[component]
+fn Layout(props: LayoutProps) -> Node {
+    rsx! {
+        <!doctype html>
+        <html lang="en">
+          <head>
+            <meta charset="utf-8" />
+            <slot:head />
+          </head>
+          <body>
+            <slot:body />
+            {props.children}
+            <slot:scripts />
+          </body>
+        </html>
+    }
+}
+
+#[component]
+fn Menu() -> Node {
+    rsx! {
+        <slot:head>
+          <link rel="stylesheet" href="/menu.css" />
+        </slot:head>
+        <slot:scripts>
+          <script src="/menu.js"></script>
+        </slot:scripts>
+        <nav>Menu</nav>
+    }
+}
+
+#[test]
+fn it_should_render_pages_within_a_layout() -> Result<(), std::fmt::Error> {
+    let html = render(rsx! {
+        <Layout>
+          <slot:head>
+            <title>Example Page</title>
+            <link rel="stylesheet" href="/menu.css" />
+          </slot:head>
+          <slot:body>
+            <header>
+              <Menu />
+            </header>
+            <footer>
+              <Menu />
+            </footer>
+          </slot:body>
+        </Layout>
+    })?;
+
+    assert_eq!(
+        concat!(
+            "<!doctype html><html lang=\"en\"><head><meta charset=\"utf-8\"/>",
+            "<title>Example Page</title><link rel=\"stylesheet\" href=\"/menu.css\"/></head>",
+            "<body><header><nav>Menu</nav></header><footer><nav>Menu</nav></footer>",
+            "<script src=\"/menu.js\"></script></body></html>",
+        ),
+        html
+    );
+
+    Ok(())
+}
+
+struct DocumentProps {
+    children: Node,
+}
+
+#[component]
+fn Document(props: DocumentProps) -> Node {
+    rsx! {
+        <!doctype html>
+        <html lang="en">
+          <head>
+            <title>Example</title>
+            <meta name="description" content="An example site" />
+          </head>
+          <body>{props.children}</body>
+        </html>
+    }
+}
+
+#[component]
+fn Article() -> Node {
+    rsx! {
+        <slot:head>
+          <title>Hello World</title>
+          <meta property="og:title" content="Hello World" />
+          <meta name="description" content="A first post" />
+          <link rel="canonical" href={"https://example.com/hello-world"} />
+        </slot:head>
+        <article>Hello</article>
+    }
+}
+
+#[test]
+fn it_should_write_head_elements_from_deep_in_the_page() -> Result<(), std::fmt::Error> {
+    let html = render(rsx! {
+        <Document>
+          <main>
+            <Article />
+          </main>
+        </Document>
+    })?;
+
+    assert_eq!(
+        concat!(
+            "<!doctype html><html lang=\"en\"><head><title>Hello World</title>",
+            "<meta name=\"description\" content=\"A first post\"/>",
+            "<meta property=\"og:title\" content=\"Hello World\"/>",
+            "<link rel=\"canonical\" href=\"https://example.com/hello-world\"/></head>",
+            "<body><main><article>Hello</article></main></body></html>",
+        ),
+        html
+    );
+
+    Ok(())
+}
@@ -56,6 +56,18 @@ mod comments {
 
         Ok(())
     }
+
+    #[test]
+    fn it_should_not_let_code_end_comments_early() -> Result<(), std::fmt::Error> {
+        let note = "--><script>alert(1)</script><!--";
+        let html = render(rsx! {
+          <!-- {note} -->
+        })?;
+
+        assert_eq!("<!-- -- ><script>alert(1)</script><!-- -->", html);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -312,6 +324,52 @@ mod attributes {
 
         Ok(())
     }
+
+    #[derive(Copy, Clone, ::renderx::ToAttributeValue)]
+    enum InputType {
+        Email,
+        DatetimeLocal,
+        #[attribute_value(rename = "tel")]
+        Phone,
+    }
+
+    #[test]
+    fn it_should_render_derived_enums_in_kebab_case() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <form>
+            <input type={InputType::Email} />
+            <input type={InputType::DatetimeLocal} />
+            <input type={InputType::Phone} />
+          </form>
+        })?;
+
+        assert_eq!(
+            r#"<form><input type="email"/><input type="datetime-local"/><input type="tel"/></form>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_typed_values() -> Result<(), std::fmt::Error> {
+        let href = ::renderx::dom::Url::parse("/recipes/crème brûlée").unwrap();
+        let width = ::renderx::dom::Precision(100.0 / 3.0, 2);
+        let rows = ::std::num::NonZeroU8::new(3).unwrap();
+        let html = render(rsx! {
+          <a href={href} accesskey={'r'} data-id={u128::MAX} data-width={width} data-rows={rows}></a>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<a href="/recipes/cr%C3%A8me%20br%C3%BBl%C3%A9e" accesskey="r" "#,
+                r#"data-id="340282366920938463463374607431768211455" data-width="33.33" data-rows="3"></a>"#,
+            ),
+            html
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -347,6 +405,36 @@ mod code {
         Ok(())
     }
 
+    #[test]
+    fn it_should_render_numbers_chars_and_bools() -> Result<(), std::fmt::Error> {
+        let count = 3_usize;
+        let price = 4.5_f64;
+        let is_sold_out = false;
+        let html = render(rsx! {
+          <p>{count} items at {'£'}{price}{is_sold_out}</p>
+        })?;
+
+        assert_eq!("<p>3 items at £4.5</p>", html);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_display_types_as_text() -> Result<(), std::fmt::Error> {
+        let address = ::std::net::Ipv4Addr::new(10, 0, 0, 1);
+        let error = ::std::fmt::Error;
+        let html = render(rsx! {
+          <p>{::renderx::dom::display(address)} {::renderx::dom::display(error)}</p>
+        })?;
+
+        assert_eq!(
+            "<p>10.0.0.1 an error occurred when formatting an argument</p>",
+            html
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn it_should_render_with_a_child_of_rsx() -> Result<(), std::fmt::Error> {
         let html = render(rsx! {
@@ -403,3 +491,851 @@ mod code {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod crate_path {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_render_using_a_given_crate_path() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          crate = ::core;
+          <input type="text" disabled />
+        })?;
+
+        assert_eq!("<input type=\"text\" disabled/>", html);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod render_pool {
+    use ::core::render::RenderPool;
+    use ::pretty_assertions::assert_eq;
+    use ::renderx::rsx;
+
+    #[test]
+    fn it_should_render_the_same_html_when_reused() -> Result<(), std::fmt::Error> {
+        let pool = RenderPool::new();
+        let name = "main";
+
+        for _ in 0..3 {
+            let mut render = pool.take();
+            render.render(&rsx! {
+              <{name} class="page">
+                <h1>Hello</h1>
+              </{}>
+            })?;
+
+            assert_eq!(
+                "<main class=\"page\"><h1>Hello</h1></main>",
+                render.as_str()
+            );
+        }
+
+        assert_eq!(1, pool.len());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod raw_text {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_render_inline_json_verbatim() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <script type="application/json">r#"{"title": "a - b", "list": [1, 2.5], "html": "<b>&amp;</b>"}"#</script>
+        })?;
+
+        assert_eq!(
+            r#"<script type="application/json">{"title": "a - b", "list": [1, 2.5], "html": "<b>&amp;</b>"}</script>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_guard_closing_script_tags_in_inline_json() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <script type="application/json">r#"{"html": "</script><script>alert(1)</script>"}"#</script>
+        })?;
+
+        assert_eq!(
+            r#"<script type="application/json">{"html": "<\/script><script>alert(1)<\/script>"}</script>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_guard_closing_script_tags_from_code() -> Result<(), std::fmt::Error> {
+        let json = r#"{"html": "</SCRIPT>"}"#;
+        let html = render(rsx! {
+          <script type="application/json">{json}</script>
+        })?;
+
+        assert_eq!(
+            r#"<script type="application/json">{"html": "<\/SCRIPT>"}</script>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_inline_css_verbatim() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <style>r#"
+            .nav > li:first-child::before { content: "\2014 "; margin: -1px 0 0; }
+            a[href^="https://"] { color: #ff0000; }
+          "#</style>
+        })?;
+
+        assert_eq!(
+            r#"<style>
+            .nav > li:first-child::before { content: "\2014 "; margin: -1px 0 0; }
+            a[href^="https://"] { color: #ff0000; }
+          </style>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_escape_textarea_contents() -> Result<(), std::fmt::Error> {
+        let contents = "</textarea><b>Fish & Chips</b>";
+        let html = render(rsx! {
+          <textarea name="notes">{contents}</textarea>
+        })?;
+
+        assert_eq!(
+            "<textarea name=\"notes\">&lt;/textarea>&lt;b>Fish &amp; Chips&lt;/b></textarea>",
+            html
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod whitespace {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_keep_spacing_around_punctuation() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>Hello , world . a - b , 50 % off ; c : d ! e ? f</p>
+        })?;
+
+        assert_eq!(
+            "<p>Hello , world . a - b , 50 % off ; c : d ! e ? f</p>",
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_keep_punctuation_without_spacing() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>Hello, world. Sign-up today, for 50% off; e.g. email@example.com!</p>
+        })?;
+
+        assert_eq!(
+            "<p>Hello, world. Sign-up today, for 50% off; e.g. email@example.com!</p>",
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_keep_contractions_together() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>It's here, so don't wait. Rock 'n' roll!</p>
+        })?;
+
+        assert_eq!("<p>It's here, so don't wait. Rock 'n' roll!</p>", html);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_keep_spacing_within_brackets() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>You should (Upgrade ( to something new ) today!) [now]</p>
+        })?;
+
+        assert_eq!(
+            "<p>You should (Upgrade ( to something new ) today!) [now]</p>",
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_join_lines_with_a_single_space() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>
+            Hello world,
+            how are
+              you?
+          </p>
+        })?;
+
+        assert_eq!("<p>Hello world, how are you?</p>", html);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_keep_spacing_next_to_tags_on_the_same_line() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>Hello <b>world</b> , how <i>are</i><i>you</i>?</p>
+        })?;
+
+        assert_eq!(
+            "<p>Hello <b>world</b> , how <i>are</i><i>you</i>?</p>",
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_remove_spacing_next_to_tags_across_lines() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>
+            Hello
+            <b>world</b>
+          </p>
+        })?;
+
+        assert_eq!("<p>Hello<b>world</b></p>", html);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_keep_spacing_next_to_code_on_the_same_line() -> Result<(), std::fmt::Error> {
+        let count = "3";
+        let html = render(rsx! {
+          <p>You have {count} items ({count} new)</p>
+        })?;
+
+        assert_eq!("<p>You have 3 items (3 new)</p>", html);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_support_explicit_spaces_across_lines() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p>
+            Hello
+            {" "}
+            <b>world</b>
+          </p>
+        })?;
+
+        assert_eq!("<p>Hello <b>world</b></p>", html);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod parse_html {
+    use super::*;
+    use ::core::parse::parse_html;
+    use ::core::parse::ParseError;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_embed_parsed_html() -> Result<(), ParseError> {
+        let content = parse_html("<p>Fish &amp; Chips<br>&copy; 2021</p>")?;
+        let html = render(rsx! {
+          <article class="cms">{content}</article>
+        })
+        .unwrap();
+
+        assert_eq!(
+            r#"<article class="cms"><p>Fish &amp; Chips<br/>© 2021</p></article>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_the_same_html_when_parsed_again() -> Result<(), ParseError> {
+        let source = r#"<!DOCTYPE html>
+<ul class=list>
+  <li><a href='/a?x=1&y=2' title="a &quot;b&quot;">A &amp; B</a></li>
+  <li><input type=checkbox checked><!--note--></li>
+</ul>"#;
+
+        let first = render(parse_html(source)?).unwrap();
+        let second = render(parse_html(&first)?).unwrap();
+
+        assert_eq!(first, second);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod entities {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_decode_entities_written_in_rsx() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p title="Fish &amp; Chips">Fish &amp; Chips&nbsp;&copy; 2021 &#x2014; &lt;b&gt;</p>
+        })?;
+
+        assert_eq!(
+            "<p title=\"Fish &amp; Chips\">Fish &amp; Chips\u{a0}© 2021 — &lt;b&gt;</p>",
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_escape_text_and_attributes_from_code() -> Result<(), std::fmt::Error> {
+        let title = r#"Say "hi""#;
+        let text = "Fish & <Chips>";
+        let html = render(rsx! {
+          <p title={title}>{text}</p>
+        })?;
+
+        assert_eq!(
+            "<p title=\"Say &quot;hi&quot;\">Fish &amp; &lt;Chips&gt;</p>",
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_escape_pre_rendered_and_dynamic_text_the_same() -> Result<(), std::fmt::Error> {
+        let text = "Fish & Chips";
+        let dynamic = render(rsx! {
+          <p>{text}</p>
+        })?;
+        let pre_rendered = render(rsx! {
+          <p>Fish &amp; Chips</p>
+        })?;
+
+        assert_eq!(dynamic, pre_rendered);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod namespaces {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_render_svg() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <svg viewbox="0 0 10 10">
+            <lineargradient id="fade"><stop offset="0" /></lineargradient>
+            <use xlink:href="#fade"></use>
+          </svg>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 10 10">"#,
+                r#"<linearGradient id="fade"><stop offset="0"/></linearGradient>"#,
+                r##"<use xlink:href="#fade"/>"##,
+                "</svg>",
+            ),
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_mathml() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <math><mi>x</mi><mspace width="1em" /></math>
+        })?;
+
+        assert_eq!(
+            r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mi>x</mi><mspace width="1em"/></math>"#,
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_close_html_elements_written_as_self_closing() -> Result<(), std::fmt::Error> {
+        let size = 10;
+        let html = render(rsx! {
+          <div><span class="icon" /><br/><svg width={size}><rect /></svg></div>
+        })?;
+
+        assert_eq!(
+            r#"<div><span class="icon"></span><br/><svg xmlns="http://www.w3.org/2000/svg" width="10"><rect/></svg></div>"#,
+            html
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod xml {
+    use super::*;
+    use ::core::render::render_xml;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_render_an_rss_feed() -> Result<(), std::fmt::Error> {
+        let site = "https://example.com";
+        let titles = vec!["Fish & Chips", "Pie"];
+        let xml = render_xml(rsx! {
+          <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+            <channel>
+              <title>Recipes</title>
+              <link>{site}</link>
+              <atom:link href="https://example.com/feed.xml" rel="self" />
+              {titles.into_iter().map(|title| rsx! {
+                <item><title>{title}</title><description></description></item>
+              }).collect::<Vec<_>>()}
+            </channel>
+          </rss>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel>"#,
+                "<title>Recipes</title>",
+                "<link>https://example.com</link>",
+                r#"<atom:link href="https://example.com/feed.xml" rel="self"/>"#,
+                "<item><title>Fish &amp; Chips</title><description/></item>",
+                "<item><title>Pie</title><description/></item>",
+                "</channel></rss>",
+            ),
+            xml
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_keep_a_written_xml_declaration() -> Result<(), std::fmt::Error> {
+        let xml = render_xml(rsx! {
+          <?xml version="1.0" encoding="UTF-8"?>
+          <?xml-stylesheet type="text/xsl" href="/sitemap.xsl"?>
+          <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <url><loc>{"https://example.com/"}</loc></url>
+          </urlset>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<?xml-stylesheet type="text/xsl" href="/sitemap.xsl"?>"#,
+                r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+                "<url><loc>https://example.com/</loc></url>",
+                "</urlset>",
+            ),
+            xml
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_the_same_html_as_before() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <p><input disabled /><span></span></p>
+        })?;
+
+        assert_eq!("<p><input disabled/><span></span></p>", html);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_static_elements_as_they_were_written() -> Result<(), std::fmt::Error> {
+        let xml = render_xml(rsx! {
+          <feed>
+            <p><div>"x"</div></p>
+            <title><b>"Fish & Chips"</b></title>
+          </feed>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                "<feed><p><div>x</div></p><title><b>Fish &amp; Chips</b></title></feed>",
+            ),
+            xml
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod cdata {
+    use super::*;
+    use ::core::render::render_xml;
+    use ::pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_should_render_cdata_as_written() -> Result<(), std::fmt::Error> {
+        let summary = "<p>Fish &amp; Chips</p>";
+        let html = render(rsx! {
+          <item>
+            <description><![CDATA[{summary}]]></description>
+            <content><![CDATA[r#"<b>a]]>b</b>"#]]></content>
+          </item>
+        })?;
+
+        assert_eq!(
+            concat!(
+                "<item>",
+                "<description><![CDATA[<p>Fish &amp; Chips</p>]]></description>",
+                "<content><![CDATA[<b>a]]]]><![CDATA[>b</b>]]></content>",
+                "</item>",
+            ),
+            html
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_render_cdata_the_same_in_xml() -> Result<(), std::fmt::Error> {
+        let xml = render_xml(rsx! {
+          <description><![CDATA[r#"<p>a & b</p>"#]]></description>
+        })?;
+
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><description><![CDATA[<p>a & b</p>]]></description>"#,
+            xml
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod traverse {
+    use super::*;
+    use ::core::dom::visit::walk_children_mut;
+    use ::core::dom::visit::VisitMut;
+    use ::core::dom::AttributeValue;
+    use ::core::dom::Node;
+    use ::pretty_assertions::assert_eq;
+
+    fn page(extra: &'static str) -> Node {
+        rsx! {
+          <article>
+            <h1>Title</h1>
+            <p>See <a href="https://example.com">example</a> and <a href="/about">about</a>.</p>
+            <h2>{extra}</h2>
+          </article>
+        }
+    }
+
+    struct NoOpener;
+
+    impl VisitMut for NoOpener {
+        fn visit_element_mut(&mut self, element: &mut Node) {
+            let is_external = matches!(
+                element.attribute("href"),
+                Some(AttributeValue::Text(href)) if href.starts_with("https://")
+            );
+            if is_external {
+                element.set_attribute("rel", "noopener");
+            }
+
+            walk_children_mut(self, element);
+        }
+    }
+
+    #[test]
+    fn it_should_add_attributes_to_rsx_output() -> Result<(), std::fmt::Error> {
+        let mut node = page("More");
+        NoOpener.visit_node_mut(&mut node);
+
+        assert_eq!(
+            r#"<article><h1>Title</h1><p>See <a href="https://example.com" rel="noopener">example</a> and <a href="/about">about</a>.</p><h2>More</h2></article>"#,
+            render(node)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_collect_headings_from_rsx_output() {
+        let node = page("More");
+        let headings: Vec<String> = node
+            .descendants()
+            .filter(|node| matches!(node.name(), Some("h1") | Some("h2")))
+            .map(Node::text_content)
+            .collect();
+
+        assert_eq!(vec!["Title", "More"], headings);
+    }
+
+    #[test]
+    fn it_should_filter_rsx_output() -> Result<(), std::fmt::Error> {
+        let node = page("More").filter(|node| node.name() != Some("a"));
+
+        assert_eq!(
+            "<article><h1>Title</h1><p>See  and .</p><h2>More</h2></article>",
+            render(node)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_traverse_static_html_as_it_was_written() {
+        let node = rsx! {
+          <p><div>"x"</div></p>
+        };
+        assert!(matches!(node, Node::Static { .. }));
+
+        assert_eq!(Some("p"), node.name());
+        assert_eq!(
+            vec![Some("div")],
+            node.children().map(Node::name).collect::<Vec<_>>()
+        );
+
+        let node = rsx! {
+          <p>"a"<p>"b"</p></p>
+        };
+        let child = node.children().nth(1).unwrap();
+        assert_eq!(Some("p"), child.name());
+        assert_eq!("b", child.text_content());
+    }
+}
+
+#[cfg(test)]
+mod normalize {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+    use ::renderx::dom::Node;
+
+    #[test]
+    fn it_should_build_canonical_trees() -> Result<(), std::fmt::Error> {
+        let name = "Anna";
+        let badge: Option<Node> = None;
+        let node = rsx! {
+          <p>Hello {name}{badge}<></>!</p>
+        };
+
+        assert_eq!(
+            vec![Some("Hello Anna!")],
+            node.children().map(Node::text).collect::<Vec<_>>()
+        );
+        assert_eq!("<p>Hello Anna!</p>", render(&node)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_normalize_changed_trees() -> Result<(), std::fmt::Error> {
+        let mut node = rsx! {
+          <ul>{"a"}</ul>
+        };
+        let children = node.children_mut().unwrap();
+        children.push(Node::Empty);
+        children.push(Node::new_text("b"));
+        assert_eq!(2, node.children().count());
+
+        node.normalize();
+        assert_eq!(
+            vec![Some("ab")],
+            node.children().map(Node::text).collect::<Vec<_>>()
+        );
+        assert_eq!("<ul>ab</ul>", render(&node)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod render_hooks {
+    use ::core::render::ElementStart;
+    use ::core::render::Render;
+    use ::core::render::RenderHook;
+    use ::pretty_assertions::assert_eq;
+    use ::renderx::rsx;
+    use ::std::sync::Arc;
+
+    #[derive(Debug)]
+    struct ScriptNonce;
+
+    impl RenderHook for ScriptNonce {
+        fn on_element(&self, element: &mut ElementStart<'_>) {
+            if element.name() == "script" {
+                element.set_attribute("nonce", "r4nd0m");
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct LazyImages;
+
+    impl RenderHook for LazyImages {
+        fn on_element(&self, element: &mut ElementStart<'_>) {
+            if element.name() == "img" && !element.has_attribute("loading") {
+                element.set_attribute("loading", "lazy");
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_change_elements_without_changing_components() -> Result<(), std::fmt::Error> {
+        let alt = "A cat";
+        let mut render = Render::new();
+        render.add_hook(Arc::new(ScriptNonce));
+        render.add_hook(Arc::new(LazyImages));
+        render.render(&rsx! {
+          <main>
+            <img src="/cat.png" alt={alt} />
+            <img src="/logo.png" loading="eager" />
+            <script src="/app.js"></script>
+          </main>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<main><img src="/cat.png" alt="A cat" loading="lazy"/>"#,
+                r#"<img src="/logo.png" loading="eager"/>"#,
+                r#"<script src="/app.js" nonce="r4nd0m"></script></main>"#,
+            ),
+            render.as_str()
+        );
+
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    struct NoOp;
+
+    impl RenderHook for NoOp {
+        fn on_element(&self, _element: &mut ElementStart<'_>) {}
+    }
+
+    #[test]
+    fn it_should_render_static_html_the_same_with_a_hook_which_does_nothing(
+    ) -> Result<(), std::fmt::Error> {
+        let pages = vec![
+            rsx! { <p><div>"x"</div></p> },
+            rsx! { <p>"a"<p>"b"</p></p> },
+            rsx! { <title><b>"Fish & Chips"</b></title> },
+            rsx! { <ul><li>"a"<li>"b"</li></li></ul> },
+        ];
+
+        for page in pages {
+            let mut render = Render::new();
+            render.add_hook(Arc::new(NoOp));
+            render.render(&page)?;
+
+            assert_eq!(::core::render::render(&page)?, render.as_str());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod prerender {
+    use super::*;
+    use ::pretty_assertions::assert_eq;
+    use ::renderx::dom::Attribute;
+    use ::renderx::dom::AttributeValue;
+    use ::renderx::dom::Node;
+
+    #[test]
+    fn it_should_prerender_the_same_html_as_at_runtime() -> Result<(), std::fmt::Error> {
+        let prerendered = rsx! {
+          <div title="Fish & 'Chips' <b>">
+            <p>r#"a < b && c > d"#</p>
+            <script>r#"x = "</script><b>";"#</script>
+            <style>r#"a > b { content: "&"; }"#</style>
+            <textarea>r#"</textarea> & <b>"#</textarea>
+            <!-- r#"a <b> & c"# -->
+            <![CDATA[r#"a ]]> b"#]]>
+          </div>
+        };
+        assert!(matches!(prerendered, Node::Static { .. }));
+
+        let title = Attribute::new("title", AttributeValue::Text("Fish & 'Chips' <b>".into()));
+        let runtime = Node::new_open(
+            "div",
+            Some(vec![title].into()),
+            Some(vec![
+                Node::new_open("p", None, Some("a < b && c > d")),
+                Node::new_open("script", None, Some(r#"x = "</script><b>";"#)),
+                Node::new_open("style", None, Some(r#"a > b { content: "&"; }"#)),
+                Node::new_open("textarea", None, Some("</textarea> & <b>")),
+                Node::Comment {
+                    children: Some(vec![Node::new_text("a <b> & c")]),
+                },
+                Node::CData {
+                    children: Some(vec![Node::new_text("a ]]> b")]),
+                },
+            ]),
+        );
+
+        assert_eq!(render(&runtime)?, render(&prerendered)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod head {
+    use super::*;
+    use ::renderx::t;
+
+    #[test]
+    fn it_should_replace_meta_by_the_rendered_value_of_code() -> Result<(), std::fmt::Error> {
+        let html = render(rsx! {
+          <head>
+            <meta name={2024} content="a" />
+            <meta name={t!("author")} content="Anna" />
+            <meta name={t!("keywords")} content="soup" />
+            <meta name="2024" content="b" />
+          </head>
+        })?;
+
+        assert_eq!(
+            concat!(
+                r#"<head><meta name="2024" content="b"/><meta name="author" content="Anna"/>"#,
+                r#"<meta name="keywords" content="soup"/></head>"#,
+            ),
+            html
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use ::core::dom::Node;
+use ::core::parse::parse_html;
+use ::core::render::render;
+use ::core::sanitize::Sanitizer;
+use ::pretty_assertions::assert_eq;
+use ::renderx::rsx;
+
+/// Known XSS vectors, and what they should be sanitized to with the default policy.
+///
+/// Many of these come from the OWASP XSS filter evasion cheat sheet.
+const XSS_CORPUS: &[(&str, &str)] = &[
+    ("<script>alert(1)</script>", ""),
+    ("<SCRIPT SRC=//example.com/xss.js></SCRIPT>", ""),
+    ("<ScRiPt>alert(1)</sCrIpT>", ""),
+    ("<p>a<script>alert(1)</script>b</p>", "<p>ab</p>"),
+    ("<img src=x onerror=alert(1)>", r#"<img src="x"/>"#),
+    ("<img src=x ONERROR=alert(1)>", r#"<img src="x"/>"#),
+    (r#"<img src="javascript:alert(1)">"#, "<img/>"),
+    (r#"<img src=" javascript:alert(1)">"#, "<img/>"),
+    (r#"<img src="JaVaScRiPt:alert(1)">"#, "<img/>"),
+    ("<img src=\"jav\tascript:alert(1)\">", "<img/>"),
+    (r#"<img src="jav&#x09;ascript:alert(1)">"#, "<img/>"),
+    (r#"<img src="jav&#x0A;ascript:alert(1)">"#, "<img/>"),
+    (
+        r#"<img src="&#106;&#97;&#118;&#97;&#115;&#99;&#114;&#105;&#112;&#116;&#58;alert(1)">"#,
+        "<img/>",
+    ),
+    (
+        r#"<img src="&#0000106&#0000097&#0000118&#0000097&#0000115&#0000099&#0000114&#0000105&#0000112&#0000116&#0000058alert(1)">"#,
+        "<img/>",
+    ),
+    (
+        r#"<img src="&#x6A&#x61&#x76&#x61&#x73&#x63&#x72&#x69&#x70&#x74&#x3A;alert(1)">"#,
+        "<img/>",
+    ),
+    (r#"<a href="javascript&colon;alert(1)">x</a>"#, "<a>x</a>"),
+    (r#"<a href="vbscript:msgbox(1)">x</a>"#, "<a>x</a>"),
+    (
+        r#"<a href="data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==">x</a>"#,
+        "<a>x</a>",
+    ),
+    (
+        r#"<a href="https://example.com">x</a>"#,
+        r#"<a href="https://example.com">x</a>"#,
+    ),
+    (
+        r#"<a href="/a?b=javascript:c">x</a>"#,
+        r#"<a href="/a?b=javascript:c">x</a>"#,
+    ),
+    (
+        r#"<a href="x" onmouseover="alert(1)">x</a>"#,
+        r#"<a href="x">x</a>"#,
+    ),
+    (
+        r#"<a style="background:url(javascript:alert(1))">x</a>"#,
+        "<a>x</a>",
+    ),
+    ("<svg/onload=alert(1)></svg>", ""),
+    ("<svg><script>alert(1)</script></svg>", ""),
+    (
+        "<math><mi xlink:href=\"javascript:alert(1)\">x</mi></math>",
+        "",
+    ),
+    ("<body onload=alert(1)>x</body>", "x"),
+    ("<iframe src=javascript:alert(1)></iframe>", ""),
+    ("<iframe srcdoc=\"<script>alert(1)</script>\"></iframe>", ""),
+    ("<object data=javascript:alert(1)></object>", ""),
+    ("<embed src=javascript:alert(1)>", ""),
+    (
+        "<form action=javascript:alert(1)><button>x</button></form>",
+        "x",
+    ),
+    ("<input onfocus=alert(1) autofocus>", ""),
+    ("<details open ontoggle=alert(1)>x</details>", "x"),
+    ("<video><source onerror=alert(1)></video>", ""),
+    ("<style>@import 'javascript:alert(1)';</style>", ""),
+    (
+        "<div style=\"width: expression(alert(1))\">x</div>",
+        "<div>x</div>",
+    ),
+    (
+        "<meta http-equiv=refresh content=\"0;url=javascript:alert(1)\">",
+        "",
+    ),
+    ("<link rel=stylesheet href=javascript:alert(1)>", ""),
+    ("<base href=javascript:alert(1)//>", ""),
+    (
+        "<table background=javascript:alert(1)><tr><td>x</td></tr></table>",
+        "<table><tr><td>x</td></tr></table>",
+    ),
+    (
+        "<!--[if gte IE 4]><script>alert(1)</script><![endif]-->",
+        "",
+    ),
+    (
+        "<noscript><p title=\"</noscript><img src=x onerror=alert(1)>\"></p></noscript>",
+        "",
+    ),
+    ("<textarea><script>alert(1)</script></textarea>", ""),
+    ("<title><script>alert(1)</script></title>", ""),
+    ("<template><script>alert(1)</script></template>", ""),
+    (
+        "<p title=\"&quot;><script>alert(1)</script>\">x</p>",
+        "<p>x</p>",
+    ),
+    (
+        "a &lt;script&gt;alert(1)&lt;/script&gt; b",
+        "a &lt;script&gt;alert(1)&lt;/script&gt; b",
+    ),
+];
+
+fn sanitize_html(html: &str) -> String {
+    let node = parse_html(html).unwrap_or_else(|err| panic!("{} failed to parse, {}", html, err));
+    render(Sanitizer::default().sanitize(node)).unwrap()
+}
+
+#[test]
+fn it_should_sanitize_known_xss_vectors() {
+    for (html, expected) in XSS_CORPUS {
+        assert_eq!(*expected, sanitize_html(html), "sanitizing {}", html);
+    }
+}
+
+#[test]
+fn it_should_keep_text_given_to_rsx_as_text() -> Result<(), std::fmt::Error> {
+    let comment = "Nice post!<img src=x onerror=alert(1)>";
+    let node = Sanitizer::default().sanitize(rsx! {
+      <div class="comment">{comment}</div>
+    });
+
+    assert_eq!(
+        "<div>Nice post!&lt;img src=x onerror=alert(1)&gt;</div>",
+        render(node)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_should_sanitize_pre_rendered_html() -> Result<(), std::fmt::Error> {
+    let node: Node = rsx! {
+      <div onclick="alert(1)"><p>Hello</p></div>
+    };
+    let node = Sanitizer::default().sanitize(node);
+
+    assert_eq!("<div><p>Hello</p></div>", render(node)?);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_keep_what_a_custom_policy_allows() -> Result<(), std::fmt::Error> {
+    let sanitizer = Sanitizer::new()
+        .allow_tags(&["a", "p"])
+        .allow_attributes("a", &["href", "rel"])
+        .allow_global_attributes(&["class"])
+        .allow_url_schemes(&["https"]);
+    let node = parse_html(
+        r#"<p class="intro"><a href="https://example.com" rel="nofollow" id="x">a</a><a href="http://example.com">b</a></p>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        r#"<p class="intro"><a href="https://example.com" rel="nofollow">a</a><a>b</a></p>"#,
+        render(sanitizer.sanitize(node))?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use ::pretty_assertions::assert_eq;
+use ::renderx::component;
+use ::renderx::dom::Node;
+use ::renderx::rsx;
+use ::renderx::testing::render_component;
+use ::renderx::testing::Rendered;
+
+struct SignUpProps {
+    error: Option<&'static str>,
+}
+
+#[component]
+fn SignUp(props: SignUpProps) -> Node {
+    let error = props.error.map(|error| {
+        rsx! {
+          <p class="error" role="alert">{error}</p>
+        }
+    });
+
+    rsx! {
+      <form method="post" action="/sign-up">
+        <h1>Sign up</h1>
+        {error}
+        <input type="email" name="email" data-testid="email" />
+        <button type="submit" class="btn btn--primary">Create account</button>
+      </form>
+    }
+}
+
+#[test]
+fn it_should_find_elements_in_a_component() {
+    let page = render_component(SignUp, SignUpProps { error: None });
+
+    let button = page.find_by_role("button").unwrap();
+    assert_eq!(button.text_content(), "Create account");
+    assert!(page.find_by_text("Create account").is_some());
+    assert!(page.find_by_test_id("email").is_some());
+    assert!(page.find_by_role("alert").is_none());
+    assert!(page
+        .node()
+        .select_first("form > button.btn--primary")
+        .is_some());
+}
+
+#[test]
+fn it_should_pretty_print_components() {
+    let page = render_component(
+        SignUp,
+        SignUpProps {
+            error: Some("That email is taken"),
+        },
+    );
+
+    assert_eq!(
+        page.pretty(),
+        r#"<form action="/sign-up" method="post">
+  <h1>Sign up</h1>
+  <p class="error" role="alert">That email is taken</p>
+  <input data-testid="email" name="email" type="email"/>
+  <button class="btn btn--primary" type="submit">Create account</button>
+</form>
+"#
+    );
+}
+
+#[test]
+fn it_should_match_snapshots() {
+    let page = render_component(
+        SignUp,
+        SignUpProps {
+            error: Some("That email is taken"),
+        },
+    );
+
+    page.assert_snapshot("sign_up_with_error");
+}
+
+#[test]
+fn it_should_wrap_rsx_output() {
+    let page = Rendered::new(rsx! {
+      <ul><li>a</li><li>b</li></ul>
+    });
+
+    assert_eq!(page.find_all_by_role("listitem").len(), 2);
+    assert_eq!(page.html(), "<ul><li>a</li><li>b</li></ul>");
+}